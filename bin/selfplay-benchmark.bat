@@ -3,16 +3,8 @@ REM configuration
 set threads=8
 set size=13
 
-REM set up the engines, use random for the fastest possible test
-set iomrascalai=..\target\release\iomrascalai.exe -t %threads% --play-out-aftermath true
-set random=%iomrascalai% -e random
+REM the options of the second engine (B), e.g. --use-nakade-in-playouts false
+set opponent=
 
-REM set up which color each engine plays
-set black=%iomrascalai%
-set white=%random%
-
-REM set up the script to run both engines against each other, change the path to the gogui-gtp executable if it's different for you
-set twogtp="C:\Program Files (x86)\GoGui\gogui-twogtp"
-set test=%twogtp% -black "%black%" -white "%white%" -verbose -size %size%  -time 10m -games 100 -sgffile ../selfplay-benchmark -auto -komi 7.5
-
-%test%
\ No newline at end of file
+mkdir ..\selfplay-benchmark
+..\target\release\iomrascalai.exe -t %threads% match --games 100 --board-size %size% --komi 7.5 --move-time 2000 --opponent "-t %threads% %opponent%" --sgf-dir ..\selfplay-benchmark
@@ -0,0 +1,10 @@
+#!/bin/sh
+# A minimal GTP engine that always passes and rejects every move.
+while read command args; do
+    case $command in
+        genmove) printf '= pass\n\n' ;;
+        play) printf '? illegal move\n\n' ;;
+        quit) printf '=\n\n'; exit 0 ;;
+        *) printf '=\n\n' ;;
+    esac
+done
@@ -0,0 +1,18 @@
+# A small suite for the regression runner tests
+
+loadsgf ../sgf/life-and-death/dead.sgf
+1 group_status c9
+#? [dead]
+
+2 group_status c9
+#? [!alive|unknown]
+
+# The solver can't read out an empty point
+3 group_status a1
+#? [alive]*
+
+4 final_status_list dead
+#? [A8 B8 C8 C9]
+
+5 group_status c9
+#? [alive]
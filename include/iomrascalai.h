@@ -0,0 +1,51 @@
+#ifndef IOMRASCALAI_H
+#define IOMRASCALAI_H
+
+/* Generated by cbindgen from src/ffi/mod.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define IOM_EMPTY 0
+
+#define IOM_BLACK 1
+
+#define IOM_WHITE 2
+
+#define IOM_OK 0
+
+#define IOM_RESIGN 1
+
+#define IOM_ILLEGAL_MOVE -1
+
+#define IOM_INVALID_ARGUMENT -2
+
+#define IOM_INTERNAL_ERROR -3
+
+typedef struct IomGame IomGame;
+
+typedef struct IomVertex {
+  uint8_t col;
+  uint8_t row;
+} IomVertex;
+
+IomGame *iom_game_new(uint8_t width, uint8_t height, float komi);
+
+void iom_game_free(IomGame *game);
+
+int iom_play(IomGame *game, int color, IomVertex vertex);
+
+int iom_next_player(const IomGame *game);
+
+size_t iom_legal_moves(const IomGame *game, IomVertex *vertices, size_t len);
+
+int iom_genmove(IomGame *game, int color, uint32_t time_limit, IomVertex *vertex);
+
+float iom_score(const IomGame *game);
+
+size_t iom_ownership(const IomGame *game, int *owners, size_t len);
+
+#endif /* IOMRASCALAI_H */
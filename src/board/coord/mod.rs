@@ -35,24 +35,24 @@ impl Coord {
         Coord {col: col, row: row}
     }
 
-    pub fn for_board_size(size: u8) -> Vec<Coord> {
+    pub fn for_board_size(width: u8, height: u8) -> Vec<Coord> {
         let mut coords = Vec::new();
-        for i in 0..size {
-            for j in 0..size {
+        for i in 0..height {
+            for j in 0..width {
                 coords.push(Coord::new(j+1, i+1));
             }
         }
         coords
     }
 
-    pub fn neighbours(&self, board_size: u8) -> Vec<Coord> {
+    pub fn neighbours(&self, width: u8, height: u8) -> Vec<Coord> {
         let mut neighbours = Vec::new();
 
         for i in -1i8..2 {
             for j in -1i8..2 {
                 let (col, row) = (self.col as i8 + i, self.row as i8 + j);
                 let potential_neighbour = Coord::new(col as u8, row as u8);
-                if ((i == 0 && j !=0) || (i != 0 && j == 0)) && (potential_neighbour.is_inside(board_size)) {
+                if ((i == 0 && j !=0) || (i != 0 && j == 0)) && (potential_neighbour.is_inside(width, height)) {
                     neighbours.push(potential_neighbour);
                 }
             }
@@ -60,14 +60,14 @@ impl Coord {
         neighbours
     }
 
-    pub fn diagonals(&self, board_size: u8) -> Vec<Coord> {
+    pub fn diagonals(&self, width: u8, height: u8) -> Vec<Coord> {
         vec!(
             Coord::new(self.col-1, self.row-1),
             Coord::new(self.col+1, self.row-1),
             Coord::new(self.col+1, self.row+1),
             Coord::new(self.col-1, self.row+1)
                 ).iter()
-            .filter(|c| c.is_inside(board_size))
+            .filter(|c| c.is_inside(width, height))
             .cloned()
             .collect()
     }
@@ -85,22 +85,22 @@ impl Coord {
             Coord::new(self.col-1, self.row))   // W
     }
 
-    pub fn to_index(&self, board_size: u8) -> usize {
-        (self.col as usize-1 + (self.row as usize-1)*board_size as usize)
+    pub fn to_index(&self, width: u8) -> usize {
+        (self.col as usize-1 + (self.row as usize-1)*width as usize)
     }
 
-    pub fn is_inside(&self, board_size: u8) -> bool {
-        1 <= self.col && self.col <= board_size && 1 <= self.row && self.row <= board_size
+    pub fn is_inside(&self, width: u8, height: u8) -> bool {
+        1 <= self.col && self.col <= width && 1 <= self.row && self.row <= height
     }
 
-    pub fn distance_to_border(&self, board_size: u8) -> u8 {
-        *[self.col-1, self.row-1, board_size - self.col, board_size - self.row]
+    pub fn distance_to_border(&self, width: u8, height: u8) -> u8 {
+        *[self.col-1, self.row-1, width - self.col, height - self.row]
             .iter()
             .min()
             .unwrap()
     }
 
-    pub fn manhattan_distance_three_neighbours(&self, board_size: u8) -> Vec<Coord> {
+    pub fn manhattan_distance_three_neighbours(&self, width: u8, height: u8) -> Vec<Coord> {
         let offsets = vec!(
                                       (0, 3),
                              (-1, 2), (0, 2), (1, 2),
@@ -114,7 +114,7 @@ impl Coord {
             .map(|&(co,ro)| (self.col as isize + co, self.row as isize + ro))
             .filter(|&(co,ro)| co > 0 && ro > 0)
             .map(|(c,r)| Coord::new(c as u8,r as u8))
-            .filter(|c| c.is_inside(board_size))
+            .filter(|c| c.is_inside(width, height))
             .collect()
     }
 
@@ -133,6 +133,23 @@ impl Coord {
         Coord::new(col, row)
     }
 
+    // Like from_gtp, but doesn't panic on malformed vertices and
+    // rejects the letter I as well as vertices off the board.
+    pub fn parse_gtp(gtp_vertex: &str, width: u8, height: u8) -> Option<Coord> {
+        let valid = gtp_vertex.len() >= 2 &&
+            gtp_vertex.chars().next().map(|c| ((c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z')) && c != 'i' && c != 'I').unwrap_or(false) &&
+            gtp_vertex[1..].parse::<u8>().is_ok();
+        if !valid {
+            return None;
+        }
+        let coord = Coord::from_gtp(gtp_vertex);
+        if coord.is_inside(width, height) {
+            Some(coord)
+        } else {
+            None
+        }
+    }
+
     // Note: there is no I column.
     pub fn to_gtp(&self) -> String {
         let gtp_col = if self.col <= 8 {
@@ -24,7 +24,7 @@ use super::Coord;
 
 #[test]
 fn test_neighbours_contain_n_s_e_w() {
-  let n = Coord::new(10,10).neighbours(19);
+  let n = Coord::new(10,10).neighbours(19, 19);
 
   assert!(n.iter().find(|c| c.col == 10 && c.row == 9 ).is_some());
   assert!(n.iter().find(|c| c.col == 9  && c.row == 10).is_some());
@@ -34,7 +34,7 @@ fn test_neighbours_contain_n_s_e_w() {
 
 #[test]
 fn test_neighbours_do_not_contain_diagonals() {
-  let n = Coord::new(10,10).neighbours(19);
+  let n = Coord::new(10,10).neighbours(19, 19);
 
   assert!(n.iter().find(|c| c.col == 11 && c.row == 11).is_none());
   assert!(n.iter().find(|c| c.col == 9  && c.row == 11).is_none());
@@ -44,29 +44,35 @@ fn test_neighbours_do_not_contain_diagonals() {
 
 #[test]
 fn test_neighbours_do_not_contain_itself() {
-  let n = Coord::new(10,10).neighbours(19);
+  let n = Coord::new(10,10).neighbours(19, 19);
 
   assert!(n.iter().find(|c| c.col == 10 && c.row == 10).is_none());
 }
 
 #[test]
 fn is_inside_valid_coords_pass() {
-  assert!(Coord::new(1,1).is_inside(19));
-  assert!(Coord::new(19,19).is_inside(19));
-  assert!(Coord::new(10,10).is_inside(19));
+  assert!(Coord::new(1,1).is_inside(19, 19));
+  assert!(Coord::new(19,19).is_inside(19, 19));
+  assert!(Coord::new(10,10).is_inside(19, 19));
 }
 
 #[test]
 fn is_inside_0_0_fails() {
-  assert!(!Coord::new(0,0).is_inside(19));
+  assert!(!Coord::new(0,0).is_inside(19, 19));
 }
 
 #[test]
 fn is_inside_invalid_coords_fail() {
-  assert!(!Coord::new(4,21).is_inside(19));
-  assert!(!Coord::new(21,4).is_inside(19));
+  assert!(!Coord::new(4,21).is_inside(19, 19));
+  assert!(!Coord::new(21,4).is_inside(19, 19));
 
-  assert!(!Coord::new(18,18).is_inside(9));
+  assert!(!Coord::new(18,18).is_inside(9, 9));
+}
+
+#[test]
+fn is_inside_a_rectangular_board() {
+  assert!(Coord::new(13,9).is_inside(13, 9));
+  assert!(!Coord::new(9,13).is_inside(13, 9));
 }
 
 #[test]
@@ -81,6 +87,25 @@ fn from_gtp_converts_correctly() {
 
   assert_eq!(Coord::new(9,10), Coord::from_gtp("J10"));
   assert_eq!(Coord::new(8,10), Coord::from_gtp("H10"));
+
+  assert_eq!(Coord::new(25,25), Coord::from_gtp("Z25"));
+}
+
+#[test]
+fn parse_gtp_rejects_the_letter_i() {
+  assert_eq!(None, Coord::parse_gtp("I5", 19, 19));
+  assert_eq!(None, Coord::parse_gtp("i5", 19, 19));
+  assert_eq!(Some(Coord::new(9,5)), Coord::parse_gtp("J5", 19, 19));
+}
+
+#[test]
+fn parse_gtp_rejects_vertices_off_the_board() {
+  assert_eq!(Some(Coord::new(9,9)), Coord::parse_gtp("J9", 9, 9));
+  assert_eq!(None, Coord::parse_gtp("K9", 9, 9));
+  assert_eq!(None, Coord::parse_gtp("J10", 9, 9));
+  assert_eq!(None, Coord::parse_gtp("A0", 9, 9));
+  assert_eq!(Some(Coord::new(13,5)), Coord::parse_gtp("N5", 13, 5));
+  assert_eq!(None, Coord::parse_gtp("E6", 13, 5));
 }
 
 #[test]
@@ -91,41 +116,55 @@ fn to_gtp_converts_correctly() {
   assert_eq!(Coord::new(19,19).to_gtp(), String::from("T19"));
   assert_eq!(Coord::new(9,10).to_gtp(), String::from("J10"));
   assert_eq!(Coord::new(8,10).to_gtp(), String::from("H10"));
+  assert_eq!(Coord::new(25,25).to_gtp(), String::from("Z25"));
 }
 
 #[test]
 fn for_board_size_returns_the_right_number_of_coords() {
-    let coords = Coord::for_board_size(3);
+    let coords = Coord::for_board_size(3, 3);
     assert_eq!(9, coords.len());
 }
 
 #[test]
 fn for_board_size_sets_the_coordinates_correctly() {
-    let coords = Coord::for_board_size(1);
+    let coords = Coord::for_board_size(1, 1);
     assert_eq!(coords[0], Coord::new(1,1));
 }
 
+#[test]
+fn for_board_size_handles_rectangular_boards() {
+    let coords = Coord::for_board_size(3, 2);
+    assert_eq!(6, coords.len());
+    assert_eq!(coords[5], Coord::new(3,2));
+}
+
 #[test]
 fn distance_to_border() {
     let size = 9;
-    assert_eq!(0, Coord::new(1,5).distance_to_border(size));
-    assert_eq!(0, Coord::new(5,1).distance_to_border(size));
-    assert_eq!(0, Coord::new(9,5).distance_to_border(size));
-    assert_eq!(0, Coord::new(5,9).distance_to_border(size));
-    assert_eq!(1, Coord::new(2,5).distance_to_border(size));
-    assert_eq!(1, Coord::new(8,6).distance_to_border(size));
+    assert_eq!(0, Coord::new(1,5).distance_to_border(size, size));
+    assert_eq!(0, Coord::new(5,1).distance_to_border(size, size));
+    assert_eq!(0, Coord::new(9,5).distance_to_border(size, size));
+    assert_eq!(0, Coord::new(5,9).distance_to_border(size, size));
+    assert_eq!(1, Coord::new(2,5).distance_to_border(size, size));
+    assert_eq!(1, Coord::new(8,6).distance_to_border(size, size));
 }
 
 #[test]
 fn manhattan_distance_three_neighbours_middle_of_board() {
     let coord = Coord::new(5, 5);
     let size = 9;
-    assert_eq!(24, coord.manhattan_distance_three_neighbours(size).len());
+    assert_eq!(24, coord.manhattan_distance_three_neighbours(size, size).len());
 }
 
 #[test]
 fn manhattan_distance_three_neighbours_in_a_corner() {
     let coord = Coord::new(1, 1);
     let size = 9;
-    assert_eq!(9, coord.manhattan_distance_three_neighbours(size).len());
+    assert_eq!(9, coord.manhattan_distance_three_neighbours(size, size).len());
+}
+
+#[test]
+fn distance_to_border_on_a_rectangular_board() {
+    assert_eq!(0, Coord::new(5,1).distance_to_border(13, 5));
+    assert_eq!(2, Coord::new(7,3).distance_to_border(13, 5));
 }
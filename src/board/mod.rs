@@ -30,10 +30,17 @@ pub use self::movement::NoMove;
 pub use self::movement::Pass;
 pub use self::movement::Play;
 pub use self::movement::Resign;
+pub use self::nakade::EyeShape;
+pub use self::nakade::Nakade;
+pub use self::pseudo_liberty::PseudoLibertyBoard;
+pub use self::semeai::SEMEAI_LIBERTIES;
+pub use self::semeai::Semeai;
+pub use self::symmetry::Symmetry;
 
 use ruleset::Ruleset;
 use score::Score;
 use self::point::Point;
+use self::symmetry::HashKeys;
 
 use quicksort::quicksort;
 use std::collections::HashSet;
@@ -45,10 +52,19 @@ mod chain;
 mod coord;
 mod hypotheticals;
 mod movement;
+mod nakade;
+mod neighbourhood;
 mod point;
+mod pseudo_liberty;
 mod reading;
+mod seki;
+mod semeai;
+mod symmetry;
 mod test;
 
+// The GTP coordinates only have letters for 25 columns.
+pub const MAX_SIZE: u8 = 25;
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum IllegalMove {
     GameAlreadyOver,
@@ -68,6 +84,23 @@ impl fmt::Display for IllegalMove {
 }
 
 
+// The operations needed to run a playout. Implemented by Board and
+// by the faster (but less featureful) PseudoLibertyBoard.
+pub trait GoBoard: Clone {
+
+    fn play_legal_move(&mut self, m: Move);
+    fn is_legal(&self, m: Move) -> Result<(), IllegalMove>;
+    fn is_eye(&self, coord: &Coord, color: Color) -> bool;
+    fn liberty_count(&self, c: Coord) -> usize;
+    fn chains(&self) -> Vec<Chain>;
+    fn next_player(&self) -> Color;
+    fn vacant(&self) -> &Vec<Coord>;
+    fn is_game_over(&self) -> bool;
+    fn winner(&self) -> Color;
+    fn point_count(&self) -> usize;
+
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Copy)]
 pub enum Color {
     White,
@@ -92,49 +125,66 @@ impl Color {
             err           => panic!("Can't read the GTP color: {}", err)
         }
     }
+
+    // Like from_gtp, but doesn't panic on unknown colors.
+    pub fn parse_gtp(gtp_color: &str) -> Option<Color> {
+        let lower_gtp_color: String = gtp_color.chars().map(|c| c.to_lowercase().next().unwrap()).collect();
+        match lower_gtp_color.as_ref() {
+            "w" | "white" => Some(White),
+            "b" | "black" => Some(Black),
+            _             => None
+        }
+    }
 }
 
 #[derive(Debug)]
 struct Cache {
     diagonals: Vec<Vec<Coord>>,
+    hash_keys: HashKeys,
     neighbours: Vec<Vec<Coord>>,
-    neighbours8_unchecked: Vec<Vec<Coord>>
+    neighbours8_unchecked: Vec<Vec<Coord>>,
+    pseudo_liberty: Arc<pseudo_liberty::Cache>,
 }
 
 impl Cache {
-    pub fn new(size: u8) -> Cache {
+    pub fn new(width: u8, height: u8) -> Cache {
         Cache {
-            diagonals: Self::setup_diagonals(size),
-            neighbours: Self::setup_neighbours(size),
-            neighbours8_unchecked: Self::setup_neighbours8_unchecked(size),
+            diagonals: Self::setup_diagonals(width, height),
+            hash_keys: HashKeys::new(),
+            neighbours: Self::setup_neighbours(width, height),
+            neighbours8_unchecked: Self::setup_neighbours8_unchecked(width, height),
+            pseudo_liberty: Arc::new(pseudo_liberty::Cache::new(width, height)),
         }
     }
 
-    fn setup_neighbours(size: u8) -> Vec<Vec<Coord>> {
+    fn setup_neighbours(width: u8, height: u8) -> Vec<Vec<Coord>> {
         let mut neighbours = Vec::new();
-        for coord in Coord::for_board_size(size).iter() {
-            neighbours.push(coord.neighbours(size));
+        for coord in Coord::for_board_size(width, height).iter() {
+            neighbours.push(coord.neighbours(width, height));
         }
         neighbours
     }
 
-    fn setup_diagonals(size: u8) -> Vec<Vec<Coord>> {
+    fn setup_diagonals(width: u8, height: u8) -> Vec<Vec<Coord>> {
         let mut diagonals = Vec::new();
-        for coord in Coord::for_board_size(size).iter() {
-            diagonals.push(coord.diagonals(size));
+        for coord in Coord::for_board_size(width, height).iter() {
+            diagonals.push(coord.diagonals(width, height));
         }
         diagonals
     }
 
-    fn setup_neighbours8_unchecked(size: u8) -> Vec<Vec<Coord>> {
+    fn setup_neighbours8_unchecked(width: u8, height: u8) -> Vec<Vec<Coord>> {
         let mut neighbours8 = Vec::new();
-        for coord in Coord::for_board_size(size).iter() {
+        for coord in Coord::for_board_size(width, height).iter() {
             neighbours8.push(coord.neighbours8_unchecked());
         }
         neighbours8
     }
 }
 
+/// A position with its chains and liberties. Unlike `Game` it only
+/// knows the simple ko rule, use `Game` to play moves with superko.
+/// Coordinates start at 1 in the lower left corner.
 #[derive(Debug)]
 pub struct Board {
     adv_stones_removed:    Vec<Coord>,
@@ -143,13 +193,15 @@ pub struct Board {
     consecutive_passes:    u8,
     cache:                 Arc<Cache>,
     friend_stones_removed: Vec<Coord>,
+    height:                u8,
     ko:                    Option<Coord>,
     komi:                  f32,
+    pattern_codes:         Vec<u16>,
     previous_player:       Color,
     resigned_by:           Color,
     ruleset:               Ruleset,
-    size:                  u8,
     vacant:                Vec<Coord>,
+    width:                 u8,
 }
 
 impl Clone for Board {
@@ -161,51 +213,65 @@ impl Clone for Board {
             cache:                 self.cache.clone(),
             consecutive_passes:    self.consecutive_passes,
             friend_stones_removed: self.friend_stones_removed.clone(),
+            height:                self.height,
             ko:                    self.ko,
             komi:                  self.komi,
+            pattern_codes:         self.pattern_codes.clone(),
             previous_player:       self.previous_player,
             resigned_by:           self.resigned_by,
             ruleset:               self.ruleset,
-            size:                  self.size,
             vacant:                self.vacant.clone(),
+            width:                 self.width,
         }
     }
 }
 
 impl Board {
+    /// An empty square board.
     pub fn new(size: u8, komi: f32, ruleset: Ruleset) -> Board {
+        Board::rectangular(size, size, komi, ruleset)
+    }
+
+    /// An empty board of the given size.
+    pub fn rectangular(width: u8, height: u8, komi: f32, ruleset: Ruleset) -> Board {
         Board {
             adv_stones_removed:    Vec::new(),
-            board:                 (0..size as usize*size as usize).map(|_| Point::new()).collect(),
+            board:                 (0..width as usize*height as usize).map(|_| Point::new()).collect(),
             chains:                Vec::new(),
             consecutive_passes:    0,
-            cache:                 Arc::new(Cache::new(size)),
+            cache:                 Arc::new(Cache::new(width, height)),
             friend_stones_removed: Vec::new(),
+            height:                height,
             ko:                    None,
             komi:                  komi,
+            pattern_codes:         Board::initial_pattern_codes(width, height),
             previous_player:       White,
             resigned_by:           Empty,
             ruleset:               ruleset,
-            size:                  size,
-            vacant:                Coord::for_board_size(size),
+            vacant:                Coord::for_board_size(width, height),
+            width:                 width,
         }
     }
 
+    pub fn is_valid_size(width: u8, height: u8) -> bool {
+        1 <= width && width <= MAX_SIZE && 1 <= height && height <= MAX_SIZE
+    }
+
     pub fn neighbours(&self, c: Coord) -> &Vec<Coord> {
-        &self.cache.neighbours[c.to_index(self.size)]
+        &self.cache.neighbours[c.to_index(self.width)]
     }
 
     pub fn diagonals(&self, c: Coord) -> &Vec<Coord> {
-        &self.cache.diagonals[c.to_index(self.size)]
+        &self.cache.diagonals[c.to_index(self.width)]
     }
 
     pub fn neighbours8_unchecked(&self, c: Coord) -> &Vec<Coord> {
-        &self.cache.neighbours8_unchecked[c.to_index(self.size)]
+        &self.cache.neighbours8_unchecked[c.to_index(self.width)]
     }
 
     pub fn neighbours8_checked(&self, c: Coord) -> Vec<Option<Color>> {
         self.neighbours8_unchecked(c).iter().map(|c| {
-            if c.is_inside(self.size) {
+            if c.is_inside(self.width, self.height) {
                 Some(self.color(c))
             } else {
                 None
@@ -221,16 +287,17 @@ impl Board {
         &self.vacant
     }
 
+    /// The color of the stone at `c`, `Empty` if there is none.
     pub fn color(&self, c: &Coord) -> Color {
-        self.board[c.to_index(self.size)].color
+        self.board[c.to_index(self.width)].color
     }
 
     pub fn chain_id(&self, c: &Coord) -> usize {
-        self.board[c.to_index(self.size)].chain_id
+        self.board[c.to_index(self.width)].chain_id
     }
 
     pub fn get_chain<'b>(&'b self, c: Coord) -> Option<&'b Chain> {
-        let ref point = self.board[c.to_index(self.size)];
+        let ref point = self.board[c.to_index(self.width)];
         if point.color != Empty {
             Some(&self.chains[point.chain_id])
         } else {
@@ -246,6 +313,22 @@ impl Board {
         self.komi = komi;
     }
 
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    pub fn ko(&self) -> Option<Coord> {
+        self.ko
+    }
+
+    pub fn consecutive_passes(&self) -> u8 {
+        self.consecutive_passes
+    }
+
+    pub fn resigned_by(&self) -> Color {
+        self.resigned_by
+    }
+
     #[cfg(test)]
     pub fn set_ruleset(&mut self, ruleset: Ruleset) {
         self.ruleset = ruleset;
@@ -310,6 +393,13 @@ impl Board {
         if self.is_same_player(&m) && !self.ruleset.same_player() {
             return Err(IllegalMove::SamePlayerPlayedTwice);
         }
+        self.is_legal_regardless_of_turn(m)
+    }
+
+    // The legality checks of is_legal that don't depend on whose turn
+    // it is, i.e. whether m would be legal if it were m's color to
+    // move.
+    pub fn is_legal_regardless_of_turn(&self, m: Move) -> Result<(), IllegalMove> {
         // Pass is always allowed
         if m.is_pass() {
             return Ok(());
@@ -320,7 +410,7 @@ impl Board {
             return Ok(());
         }
         // Can't play outside of the board or on an occupied coord
-        if m.coord().is_inside(self.size) {
+        if m.coord().is_inside(self.width, self.height) {
             if self.color(&m.coord()) != Empty {
                 return Err(IllegalMove::IntersectionNotEmpty);
             }
@@ -360,7 +450,7 @@ impl Board {
         Ok(())
     }
 
-    // Note: Same as get(), the board is indexed starting at 1-1
+    /// Plays `m` if it is legal, see `is_legal`.
     pub fn play(&mut self, m: Move) -> Result<(), IllegalMove> {
         try!(self.is_legal(m));
         self.play_legal_move(m);
@@ -397,6 +487,8 @@ impl Board {
         if self.get_chain(m.coord()).unwrap().is_captured() {
             self.friend_stones_removed = self.remove_suicide_chain(&m);
             self.add_removed_friendly_stones_as_libs(&m);
+        } else {
+            self.friend_stones_removed.clear();
         }
         if self.adv_stones_removed.len() == 1 && self.friend_stones_removed.len() == 0 {
             let coord = self.adv_stones_removed[0];
@@ -486,7 +578,7 @@ impl Board {
                 // We merge the other chain into the final chain.
                 let other_chain = self.chains.remove(other_chain_id);
                 for &coord in other_chain.coords().iter() {
-                    self.board[coord.to_index(self.size)].chain_id = final_chain_id;
+                    self.board[coord.to_index(self.width)].chain_id = final_chain_id;
                     self.chains[final_chain_id].add_coord(coord);
                 }
                 for &lib in other_chain.liberties().iter() {
@@ -503,7 +595,7 @@ impl Board {
     fn update_board_ids_after_id(&mut self, id: usize) {
         for i in id..self.chains.len() {
             for &coord in self.chains[i].coords().iter() {
-                self.board[coord.to_index(self.size)].chain_id = i;
+                self.board[coord.to_index(self.width)].chain_id = i;
             }
         }
     }
@@ -566,12 +658,11 @@ impl Board {
     }
 
     fn remove_chain(&mut self, id: usize) {
-        {
-            let coords_to_remove = self.chains[id].coords().iter();
+        let coords_to_remove = self.chains[id].coords().clone();
 
-            for &coord in coords_to_remove {
-                self.board[coord.to_index(self.size)].color = Empty; //remove stone
-            }
+        for &coord in coords_to_remove.iter() {
+            self.board[coord.to_index(self.width)].color = Empty; //remove stone
+            self.update_pattern_codes(coord, Empty);
         }
 
         self.chains.remove(id);
@@ -583,8 +674,9 @@ impl Board {
         let new_chain    = Chain::new(
             new_chain_id, *m.color(), m.coord(), self.liberties(&m.coord()));
         self.chains.push(new_chain);
-        self.board[m.coord().to_index(self.size)].chain_id = new_chain_id;
-        self.board[m.coord().to_index(self.size)].color = *m.color();
+        self.board[m.coord().to_index(self.width)].chain_id = new_chain_id;
+        self.board[m.coord().to_index(self.width)].color = *m.color();
+        self.update_pattern_codes(m.coord(), *m.color());
         new_chain_id
     }
 
@@ -592,6 +684,7 @@ impl Board {
         self.neighbours(*c).iter().filter(|&c| self.color(c) == Empty).cloned().collect()
     }
 
+    /// The area score of the position, dead stones aren't removed.
     pub fn score(&self) -> Score {
         Score::new(self)
     }
@@ -607,8 +700,16 @@ impl Board {
         self.consecutive_passes == 2 || self.resigned_by != Empty
     }
 
-    pub fn size(&self) -> u8 {
-        self.size
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    pub fn point_count(&self) -> usize {
+        self.width as usize * self.height as usize
     }
 
     pub fn vacant_point_count(&self) -> u16 {
@@ -622,13 +723,13 @@ impl Board {
     pub fn as_string(&self) -> String {
         let mut s = String::new();
                 // First we print the board
-        for row in (1u8..self.size()+1).rev() {
+        for row in (1u8..self.height()+1).rev() {
 
             // Prints the row number
             s.push_str(format!("{:2} ", row).as_ref());
 
             // Prints the actual row
-            for col in 1u8..self.size()+1 {
+            for col in 1u8..self.width()+1 {
                 let current_coords = Coord::new(col, row);
 
                 match self.color(&current_coords) {
@@ -649,3 +750,47 @@ impl Board {
         s
     }
 }
+
+impl GoBoard for Board {
+
+    fn play_legal_move(&mut self, m: Move) {
+        Board::play_legal_move(self, m)
+    }
+
+    fn is_legal(&self, m: Move) -> Result<(), IllegalMove> {
+        Board::is_legal(self, m)
+    }
+
+    fn is_eye(&self, coord: &Coord, color: Color) -> bool {
+        Board::is_eye(self, coord, color)
+    }
+
+    fn liberty_count(&self, c: Coord) -> usize {
+        Board::liberty_count(self, c)
+    }
+
+    fn chains(&self) -> Vec<Chain> {
+        self.chains.clone()
+    }
+
+    fn next_player(&self) -> Color {
+        Board::next_player(self)
+    }
+
+    fn vacant(&self) -> &Vec<Coord> {
+        &self.vacant
+    }
+
+    fn is_game_over(&self) -> bool {
+        Board::is_game_over(self)
+    }
+
+    fn winner(&self) -> Color {
+        Board::winner(self)
+    }
+
+    fn point_count(&self) -> usize {
+        Board::point_count(self)
+    }
+
+}
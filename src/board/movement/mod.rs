@@ -28,6 +28,9 @@ use board::Coord;
 
 mod test;
 
+/// A move of one player. `Play` takes the column and the row,
+/// counted from 1 starting in the lower left corner. `NoMove` stands
+/// for the missing move before the first one.
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
 pub enum Move {
     NoMove,
@@ -37,6 +40,8 @@ pub enum Move {
 }
 
 impl Move {
+    /// Reads a GTP color and vertex such as "b" and "D4". Panics on
+    /// malformed input, see `parse_gtp`.
     pub fn from_gtp(gtp_color: &str, gtp_vertex: &str) -> Move {
         let color = Color::from_gtp(gtp_color);
         let lower_gtp_vertex: String = gtp_vertex.chars().map(|c| c.to_lowercase().next().unwrap()).collect();
@@ -51,6 +56,23 @@ impl Move {
         }
     }
 
+    /// Like from_gtp, but doesn't panic on malformed input or on
+    /// vertices that aren't on a width x height board.
+    pub fn parse_gtp(gtp_color: &str, gtp_vertex: &str, width: u8, height: u8) -> Option<Move> {
+        let color = match Color::parse_gtp(gtp_color) {
+            Some(color) => color,
+            None        => return None
+        };
+        let lower_gtp_vertex: String = gtp_vertex.chars().map(|c| c.to_lowercase().next().unwrap()).collect();
+
+        match lower_gtp_vertex.as_ref() {
+            "pass"   => Some(Pass(color)),
+            "resign" => Some(Resign(color)),
+            _        => Coord::parse_gtp(gtp_vertex, width, height).map(|coord| Play(color, coord.col, coord.row))
+        }
+    }
+
+    /// The GTP vertex of the move, e.g. "D4" or "pass".
     pub fn to_gtp(&self) -> String {
         match *self {
             Pass(_)           => String::from("pass"),
@@ -69,6 +91,7 @@ impl Move {
         }
     }
 
+    /// The point of a `Play`, panics for all other moves.
     pub fn coord(&self) -> Coord {
         match *self {
             Play(_, col, row) => Coord::new(col, row),
@@ -24,6 +24,7 @@ use board::Black;
 use board::White;
 use super::Move;
 use super::Pass;
+use super::Play;
 use super::Resign;
 
 #[test]
@@ -45,6 +46,21 @@ fn parse_resign() {
     assert_eq!(m, Resign(White));
 }
 
+#[test]
+fn parse_gtp_rejects_malformed_moves() {
+    assert_eq!(Some(Play(White, 4, 3)), Move::parse_gtp("white", "D3", 9, 9));
+    assert_eq!(None, Move::parse_gtp("x", "D3", 9, 9));
+    assert_eq!(None, Move::parse_gtp("b", "3D", 9, 9));
+    assert_eq!(None, Move::parse_gtp("b", "D", 9, 9));
+}
+
+#[test]
+fn parse_gtp_rejects_moves_off_the_board() {
+    assert_eq!(Some(Pass(Black)), Move::parse_gtp("b", "pass", 9, 9));
+    assert_eq!(None, Move::parse_gtp("b", "K5", 9, 9));
+    assert_eq!(None, Move::parse_gtp("b", "E10", 9, 9));
+}
+
 #[test]
 fn produce_gtp_resign() {
     let m = Resign(White);
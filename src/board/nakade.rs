@@ -0,0 +1,170 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::Move;
+use board::Play;
+use board::Symmetry;
+
+pub use self::EyeShape::BentThree;
+pub use self::EyeShape::BulkyFive;
+pub use self::EyeShape::CrossedFive;
+pub use self::EyeShape::FlowerSix;
+pub use self::EyeShape::PyramidFour;
+pub use self::EyeShape::StraightThree;
+
+// Larger eye spaces always make two eyes.
+pub const MAX_EYE_SPACE: usize = 6;
+
+// The eye shapes that only make one eye if the opponent plays the
+// vital point first.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EyeShape {
+    StraightThree,
+    BentThree,
+    PyramidFour,
+    BulkyFive,
+    CrossedFive,
+    FlowerSix,
+}
+
+// The shapes with the vital point marked as *. They are matched in
+// all rotations and reflections.
+const SHAPES: [(EyeShape, &'static str); 6] = [
+    (StraightThree, "X*X"),
+    (BentThree,     "*X\nX."),
+    (PyramidFour,   "X*X\n.X."),
+    (BulkyFive,     "XX.\nX*X"),
+    (CrossedFive,   ".X.\nX*X\n.X."),
+    (FlowerSix,     ".X.\nX*X\n.XX"),
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Nakade {
+    pub owner: Color,
+    pub shape: EyeShape,
+    pub space: Vec<Coord>,
+    pub vital: Coord,
+}
+
+impl Board {
+
+    // The empty points connected to c, if they are only surrounded by
+    // stones of one color (the owner) and not larger than
+    // MAX_EYE_SPACE.
+    pub fn eye_space(&self, c: &Coord) -> Option<(Color, Vec<Coord>)> {
+        if self.color(c) != Empty {
+            return None;
+        }
+        let mut owner = Empty;
+        let mut space = vec!();
+        let mut todo = vec!(*c);
+        while let Some(current) = todo.pop() {
+            if space.contains(&current) {
+                continue;
+            }
+            space.push(current);
+            if space.len() > MAX_EYE_SPACE {
+                return None;
+            }
+            for n in self.neighbours(current).iter() {
+                match self.color(n) {
+                    Empty => todo.push(*n),
+                    color if owner == Empty => owner = color,
+                    color if owner != color => return None,
+                    _ => {}
+                }
+            }
+        }
+        if owner == Empty {
+            return None;
+        }
+        space.sort();
+        Some((owner, space))
+    }
+
+    // The killable eye shape c is part of.
+    pub fn nakade(&self, c: &Coord) -> Option<Nakade> {
+        let (owner, space) = match self.eye_space(c) {
+            Some((owner, space)) if space.len() >= 3 => (owner, space),
+            _ => return None
+        };
+        let min_col = space.iter().map(|c| c.col).min().unwrap();
+        let min_row = space.iter().map(|c| c.row).min().unwrap();
+        let normalized: Vec<Coord> = space.iter().map(|c| Coord::new(c.col - min_col + 1, c.row - min_row + 1)).collect();
+        for &(shape, pattern) in SHAPES.iter() {
+            if let Some(vital) = Self::match_shape(&normalized, pattern) {
+                return Some(Nakade {
+                    owner: owner,
+                    shape: shape,
+                    space: space,
+                    vital: Coord::new(vital.col + min_col - 1, vital.row + min_row - 1),
+                });
+            }
+        }
+        None
+    }
+
+    // Plays the vital point instead if the move is inside a killable
+    // eye shape. This kills the eye space for the attacker and makes
+    // two eyes for the owner.
+    pub fn nakade_move(&self, m: Move) -> Option<Move> {
+        self.nakade(&m.coord())
+            .map(|nakade| Play(*m.color(), nakade.vital.col, nakade.vital.row))
+            .and_then(|vital| if self.is_legal(vital).is_ok() { Some(vital) } else { None })
+    }
+
+    // The points have to start at 1-1 and be sorted. Returns the vital
+    // point in the same frame.
+    fn match_shape(points: &Vec<Coord>, pattern: &str) -> Option<Coord> {
+        let mut shape = vec!();
+        let mut vital = Coord::new(1, 1);
+        for (y, line) in pattern.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let coord = Coord::new(x as u8 + 1, y as u8 + 1);
+                if c != '.' {
+                    shape.push(coord);
+                }
+                if c == '*' {
+                    vital = coord;
+                }
+            }
+        }
+        if shape.len() != points.len() {
+            return None;
+        }
+        let width = pattern.lines().map(|line| line.len()).max().unwrap() as u8;
+        let height = pattern.lines().count() as u8;
+        for symmetry in Symmetry::all() {
+            let mut transformed: Vec<Coord> = shape.iter().map(|c| c.transform(symmetry, width, height)).collect();
+            transformed.sort();
+            if transformed == *points {
+                return Some(vital.transform(symmetry, width, height));
+            }
+        }
+        None
+    }
+
+}
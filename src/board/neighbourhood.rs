@@ -0,0 +1,93 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use board::Black;
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::White;
+
+// Every intersection keeps a 16 bit code that describes its 3x3
+// neighbourhood. Each of the 8 neighbours (in the order of
+// Coord::neighbours8_unchecked) takes up two bits.
+const EMPTY:     u16 = 0;
+const BLACK:     u16 = 1;
+const WHITE:     u16 = 2;
+const OFF_BOARD: u16 = 3;
+
+fn value_for(color: Option<Color>) -> u16 {
+    match color {
+        Some(Empty) => EMPTY,
+        Some(Black) => BLACK,
+        Some(White) => WHITE,
+        None        => OFF_BOARD,
+    }
+}
+
+impl Board {
+
+    pub fn pattern_code(&self, c: &Coord) -> u16 {
+        self.pattern_codes[c.to_index(self.width)]
+    }
+
+    pub fn colors_for_pattern_code(code: u16) -> Vec<Option<Color>> {
+        (0..8).map(|i| {
+            match (code >> (2 * i)) & 3 {
+                EMPTY => Some(Empty),
+                BLACK => Some(Black),
+                WHITE => Some(White),
+                _     => None,
+            }
+        }).collect()
+    }
+
+    // The inverse of colors_for_pattern_code.
+    pub fn pattern_code_for_colors(colors: &Vec<Option<Color>>) -> u16 {
+        colors.iter().enumerate().fold(0, |code, (i, &color)| code | (value_for(color) << (2 * i)))
+    }
+
+    pub fn initial_pattern_codes(width: u8, height: u8) -> Vec<u16> {
+        Coord::for_board_size(width, height).iter().map(|c| {
+            c.neighbours8_unchecked().iter().enumerate().fold(0, |code, (i, n)| {
+                let color = if n.is_inside(width, height) { Some(Empty) } else { None };
+                code | (value_for(color) << (2 * i))
+            })
+        }).collect()
+    }
+
+    // Updates the codes of the 8 neighbours of c after its color
+    // has changed. From the point of view of a neighbour c is in the
+    // opposite direction.
+    pub fn update_pattern_codes(&mut self, c: Coord, color: Color) {
+        let value = value_for(Some(color));
+        let cache = self.cache.clone();
+        for (i, n) in cache.neighbours8_unchecked[c.to_index(self.width)].iter().enumerate() {
+            if n.is_inside(self.width, self.height) {
+                let shift = 2 * ((i + 4) % 8);
+                let index = n.to_index(self.width);
+                self.pattern_codes[index] = (self.pattern_codes[index] & !(3 << shift)) | (value << shift);
+            }
+        }
+    }
+
+}
@@ -0,0 +1,520 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use board::Black;
+use board::Board;
+use board::Chain;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::GoBoard;
+use board::IllegalMove;
+use board::Move;
+use board::White;
+use ruleset::Ruleset;
+use score::Score;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+mod test;
+
+// Built once per board size and shared by all boards of that size,
+// see board::Cache.
+#[derive(Debug)]
+pub struct Cache {
+    coords: Vec<Coord>,
+    diagonals: Vec<Vec<usize>>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl Cache {
+
+    pub fn new(width: u8, height: u8) -> Cache {
+        let coords = Coord::for_board_size(width, height);
+        Cache {
+            diagonals: coords.iter()
+                .map(|c| c.diagonals(width, height).iter().map(|d| d.to_index(width)).collect())
+                .collect(),
+            neighbours: coords.iter()
+                .map(|c| c.neighbours(width, height).iter().map(|n| n.to_index(width)).collect())
+                .collect(),
+            coords: coords,
+        }
+    }
+
+}
+
+// An alternative board representation that is optimized for fast
+// playouts. Instead of keeping an exact set of liberties per chain
+// it only counts pseudo liberties (a liberty is counted once for
+// every adjacent stone of the chain). Together with the sum and the
+// sum of squares of the liberty indices this is enough to know if a
+// chain is captured or in atari. Chains are kept as a parent array
+// (every stone points directly to the root of its chain) and a
+// circular linked list of stones, so that merging and capturing
+// doesn't need to allocate.
+#[derive(Clone, Debug)]
+pub struct PseudoLibertyBoard {
+    cache:              Arc<Cache>,
+    colors:             Vec<Color>,
+    consecutive_passes: u8,
+    height:             u8,
+    ko:                 Option<usize>,
+    komi:               f32,
+    lib_sum:            Vec<usize>,
+    lib_sum_sq:         Vec<usize>,
+    libs:               Vec<usize>,
+    next:               Vec<usize>,
+    parent:             Vec<usize>,
+    previous_player:    Color,
+    resigned_by:        Color,
+    ruleset:            Ruleset,
+    stones:             Vec<usize>,
+    vacant:             Vec<Coord>,
+    vacant_position:    Vec<usize>,
+    width:              u8,
+}
+
+impl PseudoLibertyBoard {
+
+    pub fn new(width: u8, height: u8, komi: f32, ruleset: Ruleset) -> PseudoLibertyBoard {
+        let count = width as usize * height as usize;
+        let cache = Cache::new(width, height);
+        PseudoLibertyBoard {
+            colors:             vec!(Empty; count),
+            consecutive_passes: 0,
+            height:             height,
+            ko:                 None,
+            komi:               komi,
+            lib_sum:            vec!(0; count),
+            lib_sum_sq:         vec!(0; count),
+            libs:               vec!(0; count),
+            next:               (0..count).collect(),
+            parent:             (0..count).collect(),
+            previous_player:    White,
+            resigned_by:        Empty,
+            ruleset:            ruleset,
+            stones:             vec!(0; count),
+            vacant:             cache.coords.clone(),
+            vacant_position:    (0..count).collect(),
+            width:              width,
+            cache:              Arc::new(cache),
+        }
+    }
+
+    // Uses the cache of the board and copies its chains, so that
+    // this is cheap enough to do at the start of every playout.
+    pub fn from_board(board: &Board) -> PseudoLibertyBoard {
+        let width = board.width();
+        let count = width as usize * board.height() as usize;
+        let cache = board.cache.pseudo_liberty.clone();
+        let mut colors = vec!(Empty; count);
+        let mut lib_sum = vec!(0; count);
+        let mut lib_sum_sq = vec!(0; count);
+        let mut libs = vec!(0; count);
+        let mut next: Vec<usize> = (0..count).collect();
+        let mut parent: Vec<usize> = (0..count).collect();
+        let mut stones = vec!(0; count);
+        for chain in board.chains().iter() {
+            let coords = chain.coords();
+            let root = coords[0].to_index(width);
+            for (i, coord) in coords.iter().enumerate() {
+                let index = coord.to_index(width);
+                colors[index] = chain.color();
+                next[index] = coords[(i + 1) % coords.len()].to_index(width);
+                parent[index] = root;
+                for &n in cache.neighbours[index].iter() {
+                    if board.color(&cache.coords[n]) == Empty {
+                        libs[root] += 1;
+                        lib_sum[root] += n;
+                        lib_sum_sq[root] += n * n;
+                    }
+                }
+            }
+            stones[root] = coords.len();
+        }
+        let vacant = board.vacant().clone();
+        let mut vacant_position = vec!(0; count);
+        for (position, coord) in vacant.iter().enumerate() {
+            vacant_position[coord.to_index(width)] = position;
+        }
+        PseudoLibertyBoard {
+            cache:              cache,
+            colors:             colors,
+            consecutive_passes: board.consecutive_passes(),
+            height:             board.height(),
+            ko:                 board.ko().map(|c| c.to_index(width)),
+            komi:               board.komi(),
+            lib_sum:            lib_sum,
+            lib_sum_sq:         lib_sum_sq,
+            libs:               libs,
+            next:               next,
+            parent:             parent,
+            previous_player:    board.next_player().opposite(),
+            resigned_by:        board.resigned_by(),
+            ruleset:            board.ruleset(),
+            stones:             stones,
+            vacant:             vacant,
+            vacant_position:    vacant_position,
+            width:              width,
+        }
+    }
+
+    pub fn color(&self, c: &Coord) -> Color {
+        self.colors[c.to_index(self.width)]
+    }
+
+    pub fn score(&self) -> Score {
+        let mut black = 0;
+        let mut white = 0;
+        let mut visited = vec!(false; self.colors.len());
+        for i in 0..self.colors.len() {
+            match self.colors[i] {
+                Black => black += 1,
+                White => white += 1,
+                Empty => if !visited[i] {
+                    let (size, owner) = self.empty_region(i, &mut visited);
+                    match owner {
+                        Black => black += size,
+                        White => white += size,
+                        Empty => {}
+                    }
+                }
+            }
+        }
+        Score::from_area(black, white, self.komi)
+    }
+
+    // Returns the size of the empty region containing index and
+    // the color that encloses it (Empty if both colors touch it).
+    fn empty_region(&self, index: usize, visited: &mut Vec<bool>) -> (usize, Color) {
+        let mut to_visit = vec!(index);
+        let mut size = 0;
+        let mut owner = None;
+        let mut neutral = false;
+        visited[index] = true;
+        while let Some(i) = to_visit.pop() {
+            size += 1;
+            for &n in self.cache.neighbours[i].iter() {
+                match self.colors[n] {
+                    Empty => if !visited[n] {
+                        visited[n] = true;
+                        to_visit.push(n);
+                    },
+                    color => match owner {
+                        None => owner = Some(color),
+                        Some(c) => if c != color { neutral = true; }
+                    }
+                }
+            }
+        }
+        if neutral {
+            (size, Empty)
+        } else {
+            (size, owner.unwrap_or(Empty))
+        }
+    }
+
+    fn is_captured(&self, root: usize) -> bool {
+        self.libs[root] == 0
+    }
+
+    // All pseudo liberties point to the same intersection if the
+    // sum of squares times the count equals the squared sum.
+    fn in_atari(&self, root: usize) -> bool {
+        let libs = self.libs[root];
+        libs > 0 && libs * self.lib_sum_sq[root] == self.lib_sum[root] * self.lib_sum[root]
+    }
+
+    fn atari_liberty(&self, root: usize) -> usize {
+        self.lib_sum[root] / self.libs[root]
+    }
+
+    fn add_liberty(&mut self, root: usize, lib: usize) {
+        self.libs[root] += 1;
+        self.lib_sum[root] += lib;
+        self.lib_sum_sq[root] += lib * lib;
+    }
+
+    fn remove_liberty(&mut self, root: usize, lib: usize) {
+        self.libs[root] -= 1;
+        self.lib_sum[root] -= lib;
+        self.lib_sum_sq[root] -= lib * lib;
+    }
+
+    fn place_stone(&mut self, index: usize, color: Color) {
+        self.colors[index] = color;
+        self.parent[index] = index;
+        self.next[index] = index;
+        self.stones[index] = 1;
+        self.libs[index] = 0;
+        self.lib_sum[index] = 0;
+        self.lib_sum_sq[index] = 0;
+        self.remove_vacant(index);
+        let cache = self.cache.clone();
+        for &n in cache.neighbours[index].iter() {
+            if self.colors[n] == Empty {
+                self.add_liberty(index, n);
+            } else {
+                let root = self.parent[n];
+                self.remove_liberty(root, index);
+            }
+        }
+        for &n in cache.neighbours[index].iter() {
+            if self.colors[n] == color {
+                let (a, b) = (self.parent[index], self.parent[n]);
+                if a != b {
+                    self.merge(a, b);
+                }
+            }
+        }
+    }
+
+    // Merges the smaller chain into the larger one.
+    fn merge(&mut self, a: usize, b: usize) {
+        let (root, other) = if self.stones[a] >= self.stones[b] { (a, b) } else { (b, a) };
+        let mut i = other;
+        loop {
+            self.parent[i] = root;
+            i = self.next[i];
+            if i == other { break; }
+        }
+        let tmp = self.next[root];
+        self.next[root] = self.next[other];
+        self.next[other] = tmp;
+        self.stones[root] += self.stones[other];
+        self.libs[root] += self.libs[other];
+        self.lib_sum[root] += self.lib_sum[other];
+        self.lib_sum_sq[root] += self.lib_sum_sq[other];
+    }
+
+    fn remove_chain(&mut self, root: usize) -> usize {
+        let mut removed = 0;
+        let mut i = root;
+        loop {
+            self.colors[i] = Empty;
+            self.add_vacant(i);
+            removed += 1;
+            i = self.next[i];
+            if i == root { break; }
+        }
+        let cache = self.cache.clone();
+        loop {
+            for &n in cache.neighbours[i].iter() {
+                if self.colors[n] != Empty {
+                    let r = self.parent[n];
+                    self.add_liberty(r, i);
+                }
+            }
+            i = self.next[i];
+            if i == root { break; }
+        }
+        removed
+    }
+
+    fn add_vacant(&mut self, index: usize) {
+        self.vacant_position[index] = self.vacant.len();
+        self.vacant.push(self.cache.coords[index]);
+    }
+
+    fn remove_vacant(&mut self, index: usize) {
+        let position = self.vacant_position[index];
+        let last = self.vacant.len() - 1;
+        let last_index = self.vacant[last].to_index(self.width);
+        self.vacant.swap(position, last);
+        self.vacant_position[last_index] = position;
+        self.vacant.pop();
+    }
+
+    fn chain_liberties(&self, root: usize) -> HashSet<Coord> {
+        let mut libs = HashSet::new();
+        let mut i = root;
+        loop {
+            for &n in self.cache.neighbours[i].iter() {
+                if self.colors[n] == Empty {
+                    libs.insert(self.cache.coords[n]);
+                }
+            }
+            i = self.next[i];
+            if i == root { break; }
+        }
+        libs
+    }
+}
+
+impl GoBoard for PseudoLibertyBoard {
+
+    fn play_legal_move(&mut self, m: Move) {
+        self.previous_player = *m.color();
+
+        if m.is_pass() {
+            self.consecutive_passes += 1;
+            return;
+        } else {
+            self.consecutive_passes = 0;
+        }
+
+        if m.is_resign() {
+            self.resigned_by = *m.color();
+            return;
+        }
+
+        let index = m.coord().to_index(self.width);
+        let color = *m.color();
+        self.place_stone(index, color);
+
+        let mut captured = 0;
+        let mut last_captured = index;
+        let cache = self.cache.clone();
+        for &n in cache.neighbours[index].iter() {
+            if self.colors[n] == color.opposite() {
+                let root = self.parent[n];
+                if self.is_captured(root) {
+                    captured += self.remove_chain(root);
+                    last_captured = n;
+                }
+            }
+        }
+        let root = self.parent[index];
+        let suicide = self.is_captured(root);
+        if suicide {
+            self.remove_chain(root);
+        }
+        self.ko = if captured == 1 && !suicide {
+            Some(last_captured)
+        } else {
+            None
+        };
+    }
+
+    fn is_legal(&self, m: Move) -> Result<(), IllegalMove> {
+        if self.is_game_over() && !self.ruleset.game_over_play() {
+            return Err(IllegalMove::GameAlreadyOver);
+        }
+        if self.previous_player == *m.color() && !self.ruleset.same_player() {
+            return Err(IllegalMove::SamePlayerPlayedTwice);
+        }
+        if m.is_pass() || m.is_resign() {
+            return Ok(());
+        }
+        if !m.coord().is_inside(self.width, self.height) {
+            return Err(IllegalMove::PlayOutOfBoard);
+        }
+        let index = m.coord().to_index(self.width);
+        if self.colors[index] != Empty {
+            return Err(IllegalMove::IntersectionNotEmpty);
+        }
+        let color = *m.color();
+        let neighbours = &self.cache.neighbours[index];
+        if self.ko == Some(index) {
+            let retakes_ko = neighbours.iter()
+                .filter(|&&n| self.colors[n] == color.opposite())
+                .any(|&n| {
+                    let root = self.parent[n];
+                    self.stones[root] == 1 && self.in_atari(root)
+                });
+            if retakes_ko {
+                return Err(IllegalMove::Ko);
+            }
+        }
+        if !self.ruleset.suicide_allowed() {
+            let has_liberty = neighbours.iter().any(|&n| {
+                if self.colors[n] == Empty {
+                    true
+                } else {
+                    let root = self.parent[n];
+                    let only_liberty_is_move = self.in_atari(root) && self.atari_liberty(root) == index;
+                    if self.colors[n] == color {
+                        !only_liberty_is_move
+                    } else {
+                        only_liberty_is_move
+                    }
+                }
+            });
+            if !has_liberty {
+                return Err(IllegalMove::SuicidePlay);
+            }
+        }
+        Ok(())
+    }
+
+    fn is_eye(&self, coord: &Coord, color: Color) -> bool {
+        let index = coord.to_index(self.width);
+        if self.cache.neighbours[index].iter().all(|&n| self.colors[n] == color) {
+            let diagonals = &self.cache.diagonals[index];
+            let enemies = diagonals.iter().filter(|&&d| self.colors[d] == color.opposite()).count();
+            if diagonals.len() < 4 {
+                enemies == 0
+            } else {
+                enemies <= 1
+            }
+        } else {
+            false
+        }
+    }
+
+    fn liberty_count(&self, c: Coord) -> usize {
+        self.cache.neighbours[c.to_index(self.width)].iter()
+            .filter(|&&n| self.colors[n] == Empty)
+            .count()
+    }
+
+    fn chains(&self) -> Vec<Chain> {
+        let mut chains = Vec::new();
+        for i in 0..self.colors.len() {
+            if self.colors[i] != Empty && self.parent[i] == i {
+                let mut chain = Chain::new(chains.len(), self.colors[i], self.cache.coords[i], self.chain_liberties(i));
+                let mut j = self.next[i];
+                while j != i {
+                    chain.add_coord(self.cache.coords[j]);
+                    j = self.next[j];
+                }
+                chains.push(chain);
+            }
+        }
+        chains
+    }
+
+    fn next_player(&self) -> Color {
+        self.previous_player.opposite()
+    }
+
+    fn vacant(&self) -> &Vec<Coord> {
+        &self.vacant
+    }
+
+    fn is_game_over(&self) -> bool {
+        self.consecutive_passes == 2 || self.resigned_by != Empty
+    }
+
+    fn winner(&self) -> Color {
+        match self.resigned_by {
+            Empty => self.score().color(),
+            color => color.opposite(),
+        }
+    }
+
+    fn point_count(&self) -> usize {
+        self.colors.len()
+    }
+}
@@ -0,0 +1,148 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Empty;
+use board::GoBoard;
+use board::IllegalMove;
+use board::Pass;
+use board::Play;
+use board::White;
+use ruleset::AnySizeTrompTaylor;
+use ruleset::KgsChinese;
+use sgf::Parser;
+use super::PseudoLibertyBoard;
+
+use rand::Rng;
+use rand::weak_rng;
+use std::path::Path;
+
+#[test]
+fn capturing_a_stone_removes_it() {
+    let mut b = PseudoLibertyBoard::new(9, 9, 6.5, KgsChinese);
+    b.play_legal_move(Play(Black, 1, 1));
+    b.play_legal_move(Play(White, 2, 1));
+    b.play_legal_move(Pass(Black));
+    b.play_legal_move(Play(White, 1, 2));
+    assert_eq!(Empty, b.color(&Coord::new(1, 1)));
+    assert_eq!(79, b.vacant().len());
+}
+
+#[test]
+fn retaking_a_ko_is_illegal() {
+    let mut b = PseudoLibertyBoard::new(19, 19, 6.5, AnySizeTrompTaylor);
+    b.play_legal_move(Play(Black, 4, 4));
+    b.play_legal_move(Play(White, 5, 4));
+    b.play_legal_move(Play(Black, 3, 3));
+    b.play_legal_move(Play(White, 4, 3));
+    b.play_legal_move(Play(Black, 3, 5));
+    b.play_legal_move(Play(White, 4, 5));
+    b.play_legal_move(Play(Black, 2, 4));
+    b.play_legal_move(Play(White, 3, 4));
+    assert_eq!(Err(IllegalMove::Ko), b.is_legal(Play(Black, 4, 4)));
+}
+
+#[test]
+fn suicide_is_illegal_under_chinese_rules() {
+    let mut b = PseudoLibertyBoard::new(3, 3, 6.5, KgsChinese);
+    b.play_legal_move(Play(Black, 2, 1));
+    b.play_legal_move(Pass(White));
+    b.play_legal_move(Play(Black, 1, 2));
+    assert_eq!(Err(IllegalMove::SuicidePlay), b.is_legal(Play(White, 1, 1)));
+}
+
+#[test]
+fn chains_have_the_correct_liberties() {
+    let mut b = PseudoLibertyBoard::new(9, 9, 6.5, KgsChinese);
+    b.play_legal_move(Play(Black, 1, 1));
+    b.play_legal_move(Pass(White));
+    b.play_legal_move(Play(Black, 2, 1));
+    let chains = b.chains();
+    assert_eq!(1, chains.len());
+    assert_eq!(2, chains[0].coords().len());
+    assert_eq!(3, chains[0].liberties().len());
+}
+
+#[test]
+fn from_board_copies_the_position() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/endgame-black-wins.sgf")).unwrap();
+    let board = parser.game().unwrap().board();
+    let fast = PseudoLibertyBoard::from_board(&board);
+    for coord in Coord::for_board_size(board.width(), board.height()) {
+        assert_eq!(board.color(&coord), fast.color(&coord));
+    }
+    assert_eq!(board.vacant().len(), fast.vacant().len());
+    assert_eq!(board.chains().len(), GoBoard::chains(&fast).len());
+}
+
+#[test]
+fn from_board_copies_the_chains_and_liberties() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/endgame-black-wins.sgf")).unwrap();
+    let board = parser.game().unwrap().board();
+    let fast = PseudoLibertyBoard::from_board(&board);
+    let mut chains = GoBoard::chains(&fast);
+    assert_eq!(board.chains().len(), chains.len());
+    chains.sort_by(|a, b| a.coords()[0].cmp(&b.coords()[0]));
+    for chain in chains.iter() {
+        let expected = board.get_chain(chain.coords()[0]).unwrap();
+        assert_eq!(expected.coords().len(), chain.coords().len());
+        assert_eq!(expected.liberties(), chain.liberties());
+    }
+    let color = board.next_player();
+    for &coord in board.vacant().iter() {
+        let m = Play(color, coord.col, coord.row);
+        assert_eq!(board.is_legal(m), fast.is_legal(m));
+    }
+}
+
+#[test]
+fn behaves_like_board_in_random_games() {
+    let mut rng = weak_rng();
+    for _ in 0..20 {
+        let mut board = Board::new(9, 6.5, KgsChinese);
+        let mut fast = PseudoLibertyBoard::new(9, 9, 6.5, KgsChinese);
+        while !board.is_game_over() {
+            let color = board.next_player();
+            let moves = board.legal_moves_without_eyes();
+            for coord in Coord::for_board_size(9, 9) {
+                let m = Play(color, coord.col, coord.row);
+                assert_eq!(board.is_legal(m), fast.is_legal(m));
+            }
+            let m = if moves.len() == 0 || board.vacant_point_count() < 10 {
+                Pass(color)
+            } else {
+                moves[rng.gen::<usize>() % moves.len()]
+            };
+            board.play_legal_move(m);
+            fast.play_legal_move(m);
+            for coord in Coord::for_board_size(9, 9) {
+                assert_eq!(board.color(&coord), fast.color(&coord));
+            }
+        }
+        assert_eq!(board.winner(), fast.winner());
+    }
+}
+
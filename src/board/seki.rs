@@ -0,0 +1,102 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use board::Black;
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::Play;
+use board::White;
+
+impl Board {
+
+    // A shared liberty of a seki is next to a black and a white
+    // chain, neither player can fill it without putting itself in
+    // atari and neither of the chains wins the capturing race.
+    pub fn is_seki_liberty(&self, c: &Coord) -> bool {
+        self.seki_chains(c).len() > 0
+    }
+
+    pub fn seki_liberties(&self) -> Vec<Coord> {
+        let mut liberties: Vec<Coord> = self.vacant().iter().filter(|c| self.is_seki_liberty(c)).cloned().collect();
+        liberties.sort();
+        liberties
+    }
+
+    // All stones of the chains in seki, sorted.
+    pub fn seki_stones(&self) -> Vec<Coord> {
+        let mut stones = vec!();
+        for c in self.vacant().iter() {
+            for chain in self.seki_chains(c) {
+                for &stone in self.get_chain(chain).unwrap().coords().iter() {
+                    if !stones.contains(&stone) {
+                        stones.push(stone);
+                    }
+                }
+            }
+        }
+        stones.sort();
+        stones
+    }
+
+    // One stone of each chain that is in seki around the liberty.
+    fn seki_chains(&self, c: &Coord) -> Vec<Coord> {
+        if self.color(c) != Empty {
+            return vec!();
+        }
+        let unapproachable = [Black, White].iter()
+            .all(|&color| !self.is_not_self_atari(&Play(color, c.col, c.row)));
+        if !unapproachable {
+            return vec!();
+        }
+        let black = self.adjacent_chains(c, Black);
+        let white = self.adjacent_chains(c, White);
+        let mut chains = vec!();
+        for &a in black.iter() {
+            for &b in white.iter() {
+                let seki = [self.semeai(a, b), self.semeai(b, a)].iter()
+                    .all(|semeai| semeai.as_ref().map(|s| s.winner == Empty).unwrap_or(false));
+                if seki {
+                    for chain in vec!(a, b) {
+                        if !chains.contains(&chain) {
+                            chains.push(chain);
+                        }
+                    }
+                }
+            }
+        }
+        chains
+    }
+
+    fn adjacent_chains(&self, c: &Coord, color: Color) -> Vec<Coord> {
+        let mut chains: Vec<Coord> = vec!();
+        for n in self.neighbours(*c).iter().filter(|n| self.color(n) == color) {
+            let first = self.get_chain(*n).unwrap().coords()[0];
+            if !chains.contains(&first) {
+                chains.push(first);
+            }
+        }
+        chains
+    }
+
+}
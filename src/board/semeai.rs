@@ -0,0 +1,142 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use board::Board;
+use board::Chain;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::Play;
+
+// Chains with more liberties than this aren't considered to be in a
+// capturing race.
+pub const SEMEAI_LIBERTIES: usize = 5;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Semeai {
+    // The chain of the player moving first and the opponent's chain.
+    pub own: Coord,
+    pub other: Coord,
+    // The player that captures the other chain if the owner of the
+    // first chain moves first, or Empty for a seki.
+    pub winner: Color,
+    // Whether the race is only won if the owner of the first chain
+    // moves now (i.e. it would lose or end in seki otherwise).
+    pub critical: bool,
+    // The liberties the player moving first should fill, if it wins.
+    pub moves: Vec<Coord>,
+}
+
+impl Board {
+
+    // Decides a capturing race by counting the liberties of the two
+    // chains with the player of the first chain moving first.
+    //
+    // Without eyes the shared liberties (but one) are only good for
+    // a seki. If only one of the chains has an eye the shared
+    // liberties count for that chain, as the other one can't fill
+    // them without putting itself in atari. If both have eyes the
+    // shared liberties don't count for either of them. Big eyes are
+    // only counted by their liberties.
+    pub fn semeai(&self, own: Coord, other: Coord) -> Option<Semeai> {
+        let (a, b) = match (self.get_chain(own), self.get_chain(other)) {
+            (Some(a), Some(b)) if a.color() == b.color().opposite() => (a, b),
+            _ => return None
+        };
+        if !self.are_adjacent(a, b) {
+            return None;
+        }
+        let shared = a.liberties().iter().filter(|l| b.liberties().contains(*l)).count();
+        let outside_a: Vec<Coord> = a.liberties().iter().filter(|l| !b.liberties().contains(*l)).cloned().collect();
+        let outside_b: Vec<Coord> = b.liberties().iter().filter(|l| !a.liberties().contains(*l)).cloned().collect();
+        let eye_a = outside_a.iter().any(|l| self.is_eye(l, a.color()));
+        let eye_b = outside_b.iter().any(|l| self.is_eye(l, b.color()));
+        let (libs_a, libs_b, extra) = match (eye_a, eye_b) {
+            (true, false)  => (outside_a.len() + shared, outside_b.len(), 0),
+            (false, true)  => (outside_a.len(), outside_b.len() + shared, 0),
+            (true, true)   => (outside_a.len(), outside_b.len(), shared),
+            (false, false) => (outside_a.len(), outside_b.len(), if shared > 0 { shared - 1 } else { 0 }),
+        };
+        // The player moving first needs `extra` liberties more than
+        // the opponent to win, the player moving second one more.
+        let color = a.color();
+        let winner = if libs_a >= libs_b + extra {
+            color
+        } else if libs_b >= libs_a + extra + 1 {
+            color.opposite()
+        } else {
+            Empty
+        };
+        let moves = if winner == color { self.semeai_moves(a, b) } else { vec!() };
+        Some(Semeai {
+            own: own,
+            other: other,
+            winner: winner,
+            critical: winner == color && libs_a < libs_b + extra + 1,
+            moves: moves,
+        })
+    }
+
+    // All races between the chains of the player and adjacent
+    // opponent chains, with the player moving first. Chains in atari
+    // are left to the atari checks.
+    pub fn semeais(&self, color: Color) -> Vec<Semeai> {
+        let in_race = |chain: &&Chain| chain.liberties().len() > 1 && chain.liberties().len() <= SEMEAI_LIBERTIES;
+        let mut semeais = vec!();
+        for a in self.chains().iter().filter(|chain| chain.color() == color).filter(&in_race) {
+            for b in self.chains().iter().filter(|chain| chain.color() == color.opposite()).filter(&in_race) {
+                if let Some(semeai) = self.semeai(a.coords()[0], b.coords()[0]) {
+                    semeais.push(semeai);
+                }
+            }
+        }
+        semeais
+    }
+
+    fn are_adjacent(&self, a: &Chain, b: &Chain) -> bool {
+        a.coords().iter().any(|&c| self.neighbours(c).iter().any(|n| b.coords().contains(n)))
+    }
+
+    // The opponent's outside liberties come first, then the shared
+    // ones and an eye is only filled last.
+    fn semeai_moves(&self, a: &Chain, b: &Chain) -> Vec<Coord> {
+        let legal = |c: &&Coord| self.is_legal(Play(a.color(), c.col, c.row)).is_ok();
+        let outside: Vec<Coord> = b.liberties().iter()
+            .filter(|l| !a.liberties().contains(*l) && !self.is_eye(l, b.color()))
+            .filter(&legal)
+            .cloned()
+            .collect();
+        if outside.len() > 0 {
+            return outside;
+        }
+        let shared: Vec<Coord> = b.liberties().iter()
+            .filter(|l| a.liberties().contains(*l))
+            .filter(&legal)
+            .cloned()
+            .collect();
+        if shared.len() > 0 {
+            return shared;
+        }
+        b.liberties().iter().filter(&legal).cloned().collect()
+    }
+
+}
@@ -0,0 +1,220 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Empty;
+use board::MAX_SIZE;
+use board::Move;
+use board::Play;
+use board::White;
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand::XorShiftRng;
+
+pub use self::Symmetry::AntiTranspose;
+pub use self::Symmetry::FlipHorizontal;
+pub use self::Symmetry::FlipVertical;
+pub use self::Symmetry::Identity;
+pub use self::Symmetry::Rotate180;
+pub use self::Symmetry::Rotate270;
+pub use self::Symmetry::Rotate90;
+pub use self::Symmetry::Transpose;
+
+// The 8 rotations and reflections of a board. The first 4 only
+// mirror the board horizontally and/or vertically, the last 4 also
+// swap the axes so they can only be used on square boards.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Symmetry {
+    Identity,
+    FlipHorizontal,
+    FlipVertical,
+    Rotate180,
+    Transpose,
+    Rotate90,
+    Rotate270,
+    AntiTranspose,
+}
+
+impl Symmetry {
+
+    pub fn all() -> Vec<Symmetry> {
+        vec!(Identity, FlipHorizontal, FlipVertical, Rotate180, Transpose, Rotate90, Rotate270, AntiTranspose)
+    }
+
+    pub fn for_board_size(width: u8, height: u8) -> Vec<Symmetry> {
+        Self::all().into_iter().filter(|s| width == height || !s.swaps_axes()).collect()
+    }
+
+    pub fn swaps_axes(&self) -> bool {
+        *self >= Transpose
+    }
+
+    pub fn inverse(&self) -> Symmetry {
+        match *self {
+            Rotate90  => Rotate270,
+            Rotate270 => Rotate90,
+            s         => s,
+        }
+    }
+
+    // The size of a width x height board after the transformation.
+    pub fn transformed_size(&self, width: u8, height: u8) -> (u8, u8) {
+        if self.swaps_axes() { (height, width) } else { (width, height) }
+    }
+
+}
+
+// The keys are the same every time so that canonical hashes can be
+// stored (e.g. in the opening book). They are indexed as if the
+// board had the maximum size so they don't depend on the board size.
+#[derive(Debug)]
+pub struct HashKeys {
+    black: Vec<u64>,
+    white: Vec<u64>,
+    width: Vec<u64>,
+    height: Vec<u64>,
+    white_to_play: u64,
+}
+
+impl HashKeys {
+
+    pub fn new() -> HashKeys {
+        let mut rng: XorShiftRng = SeedableRng::from_seed([0x6c078965, 0x9908b0df, 0x9d2c5680, 0xefc60000]);
+        let points = MAX_SIZE as usize * MAX_SIZE as usize;
+        HashKeys {
+            black: (0..points).map(|_| rng.gen()).collect(),
+            white: (0..points).map(|_| rng.gen()).collect(),
+            width: (0..MAX_SIZE as usize + 1).map(|_| rng.gen()).collect(),
+            height: (0..MAX_SIZE as usize + 1).map(|_| rng.gen()).collect(),
+            white_to_play: rng.gen(),
+        }
+    }
+
+}
+
+impl Coord {
+
+    pub fn transform(&self, symmetry: Symmetry, width: u8, height: u8) -> Coord {
+        let (x, y) = (self.col - 1, self.row - 1);
+        let (w, h) = (width - 1, height - 1);
+        let (x, y) = match symmetry {
+            Identity       => (x, y),
+            FlipHorizontal => (w - x, y),
+            FlipVertical   => (x, h - y),
+            Rotate180      => (w - x, h - y),
+            Transpose      => (y, x),
+            Rotate90       => (h - y, x),
+            Rotate270      => (y, w - x),
+            AntiTranspose  => (h - y, w - x),
+        };
+        Coord::new(x + 1, y + 1)
+    }
+
+}
+
+impl Move {
+
+    // Passes and resignations stay the same.
+    pub fn transform(&self, symmetry: Symmetry, width: u8, height: u8) -> Move {
+        match *self {
+            Play(color, col, row) => {
+                let c = Coord::new(col, row).transform(symmetry, width, height);
+                Play(color, c.col, c.row)
+            },
+            m => m
+        }
+    }
+
+}
+
+impl Board {
+
+    // A copy of the board with all stones (and the ko point) moved
+    // to their symmetric points.
+    pub fn transformed(&self, symmetry: Symmetry) -> Board {
+        let (width, height) = symmetry.transformed_size(self.width, self.height);
+        let mut board = Board::rectangular(width, height, self.komi, self.ruleset);
+        // The stones of a legal position can be placed in any order
+        // without capturing anything.
+        for c in Coord::for_board_size(self.width, self.height) {
+            let color = self.color(&c);
+            if color != Empty {
+                let t = c.transform(symmetry, self.width, self.height);
+                board.play_legal_move(Play(color, t.col, t.row));
+            }
+        }
+        board.adv_stones_removed = self.adv_stones_removed.iter()
+            .map(|c| c.transform(symmetry, self.width, self.height)).collect();
+        board.friend_stones_removed = self.friend_stones_removed.iter()
+            .map(|c| c.transform(symmetry, self.width, self.height)).collect();
+        board.ko = self.ko.map(|c| c.transform(symmetry, self.width, self.height));
+        board.consecutive_passes = self.consecutive_passes;
+        board.previous_player = self.previous_player;
+        board.resigned_by = self.resigned_by;
+        board
+    }
+
+    // The Zobrist hash of the board as it looks after the
+    // transformation. The ko point isn't part of the hash.
+    pub fn symmetric_hash(&self, symmetry: Symmetry) -> u64 {
+        let keys = &self.cache.hash_keys;
+        let (width, height) = symmetry.transformed_size(self.width, self.height);
+        let mut hash = keys.width[width as usize] ^ keys.height[height as usize];
+        if self.next_player() == White {
+            hash ^= keys.white_to_play;
+        }
+        for c in Coord::for_board_size(self.width, self.height) {
+            let color = self.color(&c);
+            if color != Empty {
+                let index = c.transform(symmetry, self.width, self.height).to_index(MAX_SIZE);
+                hash ^= if color == Black { keys.black[index] } else { keys.white[index] };
+            }
+        }
+        hash
+    }
+
+    // The hash is the same for all rotations and reflections of the
+    // board: it's the smallest hash of all symmetric boards. The
+    // symmetry that leads to that board is returned as well, to map
+    // moves into (and with its inverse out of) the canonical frame.
+    pub fn canonical_hash(&self) -> (u64, Symmetry) {
+        Symmetry::for_board_size(self.width, self.height).into_iter()
+            .map(|s| (self.symmetric_hash(s), s))
+            .min()
+            .unwrap()
+    }
+
+    pub fn to_canonical_frame(&self, m: &Move) -> Move {
+        let (_, symmetry) = self.canonical_hash();
+        m.transform(symmetry, self.width, self.height)
+    }
+
+    pub fn from_canonical_frame(&self, m: &Move) -> Move {
+        let (_, symmetry) = self.canonical_hash();
+        let (width, height) = symmetry.transformed_size(self.width, self.height);
+        m.transform(symmetry.inverse(), width, height)
+    }
+
+}
@@ -42,7 +42,12 @@ mod diagonals;
 mod eye;
 mod ko;
 mod hypotheticals;
+mod nakade;
+mod neighbourhood;
 mod reading;
+mod seki;
+mod semeai;
+mod symmetry;
 
 #[test]
 fn getting_a_valid_coord_returns_a_color() {
@@ -495,3 +500,35 @@ fn adv_stones_removed_only_contains_each_coord_once() {
     removed_coords.dedup();
     assert_eq!(removed_coords.len(), board.adv_stones_removed().len());
 }
+
+#[test]
+fn a_rectangular_board_has_the_correct_number_of_points() {
+    let b = Board::rectangular(13, 9, 6.5, KgsChinese);
+    assert_eq!(117, b.vacant_point_count());
+    assert_eq!(Empty, b.color(&Coord::new(13, 9)));
+}
+
+#[test]
+fn playing_outside_of_a_rectangular_board_is_illegal() {
+    let mut b = Board::rectangular(13, 9, 6.5, KgsChinese);
+    assert_eq!(Err(IllegalMove::PlayOutOfBoard), b.play(Play(Black, 9, 13)));
+    assert!(b.play(Play(Black, 13, 9)).is_ok());
+}
+
+#[test]
+fn captures_work_on_a_25x25_board() {
+    let mut b = Board::new(25, 6.5, KgsChinese);
+    b.play(Play(Black, 25, 25));
+    b.play(Play(White, 24, 25));
+    b.play(Pass(Black));
+    b.play(Play(White, 25, 24));
+    assert_eq!(Empty, b.color(&Coord::new(25, 25)));
+}
+
+#[test]
+fn valid_sizes() {
+    assert!(Board::is_valid_size(25, 25));
+    assert!(Board::is_valid_size(13, 9));
+    assert!(!Board::is_valid_size(26, 19));
+    assert!(!Board::is_valid_size(0, 0));
+}
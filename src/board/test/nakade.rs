@@ -0,0 +1,94 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use board::Black;
+use board::Coord;
+use board::EyeShape;
+use board::Play;
+use board::White;
+use sgf::test::board_from_sgf;
+
+fn vital_point(name: &str, shape: EyeShape) -> Coord {
+    let board = board_from_sgf(&format!("nakade/{}.sgf", name));
+    let nakade = board.nakade(&Coord::from_gtp("A9")).unwrap();
+    assert_eq!(shape, nakade.shape);
+    assert_eq!(White, nakade.owner);
+    nakade.vital
+}
+
+#[test]
+fn straight_three() {
+    assert_eq!(Coord::from_gtp("B9"), vital_point("straight-three", EyeShape::StraightThree));
+}
+
+#[test]
+fn bent_three() {
+    assert_eq!(Coord::from_gtp("A9"), vital_point("bent-three", EyeShape::BentThree));
+}
+
+#[test]
+fn pyramid_four() {
+    assert_eq!(Coord::from_gtp("B9"), vital_point("pyramid-four", EyeShape::PyramidFour));
+}
+
+#[test]
+fn bulky_five() {
+    assert_eq!(Coord::from_gtp("B8"), vital_point("bulky-five", EyeShape::BulkyFive));
+}
+
+#[test]
+fn flower_six() {
+    let board = board_from_sgf("nakade/flower-six.sgf");
+    let nakade = board.nakade(&Coord::from_gtp("C7")).unwrap();
+    assert_eq!(EyeShape::FlowerSix, nakade.shape);
+    assert_eq!(Coord::from_gtp("B8"), nakade.vital);
+    assert_eq!(6, nakade.space.len());
+}
+
+#[test]
+fn straight_four_makes_two_eyes() {
+    let board = board_from_sgf("nakade/straight-four.sgf");
+    assert_eq!(4, board.eye_space(&Coord::from_gtp("A9")).unwrap().1.len());
+    assert!(board.nakade(&Coord::from_gtp("A9")).is_none());
+}
+
+#[test]
+fn eye_spaces_must_be_surrounded_by_one_color() {
+    let board = board_from_sgf("nakade/not-enclosed.sgf");
+    assert!(board.eye_space(&Coord::from_gtp("A9")).is_none());
+    assert!(board.nakade(&Coord::from_gtp("A9")).is_none());
+}
+
+#[test]
+fn open_areas_are_no_eye_space() {
+    let board = board_from_sgf("nakade/straight-three.sgf");
+    assert!(board.eye_space(&Coord::from_gtp("E5")).is_none());
+}
+
+#[test]
+fn both_players_play_the_vital_point() {
+    let board = board_from_sgf("nakade/straight-three.sgf");
+    assert_eq!(Some(Play(Black, 2, 9)), board.nakade_move(Play(Black, 1, 9)));
+    assert_eq!(Some(Play(White, 2, 9)), board.nakade_move(Play(White, 3, 9)));
+}
@@ -0,0 +1,77 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Empty;
+use board::Play;
+use board::White;
+use ruleset::KgsChinese;
+use sgf::Parser;
+
+use std::path::Path;
+
+fn assert_codes_match_the_board(board: &Board) {
+    for coord in Coord::for_board_size(board.width(), board.height()) {
+        let colors = Board::colors_for_pattern_code(board.pattern_code(&coord));
+        assert_eq!(board.neighbours8_checked(coord), colors);
+        assert_eq!(board.pattern_code(&coord), Board::pattern_code_for_colors(&colors));
+    }
+}
+
+#[test]
+fn codes_of_an_empty_board_include_the_border() {
+    let board = Board::new(9, 6.5, KgsChinese);
+    assert_codes_match_the_board(&board);
+    let colors = Board::colors_for_pattern_code(board.pattern_code(&Coord::new(5, 5)));
+    assert!(colors.iter().all(|&c| c == Some(Empty)));
+}
+
+#[test]
+fn codes_are_updated_when_stones_are_played_and_captured() {
+    let mut board = Board::new(9, 6.5, KgsChinese);
+    board.play(Play(Black, 1, 1)).unwrap();
+    board.play(Play(White, 2, 1)).unwrap();
+    assert_codes_match_the_board(&board);
+    board.play(Play(Black, 5, 5)).unwrap();
+    board.play(Play(White, 1, 2)).unwrap();
+    assert_eq!(Empty, board.color(&Coord::new(1, 1)));
+    assert_codes_match_the_board(&board);
+}
+
+#[test]
+fn codes_match_the_board_after_a_whole_game() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/endgame-black-wins.sgf")).unwrap();
+    let board = parser.game().unwrap().board();
+    assert_codes_match_the_board(&board);
+}
+
+#[test]
+fn codes_work_on_rectangular_boards() {
+    let mut board = Board::rectangular(7, 5, 6.5, KgsChinese);
+    board.play(Play(Black, 7, 5)).unwrap();
+    board.play(Play(White, 6, 4)).unwrap();
+    assert_codes_match_the_board(&board);
+}
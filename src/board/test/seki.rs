@@ -0,0 +1,52 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use board::Coord;
+use sgf::test::board_from_sgf;
+
+#[test]
+fn the_shared_liberties_of_a_seki_are_found() {
+    let board = board_from_sgf("semeai/seki.sgf");
+    assert_eq!(vec!(Coord::from_gtp("A9"), Coord::from_gtp("D9")), board.seki_liberties());
+}
+
+#[test]
+fn the_stones_in_seki_are_found() {
+    let board = board_from_sgf("semeai/seki.sgf");
+    let expected: Vec<Coord> = vec!("A8", "B8", "B9", "C8", "C9", "D8").iter().map(|v| Coord::from_gtp(v)).collect();
+    assert_eq!(expected, board.seki_stones());
+}
+
+#[test]
+fn a_race_that_one_side_wins_is_no_seki() {
+    let board = board_from_sgf("semeai/no-eyes.sgf");
+    assert!(board.seki_liberties().is_empty());
+    assert!(board.seki_stones().is_empty());
+}
+
+#[test]
+fn a_living_group_is_no_seki() {
+    let board = board_from_sgf("life-and-death/alive.sgf");
+    assert!(board.seki_stones().is_empty());
+}
@@ -0,0 +1,94 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use board::Black;
+use board::Coord;
+use board::Empty;
+use board::White;
+use sgf::test::board_from_sgf;
+
+fn coords(vertices: Vec<&str>) -> Vec<Coord> {
+    let mut coords: Vec<Coord> = vertices.iter().map(|v| Coord::from_gtp(v)).collect();
+    coords.sort();
+    coords
+}
+
+#[test]
+fn without_eyes_the_player_to_move_wins_a_race_with_equal_liberties() {
+    let board = board_from_sgf("semeai/no-eyes.sgf");
+    let mut semeai = board.semeai(Coord::from_gtp("B9"), Coord::from_gtp("C9")).unwrap();
+    assert_eq!(Black, semeai.winner);
+    assert!(semeai.critical);
+    semeai.moves.sort();
+    assert_eq!(coords(vec!("D9", "D6")), semeai.moves);
+
+    let mut semeai = board.semeai(Coord::from_gtp("C9"), Coord::from_gtp("B9")).unwrap();
+    assert_eq!(White, semeai.winner);
+    assert!(semeai.critical);
+    semeai.moves.sort();
+    assert_eq!(coords(vec!("A9", "A8")), semeai.moves);
+}
+
+#[test]
+fn the_shared_liberties_count_for_the_chain_with_an_eye() {
+    let board = board_from_sgf("semeai/one-eye.sgf");
+    let mut semeai = board.semeai(Coord::from_gtp("B9"), Coord::from_gtp("C9")).unwrap();
+    assert_eq!(Black, semeai.winner);
+    semeai.moves.sort();
+    assert_eq!(coords(vec!("D9", "D6")), semeai.moves);
+}
+
+#[test]
+fn the_eye_is_filled_last() {
+    let board = board_from_sgf("semeai/one-eye.sgf");
+    let semeai = board.semeai(Coord::from_gtp("C9"), Coord::from_gtp("B9")).unwrap();
+    assert_eq!(White, semeai.winner);
+    assert_eq!(coords(vec!("A7")), semeai.moves);
+}
+
+#[test]
+fn shared_liberties_without_outside_liberties_are_a_seki() {
+    let board = board_from_sgf("semeai/seki.sgf");
+    let semeai = board.semeai(Coord::from_gtp("B9"), Coord::from_gtp("A8")).unwrap();
+    assert_eq!(Empty, semeai.winner);
+    assert!(!semeai.critical);
+    assert!(semeai.moves.is_empty());
+}
+
+#[test]
+fn chains_that_do_not_touch_are_not_in_a_race() {
+    let board = board_from_sgf("semeai/no-eyes.sgf");
+    assert_eq!(None, board.semeai(Coord::from_gtp("B9"), Coord::from_gtp("D8")));
+    assert_eq!(None, board.semeai(Coord::from_gtp("B9"), Coord::from_gtp("A9")));
+}
+
+#[test]
+fn semeais_finds_the_critical_race() {
+    let board = board_from_sgf("semeai/no-eyes.sgf");
+    let semeais = board.semeais(Black);
+    assert_eq!(3, semeais.len());
+    let critical: Vec<_> = semeais.iter().filter(|s| s.critical).collect();
+    assert_eq!(1, critical.len());
+    assert_eq!(White, board.color(&critical[0].other));
+}
@@ -0,0 +1,119 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Pass;
+use board::Play;
+use board::Symmetry;
+use ruleset::KgsChinese;
+use sgf::test::board_from_sgf;
+
+#[test]
+fn only_the_mirror_symmetries_apply_to_rectangular_boards() {
+    assert_eq!(8, Symmetry::for_board_size(19, 19).len());
+    let symmetries = Symmetry::for_board_size(13, 9);
+    assert_eq!(4, symmetries.len());
+    assert!(symmetries.iter().all(|s| !s.swaps_axes()));
+}
+
+#[test]
+fn the_inverse_maps_coords_back() {
+    for &(width, height) in [(19, 19), (13, 9)].iter() {
+        for s in Symmetry::for_board_size(width, height) {
+            let (w, h) = s.transformed_size(width, height);
+            for c in Coord::for_board_size(width, height) {
+                let t = c.transform(s, width, height);
+                assert!(t.is_inside(w, h));
+                assert_eq!(c, t.transform(s.inverse(), w, h));
+            }
+        }
+    }
+}
+
+#[test]
+fn transforming_a_move_keeps_the_color_and_ignores_passes() {
+    assert_eq!(Play(Black, 19, 1), Play(Black, 1, 1).transform(Symmetry::FlipHorizontal, 19, 19));
+    assert_eq!(Play(Black, 2, 1), Play(Black, 1, 2).transform(Symmetry::Transpose, 19, 19));
+    assert_eq!(Pass(Black), Pass(Black).transform(Symmetry::Rotate90, 19, 19));
+}
+
+#[test]
+fn transformed_boards_have_the_stones_on_the_symmetric_points() {
+    let board = board_from_sgf("ladders.sgf");
+    for s in Symmetry::all() {
+        let transformed = board.transformed(s);
+        assert_eq!(board.next_player(), transformed.next_player());
+        for c in Coord::for_board_size(19, 19) {
+            assert_eq!(board.color(&c), transformed.color(&c.transform(s, 19, 19)));
+        }
+        assert_eq!(board.symmetric_hash(s), transformed.symmetric_hash(Symmetry::Identity));
+    }
+}
+
+#[test]
+fn all_symmetric_boards_have_the_same_canonical_hash() {
+    for path in ["ladders.sgf", "rectangular.sgf", "twomoves.sgf"].iter() {
+        let board = board_from_sgf(path);
+        let (hash, _) = board.canonical_hash();
+        for s in Symmetry::for_board_size(board.width(), board.height()) {
+            let (transformed_hash, _) = board.transformed(s).canonical_hash();
+            assert_eq!(hash, transformed_hash);
+        }
+    }
+}
+
+#[test]
+fn different_positions_have_different_canonical_hashes() {
+    let board = board_from_sgf("ladders.sgf");
+    let mut other = board.clone();
+    other.play(Play(other.next_player(), 10, 10)).unwrap();
+    assert!(board.canonical_hash().0 != other.canonical_hash().0);
+    assert!(board.canonical_hash().0 != Board::new(19, 7.5, KgsChinese).canonical_hash().0);
+}
+
+#[test]
+fn symmetric_moves_map_to_the_same_canonical_move() {
+    let board = board_from_sgf("ladders.sgf");
+    let color = board.next_player();
+    for s in Symmetry::all() {
+        let transformed = board.transformed(s);
+        for c in Coord::for_board_size(19, 19) {
+            let m = Play(color, c.col, c.row);
+            let canonical = board.to_canonical_frame(&m);
+            assert_eq!(canonical, transformed.to_canonical_frame(&m.transform(s, 19, 19)));
+            assert_eq!(m, board.from_canonical_frame(&canonical));
+        }
+    }
+}
+
+#[test]
+fn moves_map_back_from_the_canonical_frame_on_rectangular_boards() {
+    let board = board_from_sgf("rectangular.sgf");
+    for c in Coord::for_board_size(13, 9) {
+        let m = Play(Black, c.col, c.row);
+        assert_eq!(m, board.from_canonical_frame(&board.to_canonical_frame(&m)));
+    }
+}
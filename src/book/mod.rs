@@ -0,0 +1,160 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Coord;
+use board::MAX_SIZE;
+use board::Move;
+use board::Play;
+use game::Game;
+use sgf;
+use sgf::ParseError;
+use sgf::Parser;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+mod test;
+
+// Only the first moves of each game are added to the book.
+pub const DEPTH: usize = 20;
+
+// Maps positions to the moves that were played in them and how
+// often. Positions are identified by a Zobrist hash that is the same
+// for all rotations and reflections of the board (the smallest hash
+// of all symmetric boards). The moves are stored in the frame of
+// that symmetric board.
+pub struct Book {
+    entries: HashMap<u64, HashMap<Coord, usize>>,
+}
+
+impl Book {
+
+    pub fn new() -> Book {
+        Book { entries: HashMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn from_file(path: &Path) -> Result<Book, String> {
+        let mut contents = String::new();
+        match File::open(path).and_then(|mut file| file.read_to_string(&mut contents)) {
+            Ok(_) => Self::parse(&contents),
+            Err(e) => Err(format!("Can't read {}: {}", path.display(), e))
+        }
+    }
+
+    // One entry per line: the hash of the position (in hex), the
+    // move in the frame of the canonical board and how often it was
+    // played. Empty lines and lines starting with # are ignored.
+    pub fn parse(contents: &str) -> Result<Book, String> {
+        let mut book = Book::new();
+        for (n, line) in contents.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.len() == 0 || trimmed.starts_with("#") {
+                continue;
+            }
+            let words: Vec<&str> = trimmed.split_whitespace().collect();
+            if words.len() != 3 {
+                return Err(format!("Line {}: expected 'HASH MOVE COUNT'", n+1));
+            }
+            let hash = match u64::from_str_radix(words[0], 16) {
+                Ok(hash) => hash,
+                Err(_) => return Err(format!("Line {}: invalid hash '{}'", n+1, words[0]))
+            };
+            // The hash identifies the board, so only the largest board
+            // size can be checked here.
+            let coord = match Coord::parse_gtp(words[1], MAX_SIZE, MAX_SIZE) {
+                Some(coord) => coord,
+                None => return Err(format!("Line {}: invalid move '{}'", n+1, words[1]))
+            };
+            let count = match words[2].parse::<usize>() {
+                Ok(count) => count,
+                Err(_) => return Err(format!("Line {}: invalid count '{}'", n+1, words[2]))
+            };
+            *book.entries.entry(hash).or_insert(HashMap::new()).entry(coord).or_insert(0) += count;
+        }
+        Ok(book)
+    }
+
+    pub fn to_string(&self) -> String {
+        let mut lines: Vec<String> = self.entries.iter()
+            .flat_map(|(hash, moves)| {
+                moves.iter().map(move |(coord, count)| format!("{:016x} {} {}", hash, coord.to_gtp(), count))
+            })
+            .collect();
+        lines.sort();
+        let mut s = String::new();
+        for line in lines {
+            s.push_str(&line);
+            s.push('\n');
+        }
+        s
+    }
+
+    // Adds all *.sgf files in the directory and returns the number of
+    // games that could be read. Broken games are skipped.
+    pub fn add_directory(&mut self, dir: &Path) -> Result<usize, String> {
+        sgf::for_each_game(dir, |parser| self.add_game(parser))
+    }
+
+    pub fn add_game(&mut self, parser: &Parser) -> Result<(), ParseError> {
+        let mut moves = 0;
+        try!(parser.replay(|game, m| {
+            if moves < DEPTH && !m.is_pass() && !m.is_resign() && *m.color() == game.next_player() {
+                self.add(&game.board(), m);
+            }
+            moves += 1;
+        }));
+        Ok(())
+    }
+
+    pub fn add(&mut self, board: &Board, m: &Move) {
+        let (hash, symmetry) = board.canonical_hash();
+        let coord = m.coord().transform(symmetry, board.width(), board.height());
+        *self.entries.entry(hash).or_insert(HashMap::new()).entry(coord).or_insert(0) += 1;
+    }
+
+    // The most frequently played move in this position, if it's
+    // legal.
+    pub fn lookup(&self, game: &Game) -> Option<Move> {
+        let board = game.board();
+        let (hash, symmetry) = board.canonical_hash();
+        let color = game.next_player();
+        self.entries.get(&hash)
+            .and_then(|moves| {
+                moves.iter()
+                    .max_by_key(|&(coord, &count)| (count, -(coord.to_index(MAX_SIZE) as isize)))
+                    .map(|(coord, _)| *coord)
+            })
+            .map(|coord| {
+                let (width, height) = symmetry.transformed_size(board.width(), board.height());
+                let c = coord.transform(symmetry.inverse(), width, height);
+                Play(color, c.col, c.row)
+            })
+            .and_then(|m| if game.play(m).is_ok() { Some(m) } else { None })
+    }
+
+}
@@ -0,0 +1,105 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Play;
+use board::White;
+use game::Game;
+use ruleset::KgsChinese;
+use super::Book;
+
+use std::path::Path;
+
+fn game_with_black_at(col: u8, row: u8) -> Game {
+    Game::new(9, 6.5, KgsChinese).play(Play(Black, col, row)).unwrap()
+}
+
+fn book() -> Book {
+    let mut book = Book::new();
+    book.add(&game_with_black_at(3, 3).board(), &Play(White, 7, 7));
+    book
+}
+
+#[test]
+fn returns_the_move_for_a_known_position() {
+    assert_eq!(Some(Play(White, 7, 7)), book().lookup(&game_with_black_at(3, 3)));
+}
+
+#[test]
+fn returns_nothing_for_an_unknown_position() {
+    assert_eq!(None, book().lookup(&game_with_black_at(5, 5)));
+}
+
+#[test]
+fn finds_mirrored_positions() {
+    assert_eq!(Some(Play(White, 3, 7)), book().lookup(&game_with_black_at(7, 3)));
+}
+
+#[test]
+fn finds_rotated_positions() {
+    // Rotated by 90 degrees clockwise (3,3) becomes (3,7) and (7,7)
+    // becomes (7,3).
+    assert_eq!(Some(Play(White, 7, 3)), book().lookup(&game_with_black_at(3, 7)));
+}
+
+#[test]
+fn only_mirrors_rectangular_boards() {
+    let mut book = Book::new();
+    let game = Game::rectangular(9, 7, 6.5, KgsChinese).play(Play(Black, 3, 3)).unwrap();
+    book.add(&game.board(), &Play(White, 7, 6));
+    let mirrored = Game::rectangular(9, 7, 6.5, KgsChinese).play(Play(Black, 3, 5)).unwrap();
+    assert_eq!(Some(Play(White, 7, 2)), book.lookup(&mirrored));
+}
+
+#[test]
+fn prefers_the_most_played_move() {
+    let mut book = book();
+    let board = game_with_black_at(3, 3).board();
+    book.add(&board, &Play(White, 5, 5));
+    book.add(&board, &Play(White, 5, 5));
+    assert_eq!(Some(Play(White, 5, 5)), book.lookup(&game_with_black_at(3, 3)));
+}
+
+#[test]
+fn can_be_written_and_read_again() {
+    let book = Book::parse(&book().to_string()).unwrap();
+    assert_eq!(Some(Play(White, 7, 7)), book.lookup(&game_with_black_at(3, 3)));
+}
+
+#[test]
+fn parse_fails_for_invalid_lines() {
+    assert!(Book::parse("123 d4\n").is_err());
+    assert!(Book::parse("xyz d4 1\n").is_err());
+    assert!(Book::parse("123 44 1\n").is_err());
+}
+
+#[test]
+fn add_directory_adds_the_opening_moves() {
+    let mut book = Book::new();
+    assert_eq!(Ok(2), book.add_directory(Path::new("fixtures/training")));
+    let m = book.lookup(&Game::new(9, 7.0, KgsChinese)).unwrap();
+    let openings = vec!(
+        Play(Black, 5, 5),
+        Play(Black, 5, 4), Play(Black, 4, 5), Play(Black, 6, 5), Play(Black, 5, 6));
+    assert!(openings.contains(&m));
+}
@@ -0,0 +1,338 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+// Reads and writes the configuration as a simple subset of TOML:
+// "[section]" headers, "key = value" lines and "#" comments. Strings
+// may be quoted with ' or " but escape sequences aren't supported.
+// An empty string means that no file is used.
+
+use playout::Policy;
+use ruleset::Ruleset;
+use super::Config;
+use super::Hint;
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+pub trait Value: Sized {
+
+    fn parse_value(s: &str) -> Result<Self, ()>;
+
+    fn to_value(&self) -> String;
+
+    fn to_toml(&self) -> String {
+        self.to_value()
+    }
+
+    // The type as used in the parameter commands of GoGui.
+    fn gogui_type(&self) -> String {
+        String::from("[string]")
+    }
+
+}
+
+macro_rules! plain_value {
+    ($($t:ty),+) => {
+        $(
+            impl Value for $t {
+                fn parse_value(s: &str) -> Result<$t, ()> {
+                    s.parse().map_err(|_| ())
+                }
+
+                fn to_value(&self) -> String {
+                    format!("{}", self)
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! string_value {
+    ($($t:ty),+) => {
+        $(
+            impl Value for $t {
+                fn parse_value(s: &str) -> Result<$t, ()> {
+                    s.parse().map_err(|_| ())
+                }
+
+                fn to_value(&self) -> String {
+                    format!("{}", self)
+                }
+
+                fn to_toml(&self) -> String {
+                    format!("'{}'", self)
+                }
+
+                fn gogui_type(&self) -> String {
+                    format!("[list/{}]", self.hint_str().replace("|", "/"))
+                }
+            }
+        )+
+    }
+}
+
+plain_value!(f32, usize);
+string_value!(Policy, Ruleset);
+
+impl Value for bool {
+
+    fn parse_value(s: &str) -> Result<bool, ()> {
+        s.parse().map_err(|_| ())
+    }
+
+    fn to_value(&self) -> String {
+        format!("{}", self)
+    }
+
+    fn gogui_type(&self) -> String {
+        String::from("[bool]")
+    }
+
+}
+
+impl Value for Option<String> {
+
+    fn parse_value(s: &str) -> Result<Option<String>, ()> {
+        if s.is_empty() { Ok(None) } else { Ok(Some(String::from(s))) }
+    }
+
+    fn to_value(&self) -> String {
+        match *self {
+            Some(ref s) => s.clone(),
+            None => String::new()
+        }
+    }
+
+    fn to_toml(&self) -> String {
+        format!("'{}'", self.to_value())
+    }
+
+}
+
+// Generates the accessors that work with the name of a field, e.g.
+// "uct.priors.capture_many". The keys are grouped by section.
+macro_rules! fields {
+    ($($key:tt => $($field:ident).+),+) => {
+        impl Config {
+
+            /// The keys of all settings, as used in the
+            /// configuration file and by `get` and `set`.
+            pub fn keys() -> Vec<&'static str> {
+                vec!($($key),+)
+            }
+
+            /// The value of a setting, e.g. "uct.priors.capture_many".
+            pub fn get(&self, key: &str) -> Result<String, String> {
+                match key {
+                    $($key => Ok(self.$($field).+.to_value()),)+
+                    _ => Err(format!("Unknown key '{}'", key))
+                }
+            }
+
+            /// Only checks the type of the value, call check() to
+            /// validate the whole configuration.
+            pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $($key => {
+                        self.$($field).+ = try!(Value::parse_value(value)
+                            .map_err(|_| format!("Invalid value '{}' for {}", value, key)));
+                    },)+
+                    _ => return Err(format!("Unknown key '{}'", key))
+                }
+                Ok(())
+            }
+
+            pub fn gogui_type(&self, key: &str) -> Result<String, String> {
+                match key {
+                    $($key => Ok(self.$($field).+.gogui_type()),)+
+                    _ => Err(format!("Unknown key '{}'", key))
+                }
+            }
+
+            fn get_toml(&self, key: &str) -> String {
+                match key {
+                    $($key => self.$($field).+.to_toml(),)+
+                    _ => panic!("Unknown key '{}'", key)
+                }
+            }
+
+        }
+    }
+}
+
+fields!(
+    "book_file" => book_file,
+    "concurrent_sessions" => concurrent_sessions,
+    "debug" => debug,
+    "large_patterns_file" => large_patterns_file,
+    "log" => log,
+    "patterns_file" => patterns_file,
+    "play_out_aftermath" => play_out_aftermath,
+    "ruleset" => ruleset,
+    "solver_node_limit" => solver_node_limit,
+    "threads" => threads,
+    "use_book" => use_book,
+    "playout.atari_check" => playout.atari_check,
+    "playout.ladder_check" => playout.ladder_check,
+    "playout.last_moves_for_heuristics" => playout.last_moves_for_heuristics,
+    "playout.nakade" => playout.nakade,
+    "playout.no_self_atari_cutoff" => playout.no_self_atari_cutoff,
+    "playout.pattern_probability" => playout.pattern_probability,
+    "playout.play_in_middle_of_eye" => playout.play_in_middle_of_eye,
+    "playout.policy" => playout.policy,
+    "playout.seki_check" => playout.seki_check,
+    "playout.semeai_check" => playout.semeai_check,
+    "playout.use_patterns" => playout.use_patterns,
+    "playout.use_pseudo_liberty_board" => playout.use_pseudo_liberty_board,
+    "timer.c" => timer.c,
+    "uct.end_of_game_cutoff" => uct.end_of_game_cutoff,
+    "uct.expand_after" => uct.expand_after,
+    "uct.max_playouts" => uct.max_playouts,
+    "uct.reuse_subtree" => uct.reuse_subtree,
+    "uct.tuned" => uct.tuned,
+    "uct.priors.capture_many" => uct.priors.capture_many,
+    "uct.priors.capture_one" => uct.priors.capture_one,
+    "uct.priors.empty" => uct.priors.empty,
+    "uct.priors.large_patterns" => uct.priors.large_patterns,
+    "uct.priors.neutral_plays" => uct.priors.neutral_plays,
+    "uct.priors.neutral_wins" => uct.priors.neutral_wins,
+    "uct.priors.patterns" => uct.priors.patterns,
+    "uct.priors.self_atari" => uct.priors.self_atari,
+    "uct.priors.semeai" => uct.priors.semeai,
+    "uct.priors.use_empty" => uct.priors.use_empty,
+    "uct.priors.use_large_patterns" => uct.priors.use_large_patterns,
+    "uct.priors.use_patterns" => uct.priors.use_patterns,
+    "uct.priors.use_semeai" => uct.priors.use_semeai
+);
+
+impl Config {
+
+    /// Applies the settings of a configuration file, see `dump` for
+    /// the format.
+    pub fn load(&mut self, path: &Path) -> Result<(), String> {
+        self.load_with_ruleset(path, None)
+    }
+
+    /// Like load(), but a given ruleset takes the place of the one in
+    /// the file.
+    pub fn load_with_ruleset(&mut self, path: &Path, ruleset: Option<Ruleset>) -> Result<(), String> {
+        let mut contents = String::new();
+        try!(File::open(path)
+             .and_then(|mut file| file.read_to_string(&mut contents))
+             .map_err(|e| format!("Can't read {}: {}", path.display(), e)));
+        self.apply(&contents, ruleset).map_err(|e| format!("{}:{}", path.display(), e))
+    }
+
+    /// Like load(), but with the contents of the file. The errors
+    /// start with the line number.
+    pub fn load_str(&mut self, contents: &str) -> Result<(), String> {
+        self.apply(contents, None)
+    }
+
+    fn apply(&mut self, contents: &str, ruleset: Option<Ruleset>) -> Result<(), String> {
+        let mut section = String::new();
+        let mut entries = vec!();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("#") {
+                continue;
+            }
+            if line.starts_with("[") {
+                match line.find(']') {
+                    Some(end) if end > 1 && Self::is_comment(&line[end+1..]) => {
+                        section = String::from(line[1..end].trim());
+                    },
+                    _ => return Err(format!("{}: malformed section header", i + 1))
+                }
+                continue;
+            }
+            let (key, value) = match line.find('=') {
+                Some(j) => (line[..j].trim(), try!(Self::value(line[j+1..].trim()).map_err(|e| format!("{}: {}", i + 1, e)))),
+                None => return Err(format!("{}: expected 'key = value'", i + 1))
+            };
+            let key = if section.is_empty() { String::from(key) } else { format!("{}.{}", section, key) };
+            entries.push((i + 1, key, value));
+        }
+        // The ruleset changes the defaults of other fields, so it has
+        // to come first and its defaults are applied only once.
+        let mut ruleset_given = ruleset.is_some();
+        match ruleset {
+            Some(ruleset) => self.ruleset = ruleset,
+            None => for &(line, ref key, ref value) in entries.iter().filter(|&&(_, ref key, _)| key == "ruleset") {
+                try!(self.set(key, value).map_err(|e| format!("{}: {}", line, e)));
+                ruleset_given = true;
+            }
+        }
+        if ruleset_given {
+            self.set_ruleset_dependent_defaults();
+        }
+        for &(line, ref key, ref value) in entries.iter().filter(|&&(_, ref key, _)| key != "ruleset") {
+            try!(self.set(key, value).map_err(|e| format!("{}: {}", line, e)));
+        }
+        Ok(())
+    }
+
+    /// The configuration in the format that load() reads.
+    pub fn dump(&self) -> String {
+        let mut s = String::new();
+        let mut section = "";
+        for &key in Self::keys().iter() {
+            let (key_section, name) = match key.rfind('.') {
+                Some(i) => (&key[..i], &key[i+1..]),
+                None => ("", key)
+            };
+            if key_section != section {
+                section = key_section;
+                s.push_str(&format!("\n[{}]\n", section));
+            }
+            s.push_str(&format!("{} = {}\n", name, self.get_toml(key)));
+        }
+        s
+    }
+
+    fn value(s: &str) -> Result<String, String> {
+        for quote in ['\'', '"'].iter() {
+            if s.starts_with(*quote) {
+                return match s[1..].find(*quote) {
+                    Some(end) if Self::is_comment(&s[end+2..]) => Ok(String::from(&s[1..end+1])),
+                    _ => Err(String::from("unterminated string"))
+                };
+            }
+        }
+        let value = match s.find('#') {
+            Some(i) => s[..i].trim(),
+            None => s
+        };
+        if value.is_empty() {
+            Err(String::from("missing value"))
+        } else {
+            Ok(String::from(value))
+        }
+    }
+
+    fn is_comment(s: &str) -> bool {
+        let s = s.trim();
+        s.is_empty() || s.starts_with("#")
+    }
+
+}
@@ -19,6 +19,7 @@
  *                                                                      *
  ************************************************************************/
 
+pub use playout::Policy;
 use ruleset::CGOS;
 use ruleset::KgsChinese;
 use ruleset::Ruleset;
@@ -27,13 +28,16 @@ use version;
 use core::fmt::Display;
 use getopts::Matches;
 use getopts::Options;
+use std::path::Path;
 
+mod file;
 mod test;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct UctConfig {
     pub end_of_game_cutoff: f32,
     pub expand_after: usize,
+    pub max_playouts: usize,
     pub priors: UctPriorsConfig,
     pub reuse_subtree: bool,
     pub tuned: bool,
@@ -44,12 +48,16 @@ pub struct UctPriorsConfig {
     pub capture_many: usize,
     pub capture_one: usize,
     pub empty: usize,
+    pub large_patterns: usize,
     pub neutral_plays: usize,
     pub neutral_wins: usize,
     pub patterns: usize,
     pub self_atari: usize,
+    pub semeai: usize,
     pub use_empty: bool,
+    pub use_large_patterns: bool,
     pub use_patterns: bool,
+    pub use_semeai: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -62,22 +70,36 @@ pub struct PlayoutConfig {
     pub atari_check: bool,
     pub ladder_check: bool,
     pub last_moves_for_heuristics: usize,
+    pub nakade: bool,
     pub no_self_atari_cutoff: usize,
     pub pattern_probability: f32,
     pub play_in_middle_of_eye: bool,
+    pub policy: Policy,
+    pub seki_check: bool,
+    pub semeai_check: bool,
     pub use_patterns: bool,
+    pub use_pseudo_liberty_board: bool,
 }
 
+/// All settings of the engine. Start from `Config::default()` and
+/// change the fields, or read a configuration file with `load`, then
+/// call `check` before handing the configuration to the engine.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Config {
+    pub book_file: Option<String>,
+    pub concurrent_sessions: bool,
     pub debug: bool,
+    pub large_patterns_file: Option<String>,
     pub log: bool,
+    pub patterns_file: Option<String>,
     pub play_out_aftermath: bool,
     pub playout: PlayoutConfig,
     pub ruleset: Ruleset,
+    pub solver_node_limit: usize,
     pub threads: usize,
     pub timer: TimerConfig,
     pub uct: UctConfig,
+    pub use_book: bool,
 }
 
 macro_rules! set_from_opt {
@@ -116,21 +138,32 @@ macro_rules! set_from_flag {
 
 impl Config {
 
+    /// The settings the GTP binary uses without any options.
     pub fn default() -> Config {
         Config {
+            book_file: None,
+            concurrent_sessions: false,
             debug: true,
+            large_patterns_file: None,
             log: false,
+            patterns_file: None,
             play_out_aftermath: false,
             playout: PlayoutConfig {
                 atari_check: true,
                 ladder_check: true,
                 last_moves_for_heuristics: 2,
+                nakade: false,
                 no_self_atari_cutoff: 7,
                 pattern_probability: 0.9,
                 play_in_middle_of_eye: true,
+                policy: Policy::Cascade,
+                seki_check: false,
+                semeai_check: false,
                 use_patterns: true,
+                use_pseudo_liberty_board: false,
             },
             ruleset: KgsChinese,
+            solver_node_limit: 100000,
             threads: 1,
             timer: TimerConfig {
                 c: 0.5
@@ -138,20 +171,26 @@ impl Config {
             uct: UctConfig {
                 end_of_game_cutoff: 0.08,
                 expand_after: 1,
+                max_playouts: 0,
                 priors: UctPriorsConfig {
                     capture_many: 30,
                     capture_one: 15,
                     empty: 20,
+                    large_patterns: 20,
                     neutral_plays: 10,
                     neutral_wins: 5,
                     patterns: 10,
                     self_atari: 10,
+                    semeai: 20,
                     use_empty: true,
+                    use_large_patterns: false,
                     use_patterns: false,
+                    use_semeai: false,
                 },
                 reuse_subtree: true,
                 tuned: true,
             },
+            use_book: true,
         }
     }
 
@@ -159,17 +198,45 @@ impl Config {
         opts.optflag("h", "help", "Print this help menu");
         opts.optflag("v", "version", "Print the version number");
 
+        opts.optopt("", "config", "Load the configuration from a file (command line options take precedence)", "FILE");
+        opts.optflag("", "dump-config", "Print the effective configuration in the format of the configuration file");
         self.flag(opts, "l", "log", "Log to stderr", self.log);
+        opts.optopt("", "book", "Load an opening book from a file", "FILE");
+        opts.optopt("", "patterns", "Load the 3x3 patterns from a file instead of using the built-in ones", "FILE");
+        opts.optopt("", "large-patterns", "Load the large patterns for the prior from a file", "FILE");
 
+        self.opt(opts, "capture-many-prior", "Prior value for capturing more than one stone", self.uct.priors.capture_many);
+        self.opt(opts, "capture-one-prior", "Prior value for capturing a single stone", self.uct.priors.capture_one);
+        self.opt(opts, "concurrent-sessions", "Serve several GTP connections at the same time when listening on a socket", self.concurrent_sessions);
         self.opt(opts, "empty-area-prior", "Prior value for empty areas", self.uct.priors.empty);
+        self.opt(opts, "end-of-game-cutoff", "Resign or pass when the win rate is below this value", self.uct.end_of_game_cutoff);
+        self.opt(opts, "large-patterns-prior", "Prior value for large patterns", self.uct.priors.large_patterns);
+        self.opt(opts, "max-playouts", "Stop the search after this many playouts (0 means no limit)", self.uct.max_playouts);
+        self.opt(opts, "neutral-plays-prior", "Prior number of plays added to every move", self.uct.priors.neutral_plays);
+        self.opt(opts, "neutral-wins-prior", "Prior number of wins added to every move", self.uct.priors.neutral_wins);
+        self.opt(opts, "no-self-atari-cutoff", "Allow self ataris in the playouts for chains shorter than this", self.playout.no_self_atari_cutoff);
+        self.opt(opts, "pattern-probability", "Probability of playing a 3x3 pattern move in the playouts", self.playout.pattern_probability);
+        self.opt(opts, "patterns-prior", "Prior value for 3x3 patterns", self.uct.priors.patterns);
         self.opt(opts, "play-out-aftermath", "Keep playing after the result of the game is decided", self.play_out_aftermath);
         self.opt(opts, "play-in-middle-of-eye", "Try playing in the middle of a large eye", self.playout.play_in_middle_of_eye);
+        self.opt(opts, "playout-policy", "Select how the moves in the playouts are chosen", self.playout.policy);
         self.opt(opts, "reuse-subtree", "Reuse the subtree from the previous search", self.uct.reuse_subtree);
+        self.opt(opts, "self-atari-prior", "Prior value for self ataris (counted as losses)", self.uct.priors.self_atari);
+        self.opt(opts, "semeai-prior", "Prior value for winning capturing races", self.uct.priors.semeai);
+        self.opt(opts, "solver-node-limit", "Maximum number of positions the life and death solver reads per GTP command", self.solver_node_limit);
+        self.opt(opts, "timer-c", "Divide the main time by this times the number of empty points to get the time per move", self.timer.c);
         self.opt(opts, "use-atari-check-in-playouts", "Check for atari in the playouts", self.playout.ladder_check);
+        self.opt(opts, "use-book", "Play moves from the opening book (if one is given)", self.use_book);
         self.opt(opts, "use-empty-area-prior", "Use a prior for empty areas on the board", self.uct.priors.use_empty);
         self.opt(opts, "use-ladder-check-in-playouts", "Check for ladders in the playouts", self.playout.ladder_check);
+        self.opt(opts, "use-large-patterns-prior", "Use a prior to prioritize large patterns", self.uct.priors.use_large_patterns);
+        self.opt(opts, "use-nakade-in-playouts", "Play the vital point of killable eye shapes in the playouts", self.playout.nakade);
         self.opt(opts, "use-patterns-prior", "Use a prior to prioritize 3x3 patterns", self.uct.priors.use_patterns);
         self.opt(opts, "use-patterns-in-playouts", "Use 3x3 patterns in the playouts", self.playout.use_patterns);
+        self.opt(opts, "use-pseudo-liberty-board-in-playouts", "Run light playouts on the faster pseudo liberty board", self.playout.use_pseudo_liberty_board);
+        self.opt(opts, "use-seki-check-in-playouts", "Don't fill the shared liberties of a seki in the playouts", self.playout.seki_check);
+        self.opt(opts, "use-semeai-check-in-playouts", "Fill the liberties of capturing races in the playouts", self.playout.semeai_check);
+        self.opt(opts, "use-semeai-prior", "Use a prior for moves that win capturing races", self.uct.priors.use_semeai);
         self.opt(opts, "use-ucb1-tuned", "Use the UCB1tuned selection strategy", self.uct.tuned);
         self.optopt(opts, "r", "ruleset", "Select the ruleset", self.ruleset);
         self.optopt(opts, "t", "threads", "Number of threads to use", self.threads);
@@ -177,7 +244,7 @@ impl Config {
 
     pub fn set_from_opts(&mut self, matches: &Matches, opts: &Options, args: &Vec<String>) -> Result<Option<String>, String>{
         if matches.opt_present("h") {
-            let brief = format!("Usage: {} [options]", args[0]);
+            let brief = format!("Usage: {} [options] [regress FILE... | match | tune [PARAMETER...]]", args[0]);
             let s = format!("{}", opts.usage(brief.as_ref()));
             return Ok(Some(s));
         }
@@ -185,30 +252,98 @@ impl Config {
             let s = format!("Iomrascálaí {}", version::version());
             return Ok(Some(s));
         }
-        set_from_opt!(matches, "r", "ruleset", self.ruleset);
-        self.set_ruleset_dependent_defaults();
+        // The ruleset defaults are applied once, before the values from
+        // the file and the other options, so that they don't overwrite
+        // them. The ruleset option takes the place of the file's one.
+        let mut ruleset = None;
+        if matches.opt_present("ruleset") {
+            let mut r = self.ruleset;
+            set_from_opt!(matches, "r", "ruleset", r);
+            ruleset = Some(r);
+        }
+        match matches.opt_str("config") {
+            Some(file) => try!(self.load_with_ruleset(Path::new(&file), ruleset)),
+            None => if let Some(r) = ruleset {
+                self.ruleset = r;
+                self.set_ruleset_dependent_defaults();
+            }
+        }
 
+        set_from_opt!(matches, "capture-many-prior", self.uct.priors.capture_many);
+        set_from_opt!(matches, "capture-one-prior", self.uct.priors.capture_one);
+        set_from_opt!(matches, "concurrent-sessions", self.concurrent_sessions);
         set_from_opt!(matches, "empty-area-prior", self.uct.priors.empty);
+        set_from_opt!(matches, "end-of-game-cutoff", self.uct.end_of_game_cutoff);
+        set_from_opt!(matches, "large-patterns-prior", self.uct.priors.large_patterns);
+        set_from_opt!(matches, "max-playouts", self.uct.max_playouts);
+        set_from_opt!(matches, "neutral-plays-prior", self.uct.priors.neutral_plays);
+        set_from_opt!(matches, "neutral-wins-prior", self.uct.priors.neutral_wins);
+        set_from_opt!(matches, "no-self-atari-cutoff", self.playout.no_self_atari_cutoff);
+        set_from_opt!(matches, "pattern-probability", self.playout.pattern_probability);
+        set_from_opt!(matches, "patterns-prior", self.uct.priors.patterns);
         set_from_opt!(matches, "play-out-aftermath", self.play_out_aftermath);
         set_from_opt!(matches, "play-in-middle-of-eye", self.playout.play_in_middle_of_eye);
+        set_from_opt!(matches, "playout-policy", self.playout.policy);
         set_from_opt!(matches, "reuse-subtree", self.uct.reuse_subtree);
+        set_from_opt!(matches, "self-atari-prior", self.uct.priors.self_atari);
+        set_from_opt!(matches, "semeai-prior", self.uct.priors.semeai);
+        set_from_opt!(matches, "solver-node-limit", self.solver_node_limit);
+        set_from_opt!(matches, "timer-c", self.timer.c);
         set_from_opt!(matches, "t", "threads", self.threads);
         set_from_opt!(matches, "use-atari-check-in-playouts", self.playout.atari_check);
+        set_from_opt!(matches, "use-book", self.use_book);
         set_from_opt!(matches, "use-empty-area-prior", self.uct.priors.use_empty);
         set_from_opt!(matches, "use-ladder-check-in-playouts", self.playout.ladder_check);
-        set_from_opt!(matches, "use-ucb1-tuned", self.uct.tuned);
+        set_from_opt!(matches, "use-large-patterns-prior", self.uct.priors.use_large_patterns);
+        set_from_opt!(matches, "use-nakade-in-playouts", self.playout.nakade);
         set_from_opt!(matches, "use-patterns-prior", self.uct.priors.use_patterns);
         set_from_opt!(matches, "use-patterns-in-playouts", self.playout.use_patterns);
+        set_from_opt!(matches, "use-pseudo-liberty-board-in-playouts", self.playout.use_pseudo_liberty_board);
+        set_from_opt!(matches, "use-seki-check-in-playouts", self.playout.seki_check);
+        set_from_opt!(matches, "use-semeai-check-in-playouts", self.playout.semeai_check);
+        set_from_opt!(matches, "use-semeai-prior", self.uct.priors.use_semeai);
+        set_from_opt!(matches, "use-ucb1-tuned", self.uct.tuned);
 
         set_from_flag!(matches, "l", "log", self.log);
 
-        self.check()
+        if let Some(file) = matches.opt_str("book") {
+            self.book_file = Some(file);
+        }
+        if let Some(file) = matches.opt_str("patterns") {
+            self.patterns_file = Some(file);
+        }
+        if let Some(file) = matches.opt_str("large-patterns") {
+            self.large_patterns_file = Some(file);
+        }
+
+        try!(self.check());
+        if matches.opt_present("dump-config") {
+            return Ok(Some(self.dump()));
+        }
+        Ok(None)
     }
 
-    fn check(&self) -> Result<Option<String>, String> {
+    /// Fails for inconsistent settings. A message is returned for
+    /// settings that work but probably aren't what was intended.
+    pub fn check(&self) -> Result<Option<String>, String> {
         if self.playout.ladder_check && !self.playout.atari_check {
             let s = String::from("'--use-ladder-check-in-playouts true' requires '--use-atari-check-in-playouts true'");
             Err(s)
+        } else if self.uct.priors.use_large_patterns && self.large_patterns_file.is_none() {
+            let s = String::from("'--use-large-patterns-prior true' requires '--large-patterns FILE'");
+            Err(s)
+        } else if self.playout.use_pseudo_liberty_board && self.playout.policy != Policy::Light {
+            let s = String::from("'--use-pseudo-liberty-board-in-playouts true' requires '--playout-policy light'");
+            Err(s)
+        } else if self.threads == 0 {
+            let s = String::from("'--threads' has to be at least 1");
+            Err(s)
+        } else if self.playout.pattern_probability < 0.0 || self.playout.pattern_probability > 1.0 {
+            let s = String::from("'--pattern-probability' has to be between 0 and 1");
+            Err(s)
+        } else if self.uct.end_of_game_cutoff < 0.0 || self.uct.end_of_game_cutoff > 1.0 {
+            let s = String::from("'--end-of-game-cutoff' has to be between 0 and 1");
+            Err(s)
         } else {
             Ok(None)
         }
@@ -250,6 +385,14 @@ impl Hint for bool {
 }
 
 
+impl Hint for f32 {
+
+    fn hint_str(&self) -> &'static str {
+        "NUM"
+    }
+
+}
+
 impl Hint for usize {
 
     fn hint_str(&self) -> &'static str {
@@ -22,6 +22,9 @@
 #![cfg(test)]
 
 use getopts::Options;
+use playout::Policy;
+use ruleset::CGOS;
+use ruleset::KgsChinese;
 use super::Config;
 
 #[test]
@@ -32,6 +35,34 @@ fn fail_if_ladder_and_atari_are_in_conflict() {
     assert!(config.check().is_err());
 }
 
+#[test]
+fn fail_if_pseudo_liberty_board_is_used_without_the_light_policy() {
+    let mut config = Config::default();
+    config.playout.policy = Policy::Softmax;
+    config.playout.use_pseudo_liberty_board = true;
+    assert!(config.check().is_err());
+}
+
+#[test]
+fn fail_if_the_large_patterns_prior_has_no_patterns() {
+    let mut config = Config::default();
+    config.uct.priors.use_large_patterns = true;
+    assert!(config.check().is_err());
+    config.large_patterns_file = Some(String::from("fixtures/patterns/large.txt"));
+    assert!(config.check().is_ok());
+}
+
+#[test]
+fn set_the_playout_policy() {
+    let mut config = Config::default();
+    let args = vec!(String::from("--playout-policy"), String::from("random"));
+    let mut opts = Options::new();
+    config.setup(&mut opts);
+    let matches = opts.parse(args.clone()).unwrap();
+    config.set_from_opts(&matches, &opts, &args).unwrap();
+    assert_eq!(Policy::Random, config.playout.policy);
+}
+
 #[test]
 fn playout_aftermath_under_cgos_rules_by_default() {
     let mut config = Config::default();
@@ -42,3 +73,79 @@ fn playout_aftermath_under_cgos_rules_by_default() {
     config.set_from_opts(&matches, &opts, &args).unwrap();
     assert!(config.play_out_aftermath);
 }
+
+fn config_from_args(args: Vec<&str>) -> Result<(Config, Option<String>), String> {
+    let mut config = Config::default();
+    let args: Vec<String> = args.iter().map(|&s| String::from(s)).collect();
+    let mut opts = Options::new();
+    config.setup(&mut opts);
+    let matches = opts.parse(args.clone()).unwrap();
+    let output = try!(config.set_from_opts(&matches, &opts, &args));
+    Ok((config, output))
+}
+
+#[test]
+fn load_sets_the_fields_in_sections() {
+    let mut config = Config::default();
+    config.load_str("threads = 4\n\n[uct.priors]\ncapture_one = 3\n[playout]\npolicy = \"light\"").unwrap();
+    assert_eq!(4, config.threads);
+    assert_eq!(3, config.uct.priors.capture_one);
+    assert_eq!(Policy::Light, config.playout.policy);
+}
+
+#[test]
+fn load_reports_the_line_of_an_error() {
+    let mut config = Config::default();
+    assert_eq!(Err(String::from("2: Unknown key 'uct.prior.semeai'")), config.load_str("[uct]\nprior.semeai = 1"));
+    assert_eq!(Err(String::from("1: Invalid value 'many' for threads")), config.load_str("threads = many"));
+    assert_eq!(Err(String::from("1: expected 'key = value'")), config.load_str("threads"));
+    assert_eq!(Err(String::from("1: unterminated string")), config.load_str("book_file = 'book.txt"));
+}
+
+#[test]
+fn the_dump_can_be_loaded_again() {
+    let mut config = Config::default();
+    config.book_file = Some(String::from("book.txt"));
+    config.timer.c = 0.75;
+    config.ruleset = CGOS;
+    let mut loaded = Config::default();
+    loaded.load_str(&config.dump()).unwrap();
+    assert_eq!(config, loaded);
+}
+
+#[test]
+fn the_options_override_the_config_file() {
+    let (config, _) = config_from_args(vec!("--config", "fixtures/config/example.toml", "-t", "8")).unwrap();
+    assert_eq!(8, config.threads);
+    assert_eq!(3, config.uct.expand_after);
+    assert_eq!(40, config.uct.priors.capture_many);
+    assert_eq!(CGOS, config.ruleset);
+    assert!(!config.play_out_aftermath);
+}
+
+#[test]
+fn the_ruleset_option_keeps_the_values_from_the_config_file() {
+    let (config, _) = config_from_args(vec!("--config", "fixtures/config/example.toml", "-r", "cgos")).unwrap();
+    assert_eq!(CGOS, config.ruleset);
+    assert!(!config.play_out_aftermath);
+    assert_eq!(40, config.uct.priors.capture_many);
+    let (config, _) = config_from_args(vec!("--config", "fixtures/config/example.toml", "-r", "chinese")).unwrap();
+    assert_eq!(KgsChinese, config.ruleset);
+    assert!(!config.play_out_aftermath);
+    assert_eq!(3, config.uct.expand_after);
+}
+
+#[test]
+fn the_config_file_is_validated() {
+    assert!(config_from_args(vec!("--config", "fixtures/config/example.toml", "-t", "0")).is_err());
+    assert!(config_from_args(vec!("--config", "fixtures/config/missing.toml")).is_err());
+}
+
+#[test]
+fn dump_config_prints_the_effective_config() {
+    let (_, output) = config_from_args(vec!("--dump-config", "-t", "3")).unwrap();
+    let output = output.unwrap();
+    assert!(output.starts_with("book_file = ''\n"));
+    assert!(output.contains("threads = 3\n"));
+    assert!(output.contains("\n[uct.priors]\ncapture_many = 30\n"));
+}
@@ -21,6 +21,7 @@
 
 use board::Color;
 use board::Move;
+use book::Book;
 use config::Config;
 use engine::Engine;
 use game::Game;
@@ -36,26 +37,80 @@ use thread_scoped::scoped;
 
 mod test;
 
+/// Runs an engine (see `engine::factory`) for one move at a time and
+/// plays from the opening book first, if there is one. This is what
+/// the GTP interpreter and the C API use, `engine::search` is simpler
+/// for a single search.
 pub struct EngineController<'a> {
+    book: Option<Arc<Book>>,
     config: Arc<Config>,
     engine: Box<Engine + 'a>,
 }
 
 impl<'a> EngineController<'a> {
 
-    pub fn new<'b>(config: Arc<Config>, engine: Box<Engine + 'b>) -> EngineController<'b> {
+    /// A controller that uses `engine` for all moves the book
+    /// doesn't know.
+    pub fn new<'b>(config: Arc<Config>, engine: Box<Engine + 'b>, book: Option<Arc<Book>>) -> EngineController<'b> {
         EngineController {
+            book: book,
             config: config,
             engine: engine,
         }
     }
 
+    /// Throws away what the engine learned, for a new game.
     pub fn reset(&mut self) {
         self.engine.reset();
     }
 
+    /// The configuration stays the same if the engine rejects it.
+    pub fn set_config(&mut self, config: Arc<Config>) -> Result<(), String> {
+        try!(self.engine.set_config(config.clone()));
+        self.config = config;
+        Ok(())
+    }
+
+    /// Sends the move for `color` and returns the number of playouts
+    /// it took. The thinking time comes from the timer.
     pub fn run_and_return_move(&mut self, color: Color, game: &Game, timer: &Timer, send_move: Sender<Move>) -> usize {
+        if self.play_book_move(color, game, &send_move) {
+            return 0;
+        }
         let budget = self.budget(timer, game);
+        self.run(color, game, budget, send_move)
+    }
+
+    /// Like run_and_return_move, but thinks for a fixed number of
+    /// milliseconds instead of asking the timer.
+    pub fn run_with_time_limit(&mut self, color: Color, game: &Game, time_limit: u32, send_move: Sender<Move>) -> usize {
+        if self.play_book_move(color, game, &send_move) {
+            return 0;
+        }
+        self.run(color, game, time_limit, send_move)
+    }
+
+    /// For engines that stop on their own, e.g. because of a playout
+    /// limit.
+    pub fn run_without_time_limit(&mut self, color: Color, game: &Game, send_move: Sender<Move>) -> usize {
+        if self.play_book_move(color, game, &send_move) {
+            return 0;
+        }
+        let (send_move_to_controller, receive_move_from_engine) = channel();
+        // The engine stops as soon as this is dropped, so it has to
+        // outlive the guard.
+        let (_send_signal_to_engine, receive_signal_from_controller) = channel::<()>();
+        unsafe {
+            let _guard = scoped(|| {
+                self.engine.gen_move(color, game, send_move_to_controller, receive_signal_from_controller);
+            });
+            let (m, playouts) = receive_move_from_engine.recv().unwrap();
+            send_move.send(m).unwrap();
+            playouts
+        }
+    }
+
+    fn run(&mut self, color: Color, game: &Game, budget: u32, send_move: Sender<Move>) -> usize {
         let (send_move_to_controller, receive_move_from_engine) = channel();
         let (send_signal_to_engine, receive_signal_from_controller) = channel::<()>();
         // Saving the guard into a variable is necessary. Otherwise
@@ -86,6 +141,26 @@ impl<'a> EngineController<'a> {
         }
     }
 
+    fn play_book_move(&self, color: Color, game: &Game, send_move: &Sender<Move>) -> bool {
+        match self.book_move(color, game) {
+            Some(m) => {
+                if self.config.log {
+                    log!("Playing {} from the opening book", m.to_gtp());
+                }
+                send_move.send(m).unwrap();
+                true
+            },
+            None => false
+        }
+    }
+
+    fn book_move(&self, color: Color, game: &Game) -> Option<Move> {
+        if !self.config.use_book || color != game.next_player() {
+            return None;
+        }
+        self.book.as_ref().and_then(|book| book.lookup(game))
+    }
+
     fn budget(&self, timer: &Timer, game: &Game) -> u32 {
         let budget = timer.budget(game);
         if self.config.log {
@@ -27,6 +27,8 @@ use std::sync::Arc;
 use board::Color;
 use board::Move;
 use board::Pass;
+use board::Play;
+use book::Book;
 use config::Config;
 use engine::Engine;
 use game::Game;
@@ -68,7 +70,7 @@ fn the_engine_can_use_less_time_than_allocated() {
     let timer = Timer::new(config());
     let budget = timer.budget(&game);
     let engine = Box::new(EarlyReturnEngine::new());
-    let mut controller = EngineController::new(config(), engine);
+    let mut controller = EngineController::new(config(), engine, None);
     let start_time = PreciseTime::now();
     let (sender, receiver) = channel::<Move>();
     controller.run_and_return_move(color, &game, &timer, sender);
@@ -107,7 +109,7 @@ fn the_controller_asks_the_engine_for_a_move_when_the_time_is_up() {
     timer.setup(1, 0, 0);
     let budget = timer.budget(&game);
     let engine = Box::new(WaitingEngine::new());
-    let mut controller = EngineController::new(config(), engine);
+    let mut controller = EngineController::new(config(), engine, None);
     let start_time = PreciseTime::now();
     let (sender, receiver) = channel::<Move>();
     controller.run_and_return_move(color, &game, &timer, sender);
@@ -116,3 +118,60 @@ fn the_controller_asks_the_engine_for_a_move_when_the_time_is_up() {
     assert!(elapsed_time >= budget as i64);
     assert_eq!(Pass(color), m);
 }
+
+#[test]
+fn the_controller_can_think_for_a_fixed_time() {
+    let game = Game::new(19, 6.5, Minimal);
+    let color = game.board().next_player();
+    let engine = Box::new(WaitingEngine::new());
+    let mut controller = EngineController::new(config(), engine, None);
+    let start_time = PreciseTime::now();
+    let (sender, receiver) = channel::<Move>();
+    controller.run_with_time_limit(color, &game, 50, sender);
+    let m = receiver.recv().unwrap();
+    let elapsed_time = start_time.to(PreciseTime::now()).num_milliseconds();
+    assert!(elapsed_time >= 50);
+    assert_eq!(Pass(color), m);
+}
+
+#[test]
+fn the_controller_can_wait_for_the_engine_to_finish() {
+    let game = Game::new(19, 6.5, Minimal);
+    let color = game.board().next_player();
+    let engine = Box::new(EarlyReturnEngine::new());
+    let mut controller = EngineController::new(config(), engine, None);
+    let (sender, receiver) = channel::<Move>();
+    controller.run_without_time_limit(color, &game, sender);
+    assert_eq!(Pass(color), receiver.recv().unwrap());
+}
+
+fn book() -> Option<Arc<Book>> {
+    let mut book = Book::new();
+    book.add(&Game::new(19, 6.5, Minimal).board(), &Play(Color::Black, 4, 4));
+    Some(Arc::new(book))
+}
+
+#[test]
+fn the_book_move_is_played_without_searching() {
+    let game = Game::new(19, 6.5, Minimal);
+    let timer = Timer::new(config());
+    let engine = Box::new(EarlyReturnEngine::new());
+    let mut controller = EngineController::new(config(), engine, book());
+    let (sender, receiver) = channel::<Move>();
+    controller.run_and_return_move(Color::Black, &game, &timer, sender);
+    assert_eq!(Play(Color::Black, 4, 4), receiver.recv().unwrap());
+}
+
+#[test]
+fn the_book_can_be_turned_off() {
+    let game = Game::new(19, 6.5, Minimal);
+    let mut config = Config::default();
+    config.use_book = false;
+    let config = Arc::new(config);
+    let timer = Timer::new(config.clone());
+    let engine = Box::new(EarlyReturnEngine::new());
+    let mut controller = EngineController::new(config, engine, book());
+    let (sender, receiver) = channel::<Move>();
+    controller.run_and_return_move(Color::Black, &game, &timer, sender);
+    assert_eq!(Pass(Color::Black), receiver.recv().unwrap());
+}
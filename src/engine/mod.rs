@@ -20,6 +20,10 @@
  ************************************************************************/
 
 pub use self::controller::EngineController;
+pub use self::search::Analysis;
+pub use self::search::Budget;
+pub use self::search::MoveStats;
+pub use self::search::search;
 pub use self::uct::UctEngine;
 use board::Color;
 use board::Move;
@@ -32,16 +36,24 @@ use std::sync::mpsc::Receiver;
 use std::sync::mpsc::Sender;
 
 mod controller;
+mod search;
 mod test;
 mod uct;
 
-pub fn factory(config: Arc<Config>, matcher: Arc<Matcher>) -> Box<Engine> {
-    Box::new(UctEngine::new(config, matcher))
+/// The UCT engine, to be run by an `EngineController`.
+pub fn factory(config: Arc<Config>, matcher: Arc<Matcher>) -> Result<Box<Engine>, String> {
+    let engine = try!(UctEngine::new(config, matcher));
+    Ok(Box::new(engine))
 }
 
+/// A search that the `EngineController` runs for every move.
 pub trait Engine: Send + Sync {
 
     fn gen_move(&mut self, Color, &Game, sender: Sender<(Move,usize)>, receiver: Receiver<()>);
     fn reset(&mut self) {}
+    // Called between moves when the configuration changes.
+    fn set_config(&mut self, _config: Arc<Config>) -> Result<(), String> { Ok(()) }
+    // The statistics of the last search, if the engine keeps any.
+    fn analysis(&self) -> Option<Analysis> { None }
 
 }
@@ -0,0 +1,142 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use board::Move;
+use config::Config;
+use engine::Engine;
+use engine::UctEngine;
+use game::Game;
+use patterns::Matcher;
+
+use std::sync::Arc;
+use std::sync::mpsc::channel;
+use std::thread;
+use std::thread::sleep_ms;
+use thread_scoped::scoped;
+
+mod test;
+
+/// How long a call to `search` is allowed to think.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    /// Stop after the given number of milliseconds.
+    Time(u32),
+    /// Stop after the given number of playouts.
+    Playouts(usize),
+}
+
+impl Budget {
+
+    /// Fails for a budget of zero playouts, as the engine takes a
+    /// playout limit of zero to mean no limit at all.
+    pub fn check(&self) -> Result<(), String> {
+        match *self {
+            Budget::Playouts(0) => Err(String::from("The playout budget has to be larger than 0")),
+            _                   => Ok(())
+        }
+    }
+
+}
+
+/// The statistics the search collected for one candidate move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveStats {
+    pub m: Move,
+    pub plays: usize,
+    pub win_ratio: f32,
+}
+
+/// The result of a search. All win ratios are from the point of view
+/// of the player the search was run for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub best_move: Move,
+    /// The playouts of this search, a reused subtree of an earlier
+    /// search isn't counted.
+    pub playouts: usize,
+    pub win_ratio: f32,
+    /// The candidate moves, most simulated first.
+    pub moves: Vec<MoveStats>,
+}
+
+impl Analysis {
+
+    pub fn new(best_move: Move, playouts: usize, win_ratio: f32, mut moves: Vec<MoveStats>) -> Analysis {
+        moves.sort_by(|a, b| b.plays.cmp(&a.plays));
+        Analysis {
+            best_move: best_move,
+            playouts: playouts,
+            win_ratio: win_ratio,
+            moves: moves,
+        }
+    }
+
+}
+
+/// Runs the UCT engine for the player to move in `game` until the
+/// budget is used up and returns what it found. Unlike the GTP
+/// driver this neither consults the opening book nor the time
+/// settings of the game. Fails if the configuration is inconsistent
+/// or the budget is zero playouts.
+pub fn search(config: Arc<Config>, matcher: Arc<Matcher>, game: &Game, budget: Budget) -> Result<Analysis, String> {
+    try!(budget.check());
+    let config = match budget {
+        Budget::Playouts(playouts) => {
+            let mut config = (*config).clone();
+            config.uct.max_playouts = playouts;
+            Arc::new(config)
+        },
+        Budget::Time(_) => config
+    };
+    let color = game.next_player();
+    let mut engine = try!(UctEngine::new(config, matcher));
+    let (send_move_to_search, receive_move_from_engine) = channel();
+    let (send_signal_to_engine, receive_signal_from_search) = channel::<()>();
+    // Saving the guard into a variable is necessary. Otherwise
+    // the code blocks right here.
+    unsafe {
+        let _guard = scoped(|| {
+            engine.gen_move(color, game, send_move_to_search, receive_signal_from_search);
+        });
+        match budget {
+            Budget::Time(ms) => {
+                let (send_time_up_to_search, receive_time_up) = channel();
+                thread::spawn(move || {
+                    sleep_ms(ms);
+                    // The search may already be over by now.
+                    let _ = send_time_up_to_search.send(());
+                });
+                select!(
+                    _ = receive_move_from_engine.recv() => {},
+                    _ = receive_time_up.recv() => {
+                        let _ = send_signal_to_engine.send(());
+                        receive_move_from_engine.recv().unwrap();
+                    }
+                )
+            },
+            Budget::Playouts(_) => {
+                receive_move_from_engine.recv().unwrap();
+            }
+        }
+    }
+    Ok(engine.analysis().unwrap())
+}
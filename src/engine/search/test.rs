@@ -0,0 +1,73 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use config::Config;
+use game::Game;
+use patterns::Matcher;
+use ruleset::Minimal;
+use super::Budget;
+use super::search;
+
+use std::sync::Arc;
+
+fn config() -> Arc<Config> {
+    Arc::new(Config::default())
+}
+
+fn matcher() -> Arc<Matcher> {
+    Arc::new(Matcher::new())
+}
+
+#[test]
+fn search_stops_after_the_playout_budget_is_used_up() {
+    let game = Game::new(9, 6.5, Minimal);
+    let analysis = search(config(), matcher(), &game, Budget::Playouts(200)).unwrap();
+    // Playouts that are still running in other threads when the
+    // budget is reached are dropped.
+    assert!(analysis.playouts >= 200);
+    assert!(analysis.playouts < 200 + config().threads);
+}
+
+#[test]
+fn search_rejects_a_budget_of_zero_playouts() {
+    let game = Game::new(9, 6.5, Minimal);
+    assert!(search(config(), matcher(), &game, Budget::Playouts(0)).is_err());
+}
+
+#[test]
+fn search_stops_after_the_time_budget_is_used_up() {
+    let game = Game::new(9, 6.5, Minimal);
+    let analysis = search(config(), matcher(), &game, Budget::Time(100)).unwrap();
+    assert!(analysis.playouts > 0);
+}
+
+#[test]
+fn search_returns_the_most_simulated_move_first() {
+    let game = Game::new(9, 6.5, Minimal);
+    let analysis = search(config(), matcher(), &game, Budget::Playouts(500)).unwrap();
+    assert_eq!(analysis.best_move, analysis.moves[0].m);
+    for pair in analysis.moves.windows(2) {
+        assert!(pair[0].plays >= pair[1].plays);
+    }
+}
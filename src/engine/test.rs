@@ -20,3 +20,42 @@
  ************************************************************************/
 
 #![cfg(test)]
+
+use board::Black;
+use board::Color;
+use board::Move;
+use board::White;
+use config::Config;
+use game::Game;
+use patterns::Matcher;
+use ruleset::Minimal;
+use super::Engine;
+use super::UctEngine;
+
+use std::sync::Arc;
+use std::sync::mpsc::channel;
+
+fn gen_move(engine: &mut UctEngine, color: Color, game: &Game) -> Move {
+    let (send_move, receive_move) = channel();
+    // Dropping the halt sender would stop the search right away.
+    let (_send_halt, receive_halt) = channel();
+    engine.gen_move(color, game, send_move, receive_halt);
+    receive_move.recv().unwrap().0
+}
+
+#[test]
+fn a_reused_subtree_does_not_count_towards_the_playout_limit() {
+    let mut config = Config::default();
+    config.uct.max_playouts = 300;
+    config.uct.reuse_subtree = true;
+    let config = Arc::new(config);
+    let mut engine = UctEngine::new(config.clone(), Arc::new(Matcher::new())).unwrap();
+    let mut game = Game::new(9, 6.5, Minimal);
+    for &color in [Black, White, Black].iter() {
+        let m = gen_move(&mut engine, color, &game);
+        let analysis = engine.analysis().unwrap();
+        assert!(analysis.playouts >= 300);
+        assert!(analysis.playouts < 300 + config.threads);
+        game = game.play(m).unwrap();
+    }
+}
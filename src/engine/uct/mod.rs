@@ -27,7 +27,9 @@ use board::NoMove;
 use board::Pass;
 use board::Resign;
 use config::Config;
+use engine::Analysis;
 use engine::Engine;
+use engine::MoveStats;
 use game::Game;
 use patterns::Matcher;
 use playout::Playout;
@@ -45,29 +47,42 @@ use thread_scoped::scoped;
 mod node;
 
 pub struct UctEngine {
+    analysis: Option<Analysis>,
     config: Arc<Config>,
     matcher: Arc<Matcher>,
     playout: Arc<Playout>,
     previous_node_count: usize,
     root: Node,
+    // The plays of the root when the search started, as a reused
+    // subtree already has some.
+    start_plays: usize,
 }
 
 impl UctEngine {
 
-    pub fn new(config: Arc<Config>, matcher: Arc<Matcher>) -> UctEngine {
-        UctEngine {
+    pub fn new(config: Arc<Config>, matcher: Arc<Matcher>) -> Result<UctEngine, String> {
+        let playout = try!(Playout::new(config.clone(), matcher.clone()));
+        Ok(UctEngine {
+            analysis: None,
             config: config.clone(),
-            matcher: matcher.clone(),
-            playout: Arc::new(Playout::new(config.clone(), matcher.clone())),
+            matcher: matcher,
+            playout: Arc::new(playout),
             previous_node_count: 0,
             root: Node::new(NoMove, config),
-        }
+            start_plays: 0,
+        })
     }
 
     fn set_new_root(&mut self, game: &Game, color: Color) {
         self.root = self.root.find_new_root(game, color);
     }
 
+    // Only counts the playouts of the current search, so that every
+    // move gets the same number of playouts.
+    fn playout_limit_reached(&self) -> bool {
+        self.config.uct.max_playouts > 0 && self.root.plays() - self.start_plays >= self.config.uct.max_playouts
+    }
+
 }
 
 impl Engine for UctEngine {
@@ -84,10 +99,12 @@ impl Engine for UctEngine {
                 log!("Reusing {} nodes ({}%)", reused_node_count, percentage*100.0)
             }
         }
+        self.start_plays = self.root.plays();
         if self.root.has_no_children() {
             if self.config.log {
                 log!("No moves to simulate!");
             }
+            self.analysis = Some(Analysis::new(Pass(color), 0, self.root.win_ratio(), vec!()));
             sender.send((Pass(color), self.root.plays())).unwrap();
             return;
         }
@@ -95,14 +112,13 @@ impl Engine for UctEngine {
         let (_guards, halt_senders) = spin_up(self.config.clone(), self.playout.clone(), game, send_result_to_main);
         loop {
             select!(
-                _ = receiver.recv() => {
-                    let m = finish(&self.root, game, color, sender, self.config.clone(), halt_senders);
-                    self.set_new_root(&game.play(m).unwrap(), color);
-                    break;
-                },
+                _ = receiver.recv() => { break; },
                 res = receive_result_from_threads.recv() => {
                     let ((path, winner, nodes_added), send_to_thread) = res.unwrap();
                     self.root.record_on_path(&path, winner, nodes_added);
+                    if self.playout_limit_reached() {
+                        break;
+                    }
                     let data = self.root.find_leaf_and_expand(game, self.matcher.clone());
                     match send_to_thread.send(data) {
                         Ok(_) => {},
@@ -115,13 +131,31 @@ impl Engine for UctEngine {
                 }
                 )
         }
+        let m = finish(&self.root, game, color, sender, self.config.clone(), halt_senders);
+        self.analysis = Some(analysis(&self.root, m, self.root.plays() - self.start_plays));
+        self.set_new_root(&game.play(m).unwrap(), color);
     }
 
     fn reset(&mut self) {
+        self.analysis = None;
         self.previous_node_count = 0;
         self.root = Node::new(NoMove, self.config.clone());
     }
 
+    fn analysis(&self) -> Option<Analysis> {
+        self.analysis.clone()
+    }
+
+    // The priors of the existing tree were computed with the old
+    // configuration, so the tree is thrown away.
+    fn set_config(&mut self, config: Arc<Config>) -> Result<(), String> {
+        let playout = try!(Playout::new(config.clone(), self.matcher.clone()));
+        self.config = config;
+        self.playout = Arc::new(playout);
+        self.reset();
+        Ok(())
+    }
+
 }
 
 fn spin_up<'a>(config: Arc<Config>, playout: Arc<Playout>, game: &Game, send_to_main: Sender<((Vec<usize>, Color, usize), Sender<(Vec<usize>, Vec<Move>, bool, usize)>)>) -> (Vec<JoinGuard<'a, ()>>, Vec<Sender<()>>) {
@@ -197,3 +231,10 @@ fn finish(root: &Node, game: &Game, color: Color, sender: Sender<(Move,usize)>,
         best_node.m()
     }
 }
+
+fn analysis(root: &Node, m: Move, playouts: usize) -> Analysis {
+    let moves = root.children().iter()
+        .map(|node| MoveStats { m: node.m(), plays: node.plays(), win_ratio: node.win_ratio() })
+        .collect();
+    Analysis::new(m, playouts, root.win_ratio(), moves)
+}
@@ -156,8 +156,7 @@ impl Node {
                 .iter()
                 .map(|&m| Node::new(m, self.config.clone()))
                 .collect();
-            let size = game.size() as usize;
-            if self.children.len() <= (size * size / 10) {
+            if self.children.len() <= (game.point_count() / 10) {
                 if !self.config.play_out_aftermath || game.winner() == game.next_player() {
                     //don't pass if we're losing on the board on CGOS, but otherwise it's OK
                     self.children.push(Node::new(Pass(game.next_player()), self.config.clone()));
@@ -176,10 +175,9 @@ impl Node {
                 .map(|m| self.new_leaf(board, m, matcher.clone()))
                 .collect();
 
-            self.priors(&mut children, board);
+            self.priors(&mut children, board, matcher);
             self.children = children;
-            let size = board.size() as usize;
-            if self.children.len() <= (size * size / 10) {
+            if self.children.len() <= (board.point_count() / 10) {
                 let player = board.next_player();
                 if !self.config.play_out_aftermath || board.winner() == player {
                     //don't pass if we're losing on the board on CGOS, but otherwise it's OK
@@ -194,7 +192,7 @@ impl Node {
         not_terminal
     }
 
-    pub fn priors(&self, children: &mut Vec<Node>, board: &Board) {
+    pub fn priors(&self, children: &mut Vec<Node>, board: &Board, matcher: Arc<Matcher>) {
             let color = board.next_player().opposite();
 
             let in_danger = board.chains().iter()
@@ -220,6 +218,28 @@ impl Node {
                     }
                 }
             }
+
+            if self.config.uct.priors.use_semeai {
+                let player = board.next_player();
+                for semeai in board.semeais(player).iter().filter(|s| s.winner == player && s.critical) {
+                    for c in semeai.moves.iter() {
+                        if let Some(node) = children.iter_mut().find(|n| n.m() == Play(player, c.col, c.row)) {
+                            node.plays += self.config.uct.priors.semeai;
+                            node.wins += self.config.uct.priors.semeai;
+                        }
+                    }
+                }
+            }
+
+            if self.config.uct.priors.use_large_patterns {
+                for node in children.iter_mut() {
+                    if let Some(weight) = matcher.large_pattern_weight(board, &node.m()) {
+                        let prior = (weight * self.config.uct.priors.large_patterns as f32) as usize;
+                        node.plays += prior;
+                        node.wins += prior;
+                    }
+                }
+            }
     }
 
     pub fn new_leaf(&self, board: &Board, m: &Move, matcher: Arc<Matcher>) -> Node {
@@ -230,7 +250,7 @@ impl Node {
             node.wins += 0; // That's a negative prior
         }
         if self.config.uct.priors.use_empty {
-            let distance = m.coord().distance_to_border(board.size());
+            let distance = m.coord().distance_to_border(board.width(), board.height());
             if distance <= 2 && self.in_empty_area(board, m) {
                 if distance <= 1 {
                     node.plays += self.config.uct.priors.empty;
@@ -242,20 +262,20 @@ impl Node {
             }
         }
         if self.config.uct.priors.use_patterns {
-            let count = self.matching_patterns_count(board, m, matcher);
-            let prior = count * self.config.uct.priors.patterns;
+            let weight = self.matching_patterns_weight(board, m, matcher);
+            let prior = (weight * self.config.uct.priors.patterns as f32) as usize;
             node.plays += prior;
             node.wins += prior;
         }
         node
     }
 
-    fn matching_patterns_count(&self, board: &Board, m: &Move, matcher: Arc<Matcher>) -> usize {
-        matcher.pattern_count(board, &m.coord())
+    fn matching_patterns_weight(&self, board: &Board, m: &Move, matcher: Arc<Matcher>) -> f32 {
+        matcher.pattern_weight(board, &m.coord(), *m.color())
     }
 
     fn in_empty_area(&self, board: &Board, m: &Move) -> bool {
-        m.coord().manhattan_distance_three_neighbours(board.size())
+        m.coord().manhattan_distance_three_neighbours(board.width(), board.height())
             .iter()
             .all(|c| board.color(c) == Empty)
     }
@@ -317,6 +337,10 @@ impl Node {
         self.plays
     }
 
+    pub fn children(&self) -> &Vec<Node> {
+        &self.children
+    }
+
     pub fn descendants(&self) -> usize {
         self.descendants
     }
@@ -111,6 +111,20 @@ fn expand_adds_pass_in_the_endgame() {
     assert!(found_pass);
 }
 
+#[test]
+fn expand_adds_the_semeai_prior_to_the_moves_that_win_a_race() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/semeai/no-eyes.sgf")).unwrap();
+    let board = parser.game().unwrap().board();
+    let mut with_semeai = Config::default();
+    with_semeai.uct.priors.use_semeai = true;
+    let mut node = Node::new(Pass(White), Arc::new(with_semeai));
+    node.expand(&board, matcher());
+    let mut baseline = Node::new(Pass(White), config());
+    baseline.expand(&board, matcher());
+    let m = Play(Black, 4, 9);
+    assert_eq!(baseline.find_child(m).plays() + 20, node.find_child(m).plays());
+}
+
 #[test]
 fn expand_doesnt_add_pass_before_the_endgame() {
     let game = Game::new(5, 6.5, KgsChinese);
@@ -396,7 +410,7 @@ fn full_uct_cycle(size: u8, b: &mut Bencher) {
     cfg.uct.priors.use_patterns = true;
     let config = Arc::new(cfg);
     let mut root = Node::root(&game, Black, config.clone());
-    let playout = Playout::new(config.clone(), matcher.clone());
+    let playout = Playout::new(config.clone(), matcher.clone()).unwrap();
     let mut rng = weak_rng();
     b.iter(|| {
         let (path, moves, _, nodes_added) = root.find_leaf_and_expand(&game, matcher.clone());
@@ -0,0 +1,283 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+// The C interface for embedding the engine in programs that aren't
+// written in Rust. It wraps the same Game and EngineController the
+// GTP interpreter uses. No entry point unwinds into C: a panic makes
+// it return IOM_INTERNAL_ERROR, NULL or 0 instead. The header in include/iomrascalai.h is
+// generated from this file by bin/generate-header.
+
+use board::Black;
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::Move;
+use board::Pass;
+use board::Play;
+use board::White;
+use config::Config;
+use engine;
+use engine::EngineController;
+use game::Game;
+use patterns::Matcher;
+use score::Score;
+
+use std::os::raw::c_int;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::ptr;
+use std::sync::Arc;
+use std::sync::mpsc::channel;
+
+mod test;
+
+pub const IOM_EMPTY: c_int = 0;
+pub const IOM_BLACK: c_int = 1;
+pub const IOM_WHITE: c_int = 2;
+
+pub const IOM_OK: c_int = 0;
+pub const IOM_RESIGN: c_int = 1;
+pub const IOM_ILLEGAL_MOVE: c_int = -1;
+pub const IOM_INVALID_ARGUMENT: c_int = -2;
+pub const IOM_INTERNAL_ERROR: c_int = -3;
+
+// A point on the board, counted from 1 starting at the lower left
+// corner like GTP does. The pass move is (0, 0).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IomVertex {
+    pub col: u8,
+    pub row: u8,
+}
+
+// Opaque to C.
+pub struct IomGame {
+    controller: EngineController<'static>,
+    game: Game,
+}
+
+impl IomGame {
+
+    pub fn new(width: u8, height: u8, komi: f32) -> Result<IomGame, String> {
+        let config = Arc::new(Config::default());
+        let matcher = Arc::new(Matcher::new());
+        let engine = try!(engine::factory(config.clone(), matcher));
+        Ok(IomGame {
+            controller: EngineController::new(config.clone(), engine, None),
+            game: Game::rectangular(width, height, komi, config.ruleset),
+        })
+    }
+
+    pub fn play(&mut self, m: Move) -> c_int {
+        if let Play(_, col, row) = m {
+            if !Coord::new(col, row).is_inside(self.game.width(), self.game.height()) {
+                return IOM_INVALID_ARGUMENT;
+            }
+        }
+        match self.game.play(m) {
+            Ok(g) => {
+                self.game = g;
+                IOM_OK
+            },
+            Err(_) => IOM_ILLEGAL_MOVE
+        }
+    }
+
+    pub fn genmove(&mut self, color: Color, time_limit: u32) -> Result<Move, c_int> {
+        let (send_move, receive_move) = channel();
+        self.controller.run_with_time_limit(color, &self.game, time_limit, send_move);
+        let m = match receive_move.recv() {
+            Ok(m) => m,
+            Err(_) => return Err(IOM_INTERNAL_ERROR)
+        };
+        match self.play(m) {
+            IOM_OK => Ok(m),
+            code => Err(code)
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+}
+
+// Runs the body of an entry point and returns `error` if it panics,
+// as unwinding into the C caller is undefined behaviour.
+fn catch_panic<T, F: FnOnce() -> T>(error: T, f: F) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(error)
+}
+
+fn color_from_c(color: c_int) -> Option<Color> {
+    match color {
+        IOM_BLACK => Some(Black),
+        IOM_WHITE => Some(White),
+        _ => None
+    }
+}
+
+fn color_to_c(color: Color) -> c_int {
+    match color {
+        Black => IOM_BLACK,
+        White => IOM_WHITE,
+        Empty => IOM_EMPTY,
+    }
+}
+
+fn move_from_c(color: Color, vertex: IomVertex) -> Move {
+    if vertex.col == 0 && vertex.row == 0 {
+        Pass(color)
+    } else {
+        Play(color, vertex.col, vertex.row)
+    }
+}
+
+fn move_to_c(m: Move) -> IomVertex {
+    if m.is_pass() || m.is_resign() {
+        IomVertex { col: 0, row: 0 }
+    } else {
+        let coord = m.coord();
+        IomVertex { col: coord.col, row: coord.row }
+    }
+}
+
+// Returns NULL if the size isn't supported. The game uses the
+// default configuration, so the engine behaves like the GTP binary
+// started without any options.
+#[no_mangle]
+pub extern "C" fn iom_game_new(width: u8, height: u8, komi: f32) -> *mut IomGame {
+    catch_panic(ptr::null_mut(), || {
+        if !Board::is_valid_size(width, height) {
+            return ptr::null_mut();
+        }
+        match IomGame::new(width, height, komi) {
+            Ok(game) => Box::into_raw(Box::new(game)),
+            Err(_) => ptr::null_mut()
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn iom_game_free(game: *mut IomGame) {
+    catch_panic((), || {
+        if !game.is_null() {
+            drop(Box::from_raw(game));
+        }
+    })
+}
+
+// Returns IOM_OK, IOM_ILLEGAL_MOVE, IOM_INVALID_ARGUMENT or
+// IOM_INTERNAL_ERROR.
+#[no_mangle]
+pub unsafe extern "C" fn iom_play(game: *mut IomGame, color: c_int, vertex: IomVertex) -> c_int {
+    catch_panic(IOM_INTERNAL_ERROR, || {
+        match (game.as_mut(), color_from_c(color)) {
+            (Some(game), Some(color)) => game.play(move_from_c(color, vertex)),
+            _ => IOM_INVALID_ARGUMENT
+        }
+    })
+}
+
+// Returns the color of the player to move.
+#[no_mangle]
+pub unsafe extern "C" fn iom_next_player(game: *const IomGame) -> c_int {
+    catch_panic(IOM_INTERNAL_ERROR, || {
+        match game.as_ref() {
+            Some(game) => color_to_c(game.game().next_player()),
+            None => IOM_EMPTY
+        }
+    })
+}
+
+// Writes up to `len` legal moves of the player to move into
+// `vertices` and returns the number of legal moves, which may be
+// larger than `len`. Passing is always legal and not included.
+#[no_mangle]
+pub unsafe extern "C" fn iom_legal_moves(game: *const IomGame, vertices: *mut IomVertex, len: usize) -> usize {
+    catch_panic(0, || {
+        let game = match game.as_ref() {
+            Some(game) => game,
+            None => return 0
+        };
+        let moves = game.game().legal_moves();
+        if !vertices.is_null() {
+            for (i, &m) in moves.iter().take(len).enumerate() {
+                *vertices.offset(i as isize) = move_to_c(m);
+            }
+        }
+        moves.len()
+    })
+}
+
+// Thinks for `time_limit` milliseconds, plays the move and writes it
+// to `vertex`. Returns IOM_OK or IOM_RESIGN if the engine gave up,
+// and IOM_INTERNAL_ERROR if the engine failed.
+#[no_mangle]
+pub unsafe extern "C" fn iom_genmove(game: *mut IomGame, color: c_int, time_limit: u32, vertex: *mut IomVertex) -> c_int {
+    catch_panic(IOM_INTERNAL_ERROR, || {
+        match (game.as_mut(), color_from_c(color), vertex.as_mut()) {
+            (Some(game), Some(color), Some(vertex)) => match game.genmove(color, time_limit) {
+                Ok(m) => {
+                    *vertex = move_to_c(m);
+                    if m.is_resign() { IOM_RESIGN } else { IOM_OK }
+                },
+                Err(code) => code
+            },
+            _ => IOM_INVALID_ARGUMENT
+        }
+    })
+}
+
+// The area score including komi. Positive if black is ahead and
+// negative if white is ahead.
+#[no_mangle]
+pub unsafe extern "C" fn iom_score(game: *const IomGame) -> f32 {
+    catch_panic(0.0, || {
+        match game.as_ref() {
+            Some(game) => game.game().score().margin(),
+            None => 0.0
+        }
+    })
+}
+
+// Writes the color each point is counted for (IOM_EMPTY for neutral
+// points) into `owners`, row by row starting at the lower left
+// corner. Returns the number of points on the board, or 0 if `len`
+// is too small.
+#[no_mangle]
+pub unsafe extern "C" fn iom_ownership(game: *const IomGame, owners: *mut c_int, len: usize) -> usize {
+    catch_panic(0, || {
+        let game = match game.as_ref() {
+            Some(game) => game,
+            None => return 0
+        };
+        let ownership = Score::ownership(&game.game().board());
+        if owners.is_null() || len < ownership.len() {
+            return 0;
+        }
+        for (i, &color) in ownership.iter().enumerate() {
+            *owners.offset(i as isize) = color_to_c(color);
+        }
+        ownership.len()
+    })
+}
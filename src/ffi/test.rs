@@ -0,0 +1,97 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use super::*;
+use super::catch_panic;
+
+use std::os::raw::c_int;
+use std::ptr;
+
+fn vertex(col: u8, row: u8) -> IomVertex {
+    IomVertex { col: col, row: row }
+}
+
+#[test]
+fn an_unsupported_board_size_returns_null() {
+    assert!(iom_game_new(0, 9, 6.5).is_null());
+    assert!(iom_game_new(30, 30, 6.5).is_null());
+}
+
+#[test]
+fn play_rejects_illegal_moves() {
+    unsafe {
+        let game = iom_game_new(9, 9, 6.5);
+        assert_eq!(IOM_OK, iom_play(game, IOM_BLACK, vertex(3, 3)));
+        assert_eq!(IOM_ILLEGAL_MOVE, iom_play(game, IOM_WHITE, vertex(3, 3)));
+        assert_eq!(IOM_INVALID_ARGUMENT, iom_play(game, IOM_WHITE, vertex(10, 3)));
+        assert_eq!(IOM_INVALID_ARGUMENT, iom_play(game, IOM_EMPTY, vertex(4, 4)));
+        assert_eq!(IOM_WHITE, iom_next_player(game));
+        iom_game_free(game);
+    }
+}
+
+#[test]
+fn legal_moves_returns_the_count_even_if_the_buffer_is_too_small() {
+    unsafe {
+        let game = iom_game_new(9, 9, 6.5);
+        iom_play(game, IOM_BLACK, vertex(5, 5));
+        let mut vertices = [vertex(0, 0); 10];
+        assert_eq!(80, iom_legal_moves(game, vertices.as_mut_ptr(), 10));
+        assert!(vertices.iter().all(|v| *v != vertex(5, 5) && *v != vertex(0, 0)));
+        assert_eq!(80, iom_legal_moves(game, ptr::null_mut(), 0));
+        iom_game_free(game);
+    }
+}
+
+#[test]
+fn genmove_plays_the_move() {
+    unsafe {
+        let game = iom_game_new(9, 9, 6.5);
+        let mut v = vertex(0, 0);
+        assert_eq!(IOM_OK, iom_genmove(game, IOM_BLACK, 100, &mut v));
+        assert_eq!(IOM_WHITE, iom_next_player(game));
+        assert_eq!(IOM_ILLEGAL_MOVE, iom_play(game, IOM_WHITE, v));
+        iom_game_free(game);
+    }
+}
+
+#[test]
+fn score_and_ownership_agree() {
+    unsafe {
+        let game = iom_game_new(3, 3, 0.5);
+        iom_play(game, IOM_BLACK, vertex(2, 2));
+        let mut owners = [IOM_EMPTY; 9];
+        assert_eq!(0, iom_ownership(game, owners.as_mut_ptr(), 8));
+        assert_eq!(9, iom_ownership(game, owners.as_mut_ptr(), 9));
+        assert!(owners.iter().all(|&owner| owner == IOM_BLACK));
+        assert_eq!(8.5, iom_score(game));
+        iom_game_free(game);
+    }
+}
+
+#[test]
+fn catch_panic_returns_the_error_instead_of_unwinding() {
+    assert_eq!(IOM_INTERNAL_ERROR, catch_panic(IOM_INTERNAL_ERROR, || -> c_int { panic!("engine failed") }));
+    assert_eq!(IOM_OK, catch_panic(IOM_INTERNAL_ERROR, || IOM_OK));
+}
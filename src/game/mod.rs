@@ -42,6 +42,9 @@ pub trait Info {
 }
 
 
+/// A game in progress: the board together with the history needed
+/// to detect superko. Games are immutable, playing a move returns a
+/// new game.
 #[derive(Clone)]
 pub struct Game {
     board: Board,
@@ -50,13 +53,19 @@ pub struct Game {
 }
 
 impl Game {
+    /// A new game on an empty square board.
     pub fn new(size: u8, komi: f32, ruleset: Ruleset) -> Game {
-        let new_board = Board::new(size, komi, ruleset);
+        Game::rectangular(size, size, komi, ruleset)
+    }
+
+    /// A new game on an empty board of the given size.
+    pub fn rectangular(width: u8, height: u8, komi: f32, ruleset: Ruleset) -> Game {
+        let new_board = Board::rectangular(width, height, komi, ruleset);
 
         Game {
             board: new_board,
             last_move: NoMove,
-            zobrist_hash_table: ZobristHashTable::new(size),
+            zobrist_hash_table: ZobristHashTable::new(width, height, ruleset.ko_rule()),
         }
     }
 
@@ -68,13 +77,17 @@ impl Game {
        }
     }
 
+    /// The game after `m`, or the reason why `m` is illegal under
+    /// the ruleset of the game (including superko).
     pub fn play(&self, m: Move) -> Result<Game, IllegalMove> {
         let mut new_board = self.board.clone();
 
         match new_board.play(m) {
             Ok(_) => {
                 let mut new_game_state = Game::with_new_state(new_board, self.zobrist_hash_table.clone(), m);
-                if !m.is_pass() && !m.is_resign() {
+                if m.is_pass() {
+                    new_game_state.zobrist_hash_table.update_after_pass(&m);
+                } else if !m.is_resign() {
                     match new_game_state.check_and_update_super_ko(&m) {
                         Err(_) => return Err(IllegalMove::SuperKo),
                         Ok(_) => {}
@@ -94,10 +107,12 @@ impl Game {
         self.last_move
     }
 
+    /// The player whose turn it is.
     pub fn next_player(&self) -> Color {
         self.board.next_player()
     }
 
+    /// True after two consecutive passes or a resignation.
     pub fn is_over(&self) -> bool {
         self.board.is_game_over()
     }
@@ -106,14 +121,24 @@ impl Game {
         self.board.komi()
     }
 
-    pub fn size(&self) -> u8 {
-        self.board.size()
+    pub fn width(&self) -> u8 {
+        self.board.width()
+    }
+
+    pub fn height(&self) -> u8 {
+        self.board.height()
+    }
+
+    pub fn point_count(&self) -> usize {
+        self.board.point_count()
     }
 
+    /// The score of the current position as if the game ended now.
     pub fn score(&self) -> Score {
         self.board.score()
     }
 
+    /// The winner of the current position, see `score`.
     pub fn winner(&self) -> Color {
         self.board.winner()
     }
@@ -122,10 +147,21 @@ impl Game {
         self.board.set_komi(komi);
     }
 
+    /// A copy of the current board.
     pub fn board(&self) -> Board {
         self.board.clone()
     }
 
+    /// All legal plays for the next player, taking superko into
+    /// account. Passing is always legal and not included.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        self.board
+            .legal_moves_without_superko_check()
+            .into_iter()
+            .filter(|&m| self.play(m).is_ok())
+            .collect()
+    }
+
     pub fn legal_moves_without_eyes(&self) -> Vec<Move> {
         self.board
             .legal_moves_without_eyes()
@@ -143,7 +179,7 @@ impl Display for Game {
 
         // Then we print the col numbers under the board
         s.push_str(format!("{:3}", "").as_ref());
-        for col in 1..self.board.size()+1 {
+        for col in 1..self.board.width()+1 {
             s.push_str(format!("{:<2}", col).as_ref());
         }
         s.push_str("\n");
@@ -61,3 +61,16 @@ fn it_should_handle_resign() {
     let res = g.play(Resign(Black));
     assert!(res.is_ok());
 }
+
+#[test]
+fn legal_moves_leaves_out_suicide() {
+    let mut g = Game::new(3, 6.5, KgsChinese);
+
+    g = g.play(Play(Black, 1, 2)).unwrap();
+    g = g.play(Pass(White)).unwrap();
+    g = g.play(Play(Black, 2, 1)).unwrap();
+
+    let moves = g.legal_moves();
+    assert_eq!(6, moves.len());
+    assert!(!moves.contains(&Play(White, 1, 1)));
+}
@@ -22,51 +22,92 @@
 
 use board::Black;
 use board::Board;
+use board::Color;
 use board::Coord;
 use board::Empty;
 use board::Move;
 use board::Play;
 use board::White;
+use ruleset::KoRule;
+use ruleset::PositionalSuperKo;
+use ruleset::SimpleKo;
+use ruleset::SituationalSuperKo;
 
 use rand::random;
 
+mod test;
+
 #[derive(Clone)]
 pub struct ZobristHashTable {
     black: Vec<u64>,
     hashes: Vec<u64>,
-    size: u8,
+    ko_rule: KoRule,
+    position: u64,
     white: Vec<u64>,
+    white_to_play: u64,
+    width: u8,
 }
 
 impl ZobristHashTable {
 
-    pub fn new(size: u8) -> ZobristHashTable {
+    pub fn new(width: u8, height: u8, ko_rule: KoRule) -> ZobristHashTable {
         let mut black = Vec::new();
         let mut white = Vec::new();
-        for _ in Coord::for_board_size(size) {
+        for _ in Coord::for_board_size(width, height) {
             black.push(random::<u64>());
             white.push(random::<u64>());
         }
         ZobristHashTable {
             black: black,
             hashes: vec!(0),
-            size: size,
+            ko_rule: ko_rule,
+            position: 0,
             white: white,
+            white_to_play: random::<u64>(),
+            width: width,
         }
     }
 
     pub fn check_and_update_super_ko(&mut self, m: &Move, b: &Board) -> Result<(),()> {
-        let hash = self.compute_hash(m, b);
-        if self.hashes.contains(&hash) {
-            Err(())
-        } else {
+        let position = self.compute_hash(m, b);
+        let hash = self.hash_for_situation(position, m.color().opposite());
+        match self.ko_rule {
+            SimpleKo => {
+                self.position = position;
+                Ok(())
+            },
+            PositionalSuperKo | SituationalSuperKo => {
+                if self.hashes.contains(&hash) {
+                    Err(())
+                } else {
+                    self.position = position;
+                    self.hashes.push(hash);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    // A pass doesn't change the position, but under situational
+    // superko it creates a new situation as the player to move
+    // changes.
+    pub fn update_after_pass(&mut self, m: &Move) {
+        if self.ko_rule == SituationalSuperKo {
+            let hash = self.hash_for_situation(self.position, m.color().opposite());
             self.hashes.push(hash);
-            Ok(())
+        }
+    }
+
+    fn hash_for_situation(&self, position: u64, to_play: Color) -> u64 {
+        if self.ko_rule == SituationalSuperKo && to_play == White {
+            position ^ self.white_to_play
+        } else {
+            position
         }
     }
 
     fn compute_hash(&self, m: &Move, b: &Board) -> u64 {
-        let mut hash = self.hashes[self.hashes.len()-1];
+        let mut hash = self.position;
         hash = self.change_hash(hash, m);
         for coord in b.adv_stones_removed() {
             hash = self.change_hash(hash, &Play(m.color().opposite(), coord.col, coord.row));
@@ -84,8 +125,8 @@ impl ZobristHashTable {
     fn hash_for(&self, m: &Move) -> u64 {
         match *m.color() {
             Empty => 0,
-            Black => self.black[m.coord().to_index(self.size)],
-            White => self.white[m.coord().to_index(self.size)]
+            Black => self.black[m.coord().to_index(self.width)],
+            White => self.white[m.coord().to_index(self.width)]
         }
     }
 }
@@ -0,0 +1,79 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Move;
+use board::Play;
+use board::White;
+use ruleset::KoRule;
+use ruleset::Minimal;
+use ruleset::PositionalSuperKo;
+use ruleset::SimpleKo;
+use ruleset::SituationalSuperKo;
+use super::ZobristHashTable;
+
+// Black plays two stones next to the corner and White then commits
+// suicide in the corner. This recreates the position after Black's
+// second move, but this time with Black to move.
+fn play_moves(ko_rule: KoRule, moves: Vec<Move>) -> Vec<Result<(),()>> {
+    let mut board = Board::new(5, 6.5, Minimal);
+    let mut table = ZobristHashTable::new(5, 5, ko_rule);
+    moves.iter().map(|m| {
+        board.play(*m).unwrap();
+        table.check_and_update_super_ko(m, &board)
+    }).collect()
+}
+
+fn suicide_in_corner() -> Vec<Move> {
+    vec!(Play(Black, 1, 2), Play(Black, 2, 1), Play(White, 1, 1))
+}
+
+#[test]
+fn positional_super_ko_forbids_the_same_position_with_the_other_player_to_move() {
+    let results = play_moves(PositionalSuperKo, suicide_in_corner());
+    assert_eq!(Err(()), results[2]);
+}
+
+#[test]
+fn situational_super_ko_allows_the_same_position_with_the_other_player_to_move() {
+    let results = play_moves(SituationalSuperKo, suicide_in_corner());
+    assert_eq!(Ok(()), results[2]);
+}
+
+#[test]
+fn situational_super_ko_forbids_the_same_position_with_the_same_player_to_move() {
+    let mut moves = suicide_in_corner();
+    moves.push(Play(White, 1, 1));
+    let results = play_moves(SituationalSuperKo, moves);
+    assert_eq!(Err(()), results[3]);
+}
+
+#[test]
+fn simple_ko_allows_any_repetition() {
+    let mut moves = suicide_in_corner();
+    moves.push(Play(White, 1, 1));
+    let results = play_moves(SimpleKo, moves);
+    assert!(results.iter().all(|r| r.is_ok()));
+}
@@ -0,0 +1,280 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+// Talks GTP to an external engine that runs as a subprocess.
+
+use board::Black;
+use board::Color;
+use board::MAX_SIZE;
+use board::Move;
+
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::process;
+use std::process::Child;
+use std::process::ChildStdin;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::Sender;
+use std::sync::mpsc::channel;
+use std::thread;
+use time::precise_time_ns;
+
+// What the reader and the timer thread tell the client.
+enum Event {
+    Response(Result<String, String>),
+    // The command with this number wasn't answered in time.
+    TimedOut(usize),
+}
+
+struct TimerState {
+    command: usize,
+    // In nanoseconds, see precise_time_ns.
+    deadline: Option<u64>,
+    stopped: bool,
+}
+
+// A single thread per client that watches the deadline of the
+// command that is currently waiting for a response. Starting and
+// cancelling a deadline wakes it up.
+struct Timer {
+    state: Arc<(Mutex<TimerState>, Condvar)>,
+}
+
+impl Timer {
+
+    fn spawn(events: Sender<Event>) -> Timer {
+        let state = Arc::new((Mutex::new(TimerState { command: 0, deadline: None, stopped: false }), Condvar::new()));
+        let shared = state.clone();
+        thread::spawn(move || {
+            let &(ref lock, ref condvar) = &*shared;
+            let mut state = lock.lock().unwrap();
+            while !state.stopped {
+                match state.deadline {
+                    None => {
+                        state = condvar.wait(state).unwrap();
+                    },
+                    Some(deadline) => {
+                        let now = precise_time_ns();
+                        if now >= deadline {
+                            state.deadline = None;
+                            if events.send(Event::TimedOut(state.command)).is_err() {
+                                return;
+                            }
+                        } else {
+                            let ms = (deadline - now) / 1000000 + 1;
+                            state = condvar.wait_timeout_ms(state, ms as u32).unwrap().0;
+                        }
+                    }
+                }
+            }
+        });
+        Timer { state: state }
+    }
+
+    fn start(&self, command: usize, timeout: u32) {
+        self.update(|state| {
+            state.command = command;
+            state.deadline = Some(precise_time_ns() + timeout as u64 * 1000000);
+        });
+    }
+
+    fn cancel(&self) {
+        self.update(|state| state.deadline = None);
+    }
+
+    fn stop(&self) {
+        self.update(|state| state.stopped = true);
+    }
+
+    fn update<F: FnOnce(&mut TimerState)>(&self, f: F) {
+        let &(ref lock, ref condvar) = &*self.state;
+        f(&mut lock.lock().unwrap());
+        condvar.notify_one();
+    }
+
+}
+
+pub struct Client {
+    child: Child,
+    // The number of commands sent so far, to recognise the time outs
+    // of earlier commands.
+    commands: usize,
+    events: Receiver<Event>,
+    program: String,
+    // The board size of the current game.
+    size: u8,
+    stdin: ChildStdin,
+    timeout: u32,
+    timer: Timer,
+}
+
+impl Client {
+
+    // The command is split at whitespace, quoting isn't supported.
+    // Every command has to be answered within timeout milliseconds,
+    // otherwise the engine is killed.
+    pub fn spawn(command: &str, timeout: u32) -> Result<Client, String> {
+        let mut words = command.split_whitespace();
+        let program = match words.next() {
+            Some(program) => String::from(program),
+            None => return Err(String::from("No GTP engine given"))
+        };
+        let args: Vec<&str> = words.collect();
+        let mut child = try!(process::Command::new(&program)
+                             .args(&args)
+                             .stdin(Stdio::piped())
+                             .stdout(Stdio::piped())
+                             .stderr(Stdio::null())
+                             .spawn()
+                             .map_err(|e| format!("Can't start {}: {}", program, e)));
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (send_event, events) = channel();
+        let timer = Timer::spawn(send_event.clone());
+        thread::spawn(move || {
+            let mut response = String::new();
+            for line in BufReader::new(stdout).lines() {
+                let line = match line {
+                    Ok(line) => String::from(line.trim_right_matches('\r')),
+                    Err(_) => break
+                };
+                if line.trim().is_empty() {
+                    // Skip empty lines between responses.
+                    if response.is_empty() {
+                        continue;
+                    }
+                    if send_event.send(Event::Response(parse_response(&response))).is_err() {
+                        return;
+                    }
+                    response.clear();
+                } else {
+                    if !response.is_empty() {
+                        response.push('\n');
+                    }
+                    response.push_str(&line);
+                }
+            }
+            let _ = send_event.send(Event::Response(Err(String::from("engine exited"))));
+        });
+        Ok(Client {
+            child: child,
+            commands: 0,
+            events: events,
+            program: program,
+            size: MAX_SIZE,
+            stdin: stdin,
+            timeout: timeout,
+            timer: timer,
+        })
+    }
+
+    // Returns the response without the leading "=" or the error
+    // message of a "?" response.
+    pub fn send(&mut self, command: &str) -> Result<String, String> {
+        let written = writeln!(self.stdin, "{}", command).and_then(|_| self.stdin.flush());
+        if let Err(e) = written {
+            return Err(format!("{}: can't send {}: {}", self.program, command, e));
+        }
+        self.commands += 1;
+        self.timer.start(self.commands, self.timeout);
+        loop {
+            let event = self.events.recv().unwrap_or(Event::Response(Err(String::from("engine exited"))));
+            match event {
+                Event::Response(response) => {
+                    self.timer.cancel();
+                    return response.map_err(|e| format!("{}: {} failed: {}", self.program, command, e));
+                },
+                Event::TimedOut(n) if n == self.commands => {
+                    let _ = self.child.kill();
+                    return Err(format!("{}: no response to {} after {}ms", self.program, command, self.timeout));
+                },
+                // A command that was answered just before its time ran out.
+                Event::TimedOut(_) => {}
+            }
+        }
+    }
+
+    pub fn name(&mut self) -> Result<String, String> {
+        self.send("name")
+    }
+
+    pub fn new_game(&mut self, size: u8, komi: f32) -> Result<(), String> {
+        try!(self.send(&format!("boardsize {}", size)));
+        try!(self.send("clear_board"));
+        try!(self.send(&format!("komi {}", komi)));
+        self.size = size;
+        Ok(())
+    }
+
+    // Gives the engine ms milliseconds for every move.
+    pub fn time_per_move(&mut self, ms: u32) -> Result<(), String> {
+        let seconds = (ms + 999) / 1000;
+        self.send(&format!("time_settings 0 {} 1", seconds)).map(|_| ())
+    }
+
+    pub fn play(&mut self, m: Move) -> Result<(), String> {
+        let command = format!("play {} {}", gtp_color(*m.color()), m.to_gtp());
+        self.send(&command).map(|_| ())
+    }
+
+    pub fn genmove(&mut self, color: Color) -> Result<Move, String> {
+        let color = gtp_color(color);
+        let vertex = try!(self.send(&format!("genmove {}", color)));
+        match Move::parse_gtp(color, &vertex, self.size, self.size) {
+            Some(m) => Ok(m),
+            None => Err(format!("{}: can't read the move {}", self.program, vertex))
+        }
+    }
+
+    pub fn quit(&mut self) {
+        let _ = self.send("quit");
+    }
+
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        self.timer.stop();
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn gtp_color(color: Color) -> &'static str {
+    if color == Black { "b" } else { "w" }
+}
+
+// Turns a complete response into its text for "=" responses and into
+// an error for "?" responses. The id is dropped.
+pub fn parse_response(response: &str) -> Result<String, String> {
+    let success = response.starts_with("=");
+    if !success && !response.starts_with("?") {
+        return Err(format!("malformed response: {}", response));
+    }
+    let text = String::from(response[1..].trim_left_matches(|c: char| c.is_digit(10)).trim());
+    if success { Ok(text) } else { Err(text) }
+}
@@ -20,57 +20,118 @@
  *                                                                      *
  ************************************************************************/
 
+use book::Book;
 use config::Config;
 use engine::Engine;
 use super::Command;
 use super::GTPInterpreter;
 use version;
 
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
 use std::io::stdin;
+use std::io::stdout;
 use std::sync::Arc;
 
 pub struct Driver;
 
 impl Driver {
-    pub fn new(config: Arc<Config>, engine: Box<Engine>) {
-        let engine_name = "Iomrascalai";
-        let engine_version = version::version();
-        let protocol_version = "2";
+    pub fn new(config: Arc<Config>, engine: Box<Engine>, book: Option<Arc<Book>>) {
+        let input = stdin();
+        Driver::run(config, engine, book, input.lock(), stdout()).unwrap();
+    }
+
+    // Answers the commands read from `reader` on `writer` until the
+    // quit command or the end of the input.
+    pub fn run<R: BufRead, W: Write>(config: Arc<Config>, engine: Box<Engine>, book: Option<Arc<Book>>, reader: R, writer: W) -> io::Result<()> {
+        let mut interpreter = GTPInterpreter::new(config, engine, book);
+        let result = Driver::answer(&mut interpreter, reader, writer);
+        // The engine thread has to be shut down even if the
+        // connection broke. Otherwise dropping the interpreter blocks.
+        interpreter.quit();
+        result
+    }
 
-        let mut interpreter = GTPInterpreter::new(config, engine);
-        let reader = stdin();
+    fn answer<R: BufRead, W: Write>(interpreter: &mut GTPInterpreter, mut reader: R, mut writer: W) -> io::Result<()> {
         let mut command = String::new();
 
         loop {
             command.clear();
-            reader.read_line(&mut command).unwrap();
-
-            let gtp_command = interpreter.read(&*command);
+            if try!(reader.read_line(&mut command)) == 0 {
+                return Ok(());
+            }
 
-            match gtp_command {
-                Command::BoardSize          => print!("= \n\n"),
-                Command::ClearBoard         => print!("= \n\n"),
-                Command::FinalScore(s)      => print!("= {}\n\n", s),
-                Command::GenMove(s)         => print!("= {}\n\n", s),
-                Command::GenMoveError(m, e) => print!("? Illegal move: {:?} ({:?})\n\n", m, e),
-                Command::KnownCommand(b)    => print!("= {}\n\n", b),
-                Command::Komi               => print!("= \n\n"),
-                Command::ListCommands(s)    => print!("= {}\n\n", s),
-                Command::LoadSgf            => print!("= \n\n"),
-                Command::Name               => print!("= {}\n\n", engine_name),
-                Command::Play               => print!("= \n\n"),
-                Command::PlayError(m, e)    => print!("? Illegal move: {:?} ({:?})\n\n", m, e),
-                Command::ProtocolVersion    => print!("= {}\n\n", protocol_version),
-                Command::Quit               => { print!("= \n\n"); return; },
-                Command::ShowBoard(s)       => print!("= {}\n\n", s),
-                Command::TimeLeft           => print!("= \n\n"),
-                Command::TimeSettings       => print!("= \n\n"),
-                Command::Version            => print!("= {}\n\n", engine_version),
-                Command::ErrorMessage(e)    => print!("? {}\n\n", e),
-                Command::Error              => print!("? unknown command\n\n"),
-                Command::Empty              => print!("? empty command\n\n"),
+            let (id, gtp_command) = interpreter.read_with_id(&*command);
+            let quit = match gtp_command {
+                Command::Quit => true,
+                _             => false
+            };
+            let response = match answer(gtp_command) {
+                Some(Ok(s))  => success(id, &s),
+                Some(Err(e)) => failure(id, &e),
+                // Empty lines are ignored by the protocol.
+                None         => continue
+            };
+            try!(write!(writer, "{}", response));
+            try!(writer.flush());
+            if quit {
+                return Ok(());
             }
         }
-
     }
 }
+
+// The text of the answer to a command (an error message if the
+// command failed) or None if the command doesn't get an answer.
+pub fn answer(command: Command) -> Option<Result<String, String>> {
+    let engine_name = "Iomrascalai";
+    let engine_version = version::version();
+    let protocol_version = "2";
+
+    let answer = match command {
+        Command::BoardSize          => Ok(String::new()),
+        Command::ClearBoard         => Ok(String::new()),
+        Command::FinalScore(s)      => Ok(s),
+        Command::FinalStatusList(s) => Ok(s),
+        Command::GenMove(s)         => Ok(s),
+        Command::GenMoveError(..)   => Err(String::from("illegal move")),
+        Command::GetParam(s)        => Ok(s),
+        Command::GoguiAnalyzeCommands(s) => Ok(s),
+        Command::GroupStatus(s)     => Ok(s),
+        Command::KnownCommand(b)    => Ok(b.to_string()),
+        Command::Komi               => Ok(String::new()),
+        Command::ListCommands(s)    => Ok(s),
+        Command::LoadSgf            => Ok(String::new()),
+        Command::Name               => Ok(String::from(engine_name)),
+        Command::Param(s)           => Ok(s),
+        Command::Play               => Ok(String::new()),
+        Command::PlayError(..)      => Err(String::from("illegal move")),
+        Command::ProtocolVersion    => Ok(String::from(protocol_version)),
+        Command::Quit               => Ok(String::new()),
+        Command::SetParam           => Ok(String::new()),
+        Command::ShowBoard(s)       => Ok(s),
+        Command::SyntaxError        => Err(String::from("syntax error")),
+        Command::TimeLeft           => Ok(String::new()),
+        Command::TimeSettings       => Ok(String::new()),
+        Command::Undo               => Ok(String::new()),
+        Command::UnknownCommand     => Err(String::from("unknown command")),
+        Command::Version            => Ok(String::from(engine_version)),
+        Command::ErrorMessage(e)    => Err(e),
+        Command::Empty              => return None,
+    };
+    Some(answer)
+}
+
+pub fn success(id: Option<u32>, message: &str) -> String {
+    response("=", id, message)
+}
+
+pub fn failure(id: Option<u32>, message: &str) -> String {
+    response("?", id, message)
+}
+
+fn response(prefix: &str, id: Option<u32>, message: &str) -> String {
+    let id = id.map(|id| id.to_string()).unwrap_or(String::new());
+    format!("{}{} {}\n\n", prefix, id, message)
+}
@@ -21,15 +21,21 @@
  ************************************************************************/
 #![allow(non_camel_case_types)]
 use std::path::Path;
+use board::Board;
 use board::Color;
+use board::Coord;
 use board::IllegalMove;
 use board::Move;
+use book::Book;
 use config::Config;
 use engine::Engine;
 use engine::EngineController;
 use game::Game;
 use ruleset::Ruleset;
 use sgf::parser::Parser;
+use solver::Solver;
+use score::Score;
+use solver::Status;
 use timer::Timer;
 use strenum::Strenum;
 
@@ -43,7 +49,9 @@ use thread_scoped::JoinGuard;
 use thread_scoped::scoped;
 use time::precise_time_ns;
 
+pub mod client;
 pub mod driver;
+pub mod server;
 mod test;
 
 strenum! {
@@ -51,18 +59,25 @@ strenum! {
         boardsize,
         clear_board,
         final_score,
+        final_status_list,
         genmove,
+        get_param,
+        gogui_analyze_commands,
+        group_status,
         known_command,
         komi,
         list_commands,
         loadsgf,
         name,
+        param,
         play,
         protocol_version,
         quit,
+        set_param,
         showboard,
         time_left,
         time_settings,
+        undo,
         version
 }
 
@@ -70,52 +85,66 @@ pub enum Command {
     BoardSize,
     ClearBoard,
     Empty,
-    Error,
     ErrorMessage(String),
     FinalScore(String),
+    FinalStatusList(String),
     GenMove(String),
     GenMoveError(Move, IllegalMove),
+    GetParam(String),
+    GoguiAnalyzeCommands(String),
+    GroupStatus(String),
     KnownCommand(bool),
     Komi,
     ListCommands(String),
     LoadSgf,
     Name,
+    Param(String),
     Play,
     PlayError(Move, IllegalMove),
     ProtocolVersion,
     Quit,
+    SetParam,
     ShowBoard(String),
+    SyntaxError,
     TimeLeft,
     TimeSettings,
+    Undo,
+    UnknownCommand,
     Version,
 }
 
 pub enum ControllerCommand {
     GenMove(Game, Color, Timer),
     Reset,
+    SetConfig(Arc<Config>),
     ShutDown,
 }
 
+// These are only read at startup, so changing them later has no
+// effect.
+const STARTUP_PARAMS: [&'static str; 4] = ["book_file", "concurrent_sessions", "large_patterns_file", "patterns_file"];
+
 pub struct GTPInterpreter<'a> {
     _guard: JoinGuard<'a, ()>,
     config: Arc<Config>,
     game: Game,
+    history: Vec<Game>,
     receive_move_from_controller: Receiver<Move>,
     send_command_to_controller: Sender<ControllerCommand>,
     timer: Timer,
 }
 
 impl<'a> GTPInterpreter<'a> {
-    pub fn new(config: Arc<Config>, engine: Box<Engine>) -> GTPInterpreter<'a> {
+    pub fn new(config: Arc<Config>, engine: Box<Engine>, book: Option<Arc<Book>>) -> GTPInterpreter<'a> {
         let komi      = 6.5;
         let boardsize = 19;
         let (send_command_to_controller, receive_command_from_interpreter) = channel::<ControllerCommand>();
         let (send_move_to_interpreter, receive_move_from_controller) = channel::<Move>();
         let controller_config = config.clone();
-        let genmove_config = config.clone();
+        let mut genmove_config = config.clone();
         unsafe {
             let guard = scoped(move || {
-                let mut controller = EngineController::new(controller_config, engine);
+                let mut controller = EngineController::new(controller_config, engine, book);
                 loop {
                     match receive_command_from_interpreter.recv() {
                         Ok(command) => {
@@ -127,7 +156,15 @@ impl<'a> GTPInterpreter<'a> {
                                 },
                                 ControllerCommand::Reset => {
                                     controller.reset();
-                                }
+                                },
+                                ControllerCommand::SetConfig(config) => {
+                                    // The interpreter already checked the
+                                    // configuration.
+                                    match controller.set_config(config.clone()) {
+                                        Ok(_) => genmove_config = config,
+                                        Err(e) => log!("Can't change the configuration: {}", e)
+                                    }
+                                },
                                 ControllerCommand::ShutDown => { break; },
                             }
                         },
@@ -139,6 +176,7 @@ impl<'a> GTPInterpreter<'a> {
                 _guard: guard,
                 config: config.clone(),
                 game: Game::new(boardsize, komi, config.ruleset),
+                history: vec!(),
                 receive_move_from_controller: receive_move_from_controller,
                 send_command_to_controller: send_command_to_controller,
                 timer: Timer::new(config),
@@ -146,8 +184,10 @@ impl<'a> GTPInterpreter<'a> {
         }
     }
 
+    // Can be called more than once, the controller is already gone
+    // after the first call.
     pub fn quit(&self) {
-        self.send_command_to_controller.send(ControllerCommand::ShutDown).unwrap();
+        let _ = self.send_command_to_controller.send(ControllerCommand::ShutDown);
     }
 
     pub fn komi(&self) -> f32 {
@@ -158,67 +198,80 @@ impl<'a> GTPInterpreter<'a> {
         self.config.ruleset
     }
 
-    pub fn boardsize(&self) -> u8 {
-        self.game.size()
+    pub fn width(&self) -> u8 {
+        self.game.width()
+    }
+
+    pub fn height(&self) -> u8 {
+        self.game.height()
     }
 
     pub fn read(&mut self, input: &str) -> Command {
-        let preprocessed = self.preprocess(input);
-        if preprocessed.len() == 0 { return Command::Empty };
+        self.read_with_id(input).1
+    }
 
-        let command: Vec<&str> = preprocessed.split(' ').collect();
+    // Also returns the optional numeric id the command started with,
+    // as it has to be repeated in the response.
+    pub fn read_with_id(&mut self, input: &str) -> (Option<u32>, Command) {
+        let preprocessed = self.preprocess(input);
+        let mut words: Vec<&str> = preprocessed.split_whitespace().collect();
+        let id = words.first().and_then(|word| word.parse::<u32>().ok());
+        if id.is_some() {
+            words.remove(0);
+        }
+        (id, self.execute(&words))
+    }
 
-        //command[0] is never empty because a split always has at least one part
-        let command_name = match <KnownCommands>::enumify(command[0]) {
-        	Some(comm)     => comm,
-        	None           => return Command::Error
-    	};
+    fn execute(&mut self, command: &[&str]) -> Command {
+        let command_name = match command.first() {
+            Some(name) => match Self::known_command(name) {
+                Some(comm) => comm,
+                None       => return Command::UnknownCommand
+            },
+            None => return Command::Empty
+        };
 
         match command_name {
             KnownCommands::name             => Command::Name,
             KnownCommands::version          => Command::Version,
             KnownCommands::protocol_version => Command::ProtocolVersion,
-            KnownCommands::list_commands    => Command::ListCommands(<KnownCommands>::stringify()),
+            KnownCommands::list_commands    => Command::ListCommands(<KnownCommands>::stringify().replace("gogui_", "gogui-")),
             KnownCommands::known_command    => match command.get(1) {
-            	Some(comm) => Command::KnownCommand(<KnownCommands>::enumify(&comm).is_some()),
-            	None => Command::KnownCommand(false)
-        	},
-            KnownCommands::boardsize        => match command.get(1) {
-            	Some(comm) => match comm.parse::<u8>() {
-                    Ok(size) => {
-                        self.game = Game::new(size, self.komi(), self.ruleset());
-                        Command::BoardSize
-                    },
-                    Err(_) => Command::Error
+                Some(comm) => Command::KnownCommand(Self::known_command(comm).is_some()),
+                None => Command::SyntaxError
+            },
+            KnownCommands::boardsize        => match command.get(1).map(|size| size.parse::<u8>()) {
+                Some(Ok(size)) if Board::is_valid_size(size, size) => {
+                    self.game = Game::new(size, self.komi(), self.ruleset());
+                    self.history.clear();
+                    Command::BoardSize
                 },
-            	None => Command::Error
-        	},
+                Some(Ok(_)) => Command::ErrorMessage(String::from("unacceptable size")),
+                _ => Command::SyntaxError
+            },
             KnownCommands::clear_board      => {
-                self.game = Game::new(self.boardsize(), self.komi(), self.ruleset());
+                self.game = Game::rectangular(self.width(), self.height(), self.komi(), self.ruleset());
+                self.history.clear();
                 self.timer.reset();
                 self.send_command_to_controller.send(ControllerCommand::Reset).unwrap();
                 Command::ClearBoard
             },
-            KnownCommands::komi             => match command.get(1) {
-                Some(comm) =>
-                    match comm.parse::<f32>() {
-                        Ok(komi) => {
-                            self.game.set_komi(komi);
-                            Command::Komi
-                        },
-                        Err(_) => Command::Error
-                    },
-                None => Command::Error
+            KnownCommands::komi             => match command.get(1).map(|komi| komi.parse::<f32>()) {
+                Some(Ok(komi)) => {
+                    self.game.set_komi(komi);
+                    Command::Komi
+                },
+                _ => Command::SyntaxError
             },
-            KnownCommands::genmove          => match command.get(1) {
-        	Some(comm) => {
+            KnownCommands::genmove          => match command.get(1).and_then(|color| Color::parse_gtp(color)) {
+                Some(color) => {
                     self.timer.start();
-        	    let color = Color::from_gtp(comm);
                     let command = ControllerCommand::GenMove(self.game.clone(), color, self.timer.clone());
                     self.send_command_to_controller.send(command).unwrap();
                     let m = self.receive_move_from_controller.recv().unwrap();
                     match self.game.play(m) {
                         Ok(g) => {
+                            self.history.push(self.game.clone());
                             self.game = g;
                             self.timer.stop();
                             Command::GenMove(m.to_gtp())
@@ -228,77 +281,211 @@ impl<'a> GTPInterpreter<'a> {
                         }
                     }
                 },
-                None => Command::Error
-    		},
-            KnownCommands::play             => match command.get(2) {
-            	Some(second) => {
-                    let m = Move::from_gtp(command[1], second); //command[1] should be there
-                    match self.game.play(m) {
+                None => Command::SyntaxError
+            },
+            KnownCommands::group_status     => match command.get(1).and_then(|vertex| Coord::parse_gtp(vertex, self.game.width(), self.game.height())) {
+                Some(target) => self.group_status(target),
+                None => Command::SyntaxError
+            },
+            KnownCommands::play             => match (command.get(1), command.get(2)) {
+                (Some(color), Some(vertex)) => match Move::parse_gtp(color, vertex, self.game.width(), self.game.height()) {
+                    Some(m) => match self.game.play(m) {
                         Ok(g) => {
+                            self.history.push(self.game.clone());
                             self.game = g;
                             Command::Play
                         },
                         Err(e) => {
                             Command::PlayError(m, e)
                         }
-                    }
+                    },
+                    None => Command::SyntaxError
                 },
-            	None => Command::Error
-        	},
+                _ => Command::SyntaxError
+            },
             KnownCommands::showboard        => Command::ShowBoard(format!("\n{}", self.game)),
             KnownCommands::quit             => {
                 self.quit();
                 Command::Quit
             },
             KnownCommands::final_score      => Command::FinalScore(format!("{}", self.game.score())),
-            KnownCommands::time_settings    => match command.get(3) {
-            	Some(third) => {
-            		//command[1] and command[2] should be there
-                    match (command[1].parse::<u32>(), command[2].parse::<u32>(), third.parse::<i32>()) {
+            KnownCommands::final_status_list => match command.get(1) {
+                Some(&status) if ["alive", "dead", "seki"].contains(&status) => self.final_status_list(status),
+                _ => Command::SyntaxError
+            },
+            KnownCommands::time_settings    => match (command.get(1), command.get(2), command.get(3)) {
+                (Some(main), Some(byo), Some(stones)) => {
+                    match (main.parse::<u32>(), byo.parse::<u32>(), stones.parse::<i32>()) {
                         (Ok(main), Ok(byo), Ok(stones)) => {
                             self.timer.setup(main, byo, stones);
                             Command::TimeSettings
                         }
-                        _ => Command::Error
+                        _ => Command::SyntaxError
                     }
                 },
-            	None => Command::Error
-        	},
-            KnownCommands::time_left        => match command.get(3) {
-            	Some(third) => {
-            		//command[2] should be there
-            		//TODO: seems wrong, missing a color
-                    match (command[2].parse::<u32>(), third.parse::<i32>()) {
+                _ => Command::SyntaxError
+            },
+            KnownCommands::time_left        => match (command.get(1).and_then(|color| Color::parse_gtp(color)), command.get(2), command.get(3)) {
+                (Some(_), Some(time), Some(stones)) => {
+                    //TODO: the color is ignored
+                    match (time.parse::<u32>(), stones.parse::<i32>()) {
                         (Ok(time), Ok(stones)) => {
                             self.timer.update(time, stones);
                             Command::TimeLeft
                         },
-                        _ => Command::Error
+                        _ => Command::SyntaxError
                     }
                 },
-            	None => Command::Error
-        	},
-            KnownCommands::loadsgf          => match command.get(1) {
-            	Some(comm) => {
-                    let filename = comm;
-
+                _ => Command::SyntaxError
+            },
+            KnownCommands::loadsgf          => match (command.get(1), command.get(2).map(|n| n.parse::<usize>())) {
+                (_, Some(Err(_))) => Command::SyntaxError,
+                (Some(filename), move_number) => {
                     match Parser::from_path(Path::new(filename)) {
-                    	Ok(parser) => {
-                    		let game = parser.game();
+                        Ok(parser) => {
+                            let game = match move_number {
+                                Some(Ok(n)) => parser.game_before(n),
+                                _           => parser.game()
+                            };
                             match game {
                                 Ok(g) => {
                                     self.game = g;
+                                    self.history.clear();
                                     Command::LoadSgf
                                 },
                                 Err(_) => Command::ErrorMessage(String::from("cannot load file"))
                             }
-                		},
-                    	Err(_) => Command::ErrorMessage(String::from("cannot load file"))
-                	}
+                        },
+                        Err(_) => Command::ErrorMessage(String::from("cannot load file"))
+                    }
+                },
+                (None, _) => Command::SyntaxError
+            },
+            KnownCommands::get_param        => match command.get(1).map(|key| self.config.get(key)) {
+                Some(Ok(value)) => Command::GetParam(value),
+                Some(Err(e)) => Command::ErrorMessage(e),
+                None => Command::SyntaxError
+            },
+            KnownCommands::set_param        => match (command.get(1), command.get(2)) {
+                (Some(key), Some(_)) => match self.set_param(key, &command[2..].join(" ")) {
+                    Ok(_) => Command::SetParam,
+                    Err(e) => Command::ErrorMessage(e)
+                },
+                _ => Command::SyntaxError
+            },
+            // The parameter command of GoGui: lists the parameters
+            // without arguments and sets one otherwise.
+            KnownCommands::param            => match command.get(1) {
+                None => Command::Param(self.gogui_params()),
+                Some(key) => {
+                    let value = command[2..].join(" ");
+                    let value = match self.config.gogui_type(key) {
+                        Ok(ref t) if t == "[bool]" => String::from(if value == "1" { "true" } else if value == "0" { "false" } else { &value[..] }),
+                        _ => value
+                    };
+                    match self.set_param(key, &value) {
+                        Ok(_) => Command::SetParam,
+                        Err(e) => Command::ErrorMessage(e)
+                    }
+                }
+            },
+            KnownCommands::gogui_analyze_commands => Command::GoguiAnalyzeCommands(String::from("param/Parameters/param")),
+            KnownCommands::undo             => match self.history.pop() {
+                Some(game) => {
+                    self.game = game;
+                    // The search tree belongs to a position that is
+                    // no longer on the board.
+                    self.send_command_to_controller.send(ControllerCommand::Reset).unwrap();
+                    Command::Undo
                 },
-            	None => Command::Error
-        	}
+                None => Command::ErrorMessage(String::from("cannot undo"))
+            }
+        }
+    }
+
+    // GTP command names may contain hyphens, the names in
+    // KnownCommands can't.
+    fn known_command(name: &str) -> Option<KnownCommands> {
+        if name.starts_with("gogui_") {
+            None
+        } else {
+            <KnownCommands>::enumify(&name.replace("gogui-", "gogui_"))
+        }
+    }
+
+    // The new configuration reaches the engine before the next move.
+    // A new ruleset only applies to the next game.
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        if STARTUP_PARAMS.contains(&key) {
+            return Err(format!("{} can only be set at startup", key));
+        }
+        let mut config = (*self.config).clone();
+        try!(config.set(key, value));
+        try!(config.check());
+        let config = Arc::new(config);
+        self.config = config.clone();
+        self.timer.set_config(config.clone());
+        self.send_command_to_controller.send(ControllerCommand::SetConfig(config)).unwrap();
+        Ok(())
+    }
+
+    // One line per parameter, booleans are 0 or 1.
+    fn gogui_params(&self) -> String {
+        let params: Vec<String> = Config::keys().iter()
+            .filter(|&&key| !STARTUP_PARAMS.contains(&key))
+            .map(|&key| {
+                let t = self.config.gogui_type(key).unwrap();
+                let value = self.config.get(key).unwrap();
+                let value = if t == "[bool]" {
+                    String::from(if value == "true" { "1" } else { "0" })
+                } else {
+                    value
+                };
+                format!("{} {} {}", t, key, value)
+            })
+            .collect();
+        params.join("\n")
+    }
+
+    // Either alive, dead or unknown, or unsettled followed by the
+    // moves that kill and save the group.
+    fn group_status(&self, target: Coord) -> Command {
+        let board = self.game.board();
+        let mut solver = Solver::new(self.config.solver_node_limit);
+        match solver.analyse(&board, target) {
+            Ok((attack, defence)) => match (Status::combine(&attack, &defence), attack.best_move, defence.best_move) {
+                (Status::Unsettled, Some(a), Some(d)) => Command::GroupStatus(format!("unsettled {} {}", a.to_gtp(), d.to_gtp())),
+                (status, _, _) => Command::GroupStatus(format!("{}", status)),
+            },
+            Err(e) => Command::ErrorMessage(e)
+        }
+    }
+
+    // One line per chain. Chains are dead if the solver can kill them
+    // even if their owner moves first. All chains share the solver's
+    // node limit, the ones left when it is used up count as alive.
+    fn final_status_list(&self, status: &str) -> Command {
+        let board = self.game.board();
+        let score = Score::with_seki(&board);
+        let mut solver = Solver::new(self.config.solver_node_limit);
+        let mut chains = vec!();
+        for chain in board.chains().iter() {
+            let stone = chain.coords()[0];
+            let chain_status = if score.seki_stones().contains(&stone) {
+                "seki"
+            } else if solver.status(&board, stone) == Ok(Status::Dead) {
+                "dead"
+            } else {
+                "alive"
+            };
+            if chain_status == status {
+                let mut coords = chain.coords().clone();
+                coords.sort();
+                let vertices: Vec<String> = coords.iter().map(|c| c.to_gtp()).collect();
+                chains.push(vertices.join(" "));
+            }
         }
+        Command::FinalStatusList(chains.join("\n"))
     }
 
     fn measure_playout_speed(started_at: u64, playouts: usize, config: &Arc<Config>) {
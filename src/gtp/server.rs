@@ -0,0 +1,123 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use book::Book;
+use config::Config;
+use engine;
+use patterns::Matcher;
+use super::driver::Driver;
+
+use std::io;
+use std::io::BufReader;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// Without concurrent sessions a client that stops sending commands
+// would block everybody else, so its connection is closed after
+// being idle for this long.
+const IDLE_TIMEOUT_S: u64 = 600;
+
+// Serves GTP over TCP. Every connection is a separate game with its
+// own interpreter and engine.
+pub struct Server {
+    book: Option<Arc<Book>>,
+    config: Arc<Config>,
+    matcher: Arc<Matcher>,
+}
+
+impl Server {
+
+    pub fn new(config: Arc<Config>, matcher: Arc<Matcher>, book: Option<Arc<Book>>) -> Server {
+        Server {
+            book: book,
+            config: config,
+            matcher: matcher,
+        }
+    }
+
+    pub fn listen(&self, addr: &str) -> io::Result<()> {
+        let listener = try!(TcpListener::bind(addr));
+        if self.config.log {
+            log!("Listening for GTP connections on {}", try!(listener.local_addr()));
+        }
+        self.serve(listener)
+    }
+
+    // Unless concurrent sessions are turned on the next connection is
+    // only accepted once the current one is closed. A failed
+    // connection attempt doesn't stop the server.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log!("Can't accept a GTP connection: {}", e);
+                    continue;
+                }
+            };
+            if self.config.concurrent_sessions {
+                let config = self.config.clone();
+                let matcher = self.matcher.clone();
+                let book = self.book.clone();
+                thread::spawn(move || Server::session(config, matcher, book, stream));
+            } else {
+                if let Err(e) = stream.set_read_timeout(Some(Duration::from_secs(IDLE_TIMEOUT_S))) {
+                    log!("Can't set the read timeout of a GTP connection: {}", e);
+                    continue;
+                }
+                Server::session(self.config.clone(), self.matcher.clone(), self.book.clone(), stream);
+            }
+        }
+        Ok(())
+    }
+
+    fn session(config: Arc<Config>, matcher: Arc<Matcher>, book: Option<Arc<Book>>, stream: TcpStream) {
+        let peer = match stream.peer_addr() {
+            Ok(addr) => format!("{}", addr),
+            Err(_) => String::from("unknown peer"),
+        };
+        if config.log {
+            log!("Starting a GTP session with {}", peer);
+        }
+        let engine = match engine::factory(config.clone(), matcher) {
+            Ok(engine) => engine,
+            Err(e) => {
+                log!("Can't start a GTP session with {}: {}", peer, e);
+                return;
+            }
+        };
+        let result = stream.try_clone().and_then(|input| {
+            Driver::run(config.clone(), engine, book, BufReader::new(input), stream)
+        });
+        if config.log {
+            match result {
+                Ok(_) => log!("The GTP session with {} is over", peer),
+                Err(e) => log!("The GTP session with {} failed: {}", peer, e),
+            }
+        }
+    }
+
+}
@@ -23,21 +23,75 @@
 #![cfg(test)]
 
 
+pub use board::Black;
+pub use board::Pass;
+pub use board::White;
 pub use config::Config;
 pub use engine::UctEngine;
 pub use patterns::Matcher;
 pub use super::Command;
 pub use super::GTPInterpreter;
+pub use super::client::Client;
+pub use super::client::parse_response;
+pub use super::driver::Driver;
+pub use super::server::Server;
 
+pub use std::io::BufRead;
+pub use std::io::BufReader;
+pub use std::io::Write;
+pub use std::net::SocketAddr;
+pub use std::net::TcpListener;
+pub use std::net::TcpStream;
 pub use std::sync::Arc;
+pub use std::thread;
+
+// Runs a whole session and returns everything the engine answered.
+pub fn session(input: &str) -> String {
+    let config = Arc::new(Config::default());
+    let engine = Box::new(UctEngine::new(config.clone(), Arc::new(Matcher::new())).unwrap());
+    let mut output = vec!();
+    Driver::run(config, engine, None, input.as_bytes(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+pub fn start_server(concurrent_sessions: bool) -> SocketAddr {
+    let mut config = Config::default();
+    config.concurrent_sessions = concurrent_sessions;
+    let server = Server::new(Arc::new(config), Arc::new(Matcher::new()), None);
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || server.serve(listener));
+    addr
+}
+
+pub fn connect(addr: SocketAddr) -> (TcpStream, BufReader<TcpStream>) {
+    let stream = TcpStream::connect(addr).unwrap();
+    let reader = BufReader::new(stream.try_clone().unwrap());
+    (stream, reader)
+}
+
+// Sends the command and returns the response without the empty line
+// that ends it.
+pub fn send(stream: &mut TcpStream, reader: &mut BufReader<TcpStream>, command: &str) -> String {
+    write!(stream, "{}\n", command).unwrap();
+    let mut response = String::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line == "\n" || line.is_empty() {
+            return response;
+        }
+        response.push_str(&line);
+    }
+}
 
 describe! interpreter {
 
     before_each {
         let config = Arc::new(Config::default());
         let matcher = Arc::new(Matcher::new());
-        let engine = Box::new(UctEngine::new(config.clone(), matcher));
-        let mut interpreter = GTPInterpreter::new(config.clone(), engine);
+        let engine = Box::new(UctEngine::new(config.clone(), matcher).unwrap());
+        let mut interpreter = GTPInterpreter::new(config.clone(), engine, None);
     }
 
     it "empty string" {
@@ -96,7 +150,7 @@ describe! interpreter {
 
     it "no newline at end of list_commands" {
         let commands = interpreter.read("list_commands\n");
-        let expected = "boardsize\nclear_board\nfinal_score\ngenmove\nknown_command\nkomi\nlist_commands\nloadsgf\nname\nplay\nprotocol_version\nquit\nshowboard\ntime_left\ntime_settings\nversion";
+        let expected = "boardsize\nclear_board\nfinal_score\nfinal_status_list\ngenmove\nget_param\ngogui-analyze_commands\ngroup_status\nknown_command\nkomi\nlist_commands\nloadsgf\nname\nparam\nplay\nprotocol_version\nquit\nset_param\nshowboard\ntime_left\ntime_settings\nundo\nversion";
         match commands {
             Command::ListCommands(cs) => assert_eq!(expected, cs),
             _                         => panic!("wrong match")
@@ -105,10 +159,34 @@ describe! interpreter {
     }
 
     it "boardsize sets the correct size" {
-        assert_eq!(19, interpreter.game.size());
+        assert_eq!(19, interpreter.game.width());
         interpreter.read("boardsize 9\n");
         interpreter.quit();
-        assert_eq!(9, interpreter.game.size());
+        assert_eq!(9, interpreter.game.width());
+        assert_eq!(9, interpreter.game.height());
+    }
+
+    it "boardsize accepts sizes up to 25" {
+        interpreter.read("boardsize 25\n");
+        interpreter.quit();
+        assert_eq!(625, interpreter.game.board().vacant_point_count());
+    }
+
+    it "boardsize rejects sizes larger than 25" {
+        match interpreter.read("boardsize 26\n") {
+            Command::ErrorMessage(e) => assert_eq!("unacceptable size", e),
+            _                        => panic!("ErrorMessage expected!")
+        }
+        interpreter.quit();
+        assert_eq!(19, interpreter.game.width());
+    }
+
+    it "boardsize rejects a size of zero" {
+        match interpreter.read("boardsize 0\n") {
+            Command::ErrorMessage(e) => assert_eq!("unacceptable size", e),
+            _                        => panic!("ErrorMessage expected!")
+        }
+        interpreter.quit();
     }
 
     it "boardsize resets the board" {
@@ -163,4 +241,239 @@ describe! interpreter {
         interpreter.quit();
     }
 
+    it "undo takes back the last move" {
+        interpreter.read("play b a1\n");
+        interpreter.read("play w b1\n");
+        match interpreter.read("undo\n") {
+            Command::Undo => (),
+            _             => panic!("Undo expected!")
+        }
+        interpreter.quit();
+        assert_eq!(360, interpreter.game.board().vacant_point_count());
+    }
+
+    it "undo fails without moves" {
+        interpreter.read("play b a1\n");
+        interpreter.read("clear_board\n");
+        match interpreter.read("undo\n") {
+            Command::ErrorMessage(e) => assert_eq!("cannot undo", e),
+            _                        => panic!("ErrorMessage expected!")
+        }
+        interpreter.quit();
+    }
+
+    it "group_status reads out a group" {
+        interpreter.read("loadsgf fixtures/sgf/life-and-death/unsettled.sgf\n");
+        match interpreter.read("group_status d9\n") {
+            Command::GroupStatus(s) => assert_eq!("unsettled B9 B9", s),
+            _                       => panic!("GroupStatus expected!")
+        }
+        interpreter.quit();
+    }
+
+    it "group_status on an empty point" {
+        match interpreter.read("group_status a1\n") {
+            Command::ErrorMessage(e) => assert_eq!("There is no stone at A1", e),
+            _                        => panic!("ErrorMessage expected!")
+        }
+        interpreter.quit();
+    }
+
+    it "final_status_list reports the stones in seki" {
+        interpreter.read("loadsgf fixtures/sgf/semeai/seki.sgf\n");
+        match interpreter.read("final_status_list seki\n") {
+            Command::FinalStatusList(s) => {
+                let lines: Vec<&str> = s.lines().collect();
+                assert_eq!(2, lines.len());
+                assert!(lines.contains(&"B9 C9"));
+            },
+            _ => panic!("FinalStatusList expected!")
+        }
+        interpreter.quit();
+    }
+
+    it "final_status_list reports dead stones" {
+        interpreter.read("loadsgf fixtures/sgf/life-and-death/dead.sgf\n");
+        match interpreter.read("final_status_list dead\n") {
+            Command::FinalStatusList(s) => assert_eq!("A8 B8 C8 C9", s),
+            _                           => panic!("FinalStatusList expected!")
+        }
+        interpreter.quit();
+    }
+
+    it "final_status_list stops reading when the solver node limit is used up" {
+        interpreter.read("loadsgf fixtures/sgf/life-and-death/dead.sgf\n");
+        interpreter.read("set_param solver_node_limit 1\n");
+        match interpreter.read("final_status_list dead\n") {
+            Command::FinalStatusList(s) => assert_eq!("", s),
+            _                           => panic!("FinalStatusList expected!")
+        }
+        interpreter.quit();
+    }
+
+}
+
+describe! server {
+
+    it "answers commands over tcp" {
+        let addr = start_server(false);
+        let (mut stream, mut reader) = connect(addr);
+        assert_eq!("= Iomrascalai\n", send(&mut stream, &mut reader, "name"));
+        assert_eq!("= \n", send(&mut stream, &mut reader, "quit"));
+    }
+
+    it "starts a new game for every connection" {
+        let addr = start_server(false);
+        {
+            let (mut stream, mut reader) = connect(addr);
+            assert_eq!("= \n", send(&mut stream, &mut reader, "play b a1"));
+            send(&mut stream, &mut reader, "quit");
+        }
+        let (mut stream, mut reader) = connect(addr);
+        assert_eq!("= \n", send(&mut stream, &mut reader, "play b a1"));
+        send(&mut stream, &mut reader, "quit");
+    }
+
+    it "serves several connections at once if asked to" {
+        let addr = start_server(true);
+        let (mut first, mut first_reader) = connect(addr);
+        let (mut second, mut second_reader) = connect(addr);
+        assert_eq!("= 2\n", send(&mut second, &mut second_reader, "protocol_version"));
+        assert_eq!("= 2\n", send(&mut first, &mut first_reader, "protocol_version"));
+        send(&mut second, &mut second_reader, "quit");
+        send(&mut first, &mut first_reader, "quit");
+    }
+
+}
+
+describe! protocol {
+
+    it "repeats the command id in the response" {
+        assert_eq!("=1 Iomrascalai\n\n", session("1 name\n"));
+        assert_eq!("?2 unknown command\n\n", session("2 foo\n"));
+    }
+
+    it "answers without an id if the command has none" {
+        assert_eq!("= 2\n\n", session("protocol_version\n"));
+    }
+
+    it "ignores comments and empty lines" {
+        assert_eq!("= Iomrascalai\n\n", session("# just a comment\n\nname # the name\n"));
+    }
+
+    it "accepts tabs and repeated whitespace" {
+        assert_eq!("=3 \n\n", session("  3\tboardsize  \t 9 \n"));
+    }
+
+    it "removes control characters" {
+        assert_eq!("= Iomrascalai\n\n", session("na\x01me\r\n"));
+    }
+
+    it "reports a syntax error for bad arguments" {
+        assert_eq!("? syntax error\n\n", session("boardsize nine\n"));
+        assert_eq!("? syntax error\n\n", session("play x a1\n"));
+        assert_eq!("? syntax error\n\n", session("play b\n"));
+        assert_eq!("? syntax error\n\n", session("genmove\n"));
+    }
+
+    it "reports a syntax error for vertices that aren't on the board" {
+        assert_eq!("? syntax error\n\n", session("play b i5\n"));
+        assert_eq!("= \n\n? syntax error\n\n", session("boardsize 9\nplay b k5\n"));
+        assert_eq!("? syntax error\n\n", session("play b a20\n"));
+        assert_eq!("? syntax error\n\n", session("play b a0\n"));
+    }
+
+    it "reports illegal moves" {
+        assert_eq!("= \n\n? illegal move\n\n", session("play b a1\nplay w a1\n"));
+    }
+
+    it "reports that there is nothing to undo" {
+        assert_eq!("= \n\n= \n\n? cannot undo\n\n", session("play b a1\nundo\nundo\n"));
+    }
+
+    it "stops at the end of the input" {
+        assert_eq!("= \n\n", session("komi 5.5\n"));
+    }
+
+}
+
+describe! client {
+
+    it "reads successful responses" {
+        assert_eq!(Ok(String::from("D4")), parse_response("= D4"));
+        assert_eq!(Ok(String::from("")), parse_response("=12"));
+        assert_eq!(Ok(String::from("a\nb")), parse_response("=1 a\nb"));
+    }
+
+    it "turns failures into errors" {
+        assert_eq!(Err(String::from("illegal move")), parse_response("? illegal move"));
+        assert_eq!(Err(String::from("unknown command")), parse_response("?3 unknown command"));
+        assert!(parse_response("D4").is_err());
+    }
+
+    it "talks to a subprocess" {
+        let mut client = Client::spawn("sh fixtures/gtp/pass.sh", 5000).unwrap();
+        assert!(client.new_game(9, 6.5).is_ok());
+        assert_eq!(Ok(Pass(White)), client.genmove(White));
+        assert!(client.play(Pass(Black)).is_err());
+        client.quit();
+    }
+
+    it "gives up on engines that don't answer" {
+        let mut client = Client::spawn("sleep 10", 100).unwrap();
+        assert!(client.send("name").is_err());
+    }
+
+    it "notices when the engine exits" {
+        let mut client = Client::spawn("true", 5000).unwrap();
+        assert!(client.send("name").is_err());
+    }
+
+    it "fails to start a missing program" {
+        assert!(Client::spawn("does-not-exist-gtp", 100).is_err());
+    }
+
+}
+
+describe! params {
+
+    it "sets and gets parameters" {
+        assert_eq!("= \n\n= 40\n\n", session("set_param uct.priors.capture_many 40\nget_param uct.priors.capture_many\n"));
+    }
+
+    it "rejects unknown parameters and invalid values" {
+        assert_eq!("? Unknown key 'uct.capture_many'\n\n", session("get_param uct.capture_many\n"));
+        assert_eq!("? Invalid value 'many' for threads\n\n", session("set_param threads many\n"));
+        assert_eq!("? syntax error\n\n", session("set_param threads\n"));
+    }
+
+    it "validates the new configuration" {
+        assert_eq!("? '--threads' has to be at least 1\n\n= 1\n\n", session("set_param threads 0\nget_param threads\n"));
+    }
+
+    it "doesn't change the parameters that are only read at startup" {
+        assert_eq!("? book_file can only be set at startup\n\n", session("set_param book_file book.txt\n"));
+    }
+
+    it "lists the parameters for gogui" {
+        let output = session("param\n");
+        assert!(output.contains("\n[bool] uct.tuned 1\n"));
+        assert!(output.contains("\n[list/cascade/light/random/softmax] playout.policy cascade\n"));
+        assert!(output.contains("\n[string] uct.priors.semeai 20\n"));
+        assert!(!output.contains("book_file"));
+    }
+
+    it "sets booleans the gogui way" {
+        assert_eq!("= \n\n= false\n\n", session("param uct.tuned 0\nget_param uct.tuned\n"));
+    }
+
+    it "tells gogui about the parameter command" {
+        assert_eq!("= true\n\n= param/Parameters/param\n\n", session("known_command gogui-analyze_commands\ngogui-analyze_commands\n"));
+    }
+
+    it "changes the engine between moves" {
+        let output = session("set_param uct.max_playouts 50\nboardsize 9\ngenmove b\n");
+        assert!(output.starts_with("= \n\n= \n\n= "));
+    }
+
 }
@@ -0,0 +1,120 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2014 Urban Hafner, Thomas Poinsot                          *
+ * Copyright 2015 Urban Hafner, Thomas Poinsot, Igor Polyakov           *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+//! Iomrascálaí is an AI for the game of Go. Apart from the GTP
+//! binary the crate can be used as a library to set up games, play
+//! moves, score positions, read SGF files and run the engine. A
+//! subset of this is available to C, see include/iomrascalai.h.
+//!
+//! ```no_run
+//! extern crate iomrascalai;
+//!
+//! use iomrascalai::board::Black;
+//! use iomrascalai::board::Play;
+//! use iomrascalai::config::Config;
+//! use iomrascalai::engine::Budget;
+//! use iomrascalai::engine::search;
+//! use iomrascalai::game::Game;
+//! use iomrascalai::patterns::Matcher;
+//! use iomrascalai::ruleset::KgsChinese;
+//! use iomrascalai::sgf::parser::Parser;
+//!
+//! use std::path::Path;
+//! use std::sync::Arc;
+//!
+//! fn main() {
+//!     let game = Game::new(9, 6.5, KgsChinese);
+//!     let game = game.play(Play(Black, 5, 5)).unwrap();
+//!     println!("{}", game.score());
+//!
+//!     let game = Parser::from_path(Path::new("game.sgf")).unwrap().game().unwrap();
+//!     let config = Arc::new(Config::default());
+//!     let matcher = Arc::new(Matcher::new());
+//!     let analysis = search(config, matcher, &game, Budget::Playouts(10000)).unwrap();
+//!     println!("{} after {} playouts", analysis.best_move.to_gtp(), analysis.playouts);
+//! }
+//! ```
+
+#![feature(core)]
+#![feature(mpsc_select)]
+#![feature(plugin)]
+#![feature(slice_splits)]
+#![feature(test)]
+#![feature(vec_push_all)]
+#![plugin(regex_macros)]
+#![plugin(stainless)]
+
+#[cfg(test)]
+extern crate hamcrest;
+
+extern crate core;
+#[macro_use] extern crate enum_primitive;
+extern crate getopts;
+extern crate num;
+extern crate quicksort;
+extern crate rand;
+extern crate regex;
+#[no_link] extern crate regex_macros;
+extern crate smallvec;
+extern crate test;
+extern crate thread_scoped;
+extern crate time;
+#[macro_use(strenum)] extern crate strenum;
+
+#[macro_export]
+macro_rules! log(
+    ($($arg:tt)*) => (
+        match writeln!(&mut ::std::io::stderr(), $($arg)* ) {
+            Ok(_) => {},
+            Err(x) => panic!("Unable to write to stderr: {}", x),
+        }
+    )
+);
+
+pub mod board;
+pub mod book;
+pub mod config;
+pub mod engine;
+pub mod game;
+pub mod patterns;
+pub mod ruleset;
+pub mod score;
+pub mod sgf;
+pub mod timer;
+
+// The C API, declared in include/iomrascalai.h.
+#[doc(hidden)]
+pub mod ffi;
+
+// Only public for the iomrascalai binary.
+#[doc(hidden)]
+pub mod gtp;
+#[doc(hidden)]
+pub mod regress;
+#[doc(hidden)]
+pub mod selfplay;
+#[doc(hidden)]
+pub mod tuning;
+
+mod playout;
+mod solver;
+mod version;
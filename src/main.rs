@@ -19,63 +19,36 @@
  * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
  *                                                                      *
  ************************************************************************/
-#![feature(core)]
-#![feature(mpsc_select)]
-#![feature(plugin)]
 #![feature(slice_splits)]
-#![feature(test)]
-#![feature(vec_push_all)]
-#![plugin(regex_macros)]
-#![plugin(stainless)]
 
-#[cfg(test)]
-extern crate hamcrest;
-
-extern crate core;
-#[macro_use] extern crate enum_primitive;
 extern crate getopts;
-extern crate num;
-extern crate quicksort;
-extern crate rand;
-extern crate regex;
-#[no_link] extern crate regex_macros;
-extern crate smallvec;
-extern crate test;
-extern crate thread_scoped;
-extern crate time;
-#[macro_use(strenum)] extern crate strenum;
-
-use config::Config;
-use gtp::driver::Driver;
-use patterns::Matcher;
+#[macro_use] extern crate iomrascalai;
+
+use iomrascalai::board::Board;
+use iomrascalai::book::Book;
+use iomrascalai::config::Config;
+use iomrascalai::engine;
+use iomrascalai::engine::Budget;
+use iomrascalai::gtp::driver::Driver;
+use iomrascalai::gtp::server::Server;
+use iomrascalai::patterns::Matcher;
+use iomrascalai::patterns::Trainer;
+use iomrascalai::regress::Regression;
+use iomrascalai::regress::report;
+use iomrascalai::selfplay::Match;
+use iomrascalai::selfplay::Player;
+use iomrascalai::tuning::Spsa;
+use iomrascalai::tuning::default_parameters;
+use iomrascalai::tuning::parameter;
 
+use getopts::Matches;
 use getopts::Options;
 use std::sync::Arc;
 use std::env::args;
 use std::io::Write;
+use std::path::Path;
 use std::process::exit;
-
-macro_rules! log(
-    ($($arg:tt)*) => (
-        match writeln!(&mut ::std::io::stderr(), $($arg)* ) {
-            Ok(_) => {},
-            Err(x) => panic!("Unable to write to stderr: {}", x),
-        }
-    )
-);
-
-mod board;
-mod config;
-mod engine;
-mod game;
-mod gtp;
-mod patterns;
-mod playout;
-mod ruleset;
-mod score;
-mod sgf;
-mod timer;
-mod version;
+use std::str::FromStr;
 
 pub fn main() {
     let mut config = Config::default();
@@ -83,6 +56,19 @@ pub fn main() {
     let args : Vec<String> = args().collect();
 
     opts.optopt("e", "engine", "Select an engine (defaults to uct)", "amaf|mc|random|uct");
+    opts.optopt("", "listen", "Serve GTP over TCP instead of stdin and stdout", "ADDR:PORT");
+    opts.optopt("", "games", "Number of games to play in a match (defaults to 100) or per tuning iteration (defaults to 2)", "NUM");
+    opts.optopt("", "iterations", "Number of tuning iterations (defaults to 100)", "NUM");
+    opts.optopt("", "opponent", "Options for the second engine in a match", "OPTIONS");
+    opts.optopt("", "opponent-command", "Play a match against an external GTP engine instead", "COMMAND");
+    opts.optopt("", "opponent-timeout", "Time an external engine has to answer a command (defaults to 60000)", "MS");
+    opts.optopt("", "move-time", "Time per move in a match (defaults to 1000)", "MS");
+    opts.optopt("", "move-playouts", "Playouts per move in a match instead of a time limit (defaults to 1000 when tuning)", "NUM");
+    opts.optopt("", "board-size", "Board size for a match (defaults to 9)", "NUM");
+    opts.optopt("", "komi", "Komi for a match (defaults to 6.5)", "NUM");
+    opts.optopt("", "sgf-dir", "Save the games of a match to DIR", "DIR");
+    opts.optopt("", "build-book", "Build an opening book from the SGF files in DIR and print it", "DIR");
+    opts.optopt("", "train-patterns", "Learn the pattern weights from the SGF files in DIR and print them", "DIR");
 
     config.setup(&mut opts);
 
@@ -111,14 +97,210 @@ pub fn main() {
         }
     }
 
+    if let Some(dir) = matches.opt_str("build-book") {
+        let mut book = Book::new();
+        match book.add_directory(Path::new(&dir)) {
+            Ok(games) => log!("Read {} games with {} positions", games, book.len()),
+            Err(s) => {
+                println!("{}", s);
+                exit(1);
+            }
+        }
+        print!("{}", book.to_string());
+        exit(0);
+    }
+
+    if let Some(dir) = matches.opt_str("train-patterns") {
+        let mut trainer = Trainer::new();
+        match trainer.add_directory(Path::new(&dir)) {
+            Ok(games) => log!("Read {} games with {} positions", games, trainer.position_count()),
+            Err(s) => {
+                println!("{}", s);
+                exit(1);
+            }
+        }
+        print!("{}", trainer.weights(&trainer.train()));
+        exit(0);
+    }
+
     let config = Arc::new(config);
     // Instantiate only one matcher as it does a lot of computation
     // during setup.
-    let matcher = Arc::new(Matcher::new());
+    let mut matcher = match config.patterns_file {
+        Some(ref file) => match Matcher::from_file(Path::new(file)) {
+            Ok(matcher) => matcher,
+            Err(s) => {
+                println!("{}", s);
+                exit(1);
+            }
+        },
+        None => Matcher::new()
+    };
+    if let Some(ref file) = config.large_patterns_file {
+        if let Err(s) = matcher.load_large_patterns(Path::new(file)) {
+            println!("{}", s);
+            exit(1);
+        }
+    }
+    let matcher = Arc::new(matcher);
+
+    if matches.free.len() > 0 && matches.free[0] == "regress" {
+        let regression = Regression::new(config.clone(), matcher.clone());
+        let mut results = vec!();
+        for file in matches.free[1..].iter() {
+            match regression.run_file(Path::new(file)) {
+                Ok(r) => results.extend(r),
+                Err(s) => {
+                    println!("{}", s);
+                    exit(1);
+                }
+            }
+        }
+        println!("{}", report(&results));
+        let failed = results.iter().any(|result| result.outcome.is_failure());
+        exit(if failed { 1 } else { 0 });
+    }
 
-    let engine = engine::factory(config.clone(), matcher);
+    if matches.free.len() > 0 && matches.free[0] == "match" {
+        match run_match(&matches, config.clone(), matcher.clone()) {
+            Ok(_) => exit(0),
+            Err(s) => {
+                println!("{}", s);
+                exit(1);
+            }
+        }
+    }
+
+    if matches.free.len() > 0 && matches.free[0] == "tune" {
+        match run_tuning(&matches, config.clone(), matcher.clone()) {
+            Ok(_) => exit(0),
+            Err(s) => {
+                println!("{}", s);
+                exit(1);
+            }
+        }
+    }
 
     log!("Current configuration: {:#?}", config);
 
-    Driver::new(config, engine);
+    let book = match config.book_file {
+        Some(ref file) if config.use_book => match Book::from_file(Path::new(file)) {
+            Ok(book) => Some(Arc::new(book)),
+            Err(s) => {
+                println!("{}", s);
+                exit(1);
+            }
+        },
+        _ => None
+    };
+
+    match matches.opt_str("listen") {
+        Some(addr) => {
+            let server = Server::new(config, matcher, book);
+            if let Err(e) = server.listen(&addr) {
+                println!("{}", e);
+                exit(1);
+            }
+        },
+        None => {
+            match engine::factory(config.clone(), matcher) {
+                Ok(engine) => Driver::new(config, engine, book),
+                Err(s) => {
+                    println!("{}", s);
+                    exit(1);
+                }
+            }
+        }
+    }
+}
+
+fn run_match(matches: &Matches, config: Arc<Config>, matcher: Arc<Matcher>) -> Result<(), String> {
+    let games = try!(parse_opt(matches, "games", 100));
+    let size = try!(parse_opt(matches, "board-size", 9));
+    let komi = try!(parse_opt(matches, "komi", 6.5));
+    let budget = match matches.opt_str("move-playouts") {
+        Some(_) => match try!(parse_opt(matches, "move-playouts", 0)) {
+            0 => return Err(String::from("--move-playouts has to be larger than 0")),
+            playouts => Budget::Playouts(playouts)
+        },
+        None => Budget::Time(try!(parse_opt(matches, "move-time", 1000)))
+    };
+    if !Board::is_valid_size(size, size) {
+        return Err(format!("Unsupported board size: {}", size));
+    }
+    let opponent = match matches.opt_str("opponent-command") {
+        Some(command) => Player::gtp("B", &command),
+        None => {
+            let opponent = try!(opponent_config(&matches.opt_str("opponent").unwrap_or(String::new())));
+            Player::new("B", Arc::new(opponent))
+        }
+    };
+    let players = [Player::new("A", config), opponent];
+    let mut selfplay = Match::new(size, komi, budget, matcher);
+    selfplay.set_timeout(try!(parse_opt(matches, "opponent-timeout", 60000)));
+    if let Some(dir) = matches.opt_str("sgf-dir") {
+        selfplay.save_sgfs(Path::new(&dir));
+    }
+    let result = try!(selfplay.play(&players, games, |i, game| {
+        println!("Game {}: {} (B) vs {} (W): {}", i, players[game.black].name, players[1 - game.black].name, game.result);
+    }));
+    println!("A vs B: {}", result);
+    Ok(())
+}
+
+// Prints the values after every iteration and the tuned values in the
+// format of the configuration file at the end.
+fn run_tuning(matches: &Matches, config: Arc<Config>, matcher: Arc<Matcher>) -> Result<(), String> {
+    let iterations = try!(parse_opt(matches, "iterations", 100));
+    let games = try!(parse_opt(matches, "games", 2));
+    let size = try!(parse_opt(matches, "board-size", 9));
+    let komi = try!(parse_opt(matches, "komi", 6.5));
+    let playouts = try!(parse_opt(matches, "move-playouts", 1000));
+    if playouts == 0 {
+        return Err(String::from("--move-playouts has to be larger than 0"));
+    }
+    if games == 0 || games % 2 != 0 {
+        return Err(String::from("--games has to be an even number when tuning"));
+    }
+    if !Board::is_valid_size(size, size) {
+        return Err(format!("Unsupported board size: {}", size));
+    }
+    let parameters = if matches.free.len() > 1 {
+        try!(matches.free[1..].iter()
+             .map(|name| parameter(name).ok_or(format!("Unknown parameter: {}", name)))
+             .collect())
+    } else {
+        default_parameters(&config)
+    };
+    let mut spsa = Spsa::new(config, matcher, parameters, size, komi, Budget::Playouts(playouts));
+    spsa.set_games(games);
+    let names: Vec<&str> = spsa.parameters().iter().map(|p| p.name).collect();
+    println!("iteration\twins\tlosses\tdraws\t{}", names.join("\t"));
+    let values = try!(spsa.run(iterations, |iteration| {
+        let values: Vec<String> = iteration.values.iter().map(|v| format!("{:.3}", v)).collect();
+        println!("{}\t{}\t{}\t{}\t{}", iteration.iteration, iteration.result.wins,
+                 iteration.result.losses, iteration.result.draws, values.join("\t"));
+    }));
+    print!("\n# Tuned values\n{}", spsa.config_file(&values));
+    Ok(())
+}
+
+fn parse_opt<T: FromStr>(matches: &Matches, name: &str, default: T) -> Result<T, String> {
+    match matches.opt_str(name) {
+        Some(s) => s.parse().map_err(|_| format!("Unknown value ({}) as argument to --{}", s, name)),
+        None => Ok(default)
+    }
+}
+
+// The second engine is configured with the same options as the
+// first one, given as a single string.
+fn opponent_config(options: &str) -> Result<Config, String> {
+    let mut config = Config::default();
+    let mut opts = Options::new();
+    config.setup(&mut opts);
+    let mut args = vec!(String::from("opponent"));
+    args.extend(options.split_whitespace().map(String::from));
+    let matches = try!(opts.parse(&args[1..]).map_err(|f| f.to_string()));
+    try!(config.set_from_opts(&matches, &opts, &args));
+    Ok(config)
 }
@@ -0,0 +1,166 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Move;
+use board::White;
+
+use std::cmp;
+
+mod test;
+
+// Only moves near the previous move (see is_near) get a distance
+// feature, as the softmax policy only applies its gamma to these.
+// None of them is farther away than this.
+pub const MAX_DISTANCE: u8 = 6;
+
+// The features that describe a move when learning move weights from
+// game records. Every move has at most one feature from each group.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Feature {
+    // The 3x3 neighbourhood relative to the player to move (see
+    // relative_pattern_code), identified by the smallest pattern code
+    // among all its rotations and reflections.
+    Pattern(u16),
+    Capture,
+    Atari,
+    SelfAtari,
+    Distance(u8),
+}
+
+pub const GROUPS: usize = 5;
+
+impl Feature {
+
+    pub fn group(&self) -> usize {
+        match *self {
+            Feature::Pattern(_)  => 0,
+            Feature::Capture     => 1,
+            Feature::Atari       => 2,
+            Feature::SelfAtari   => 3,
+            Feature::Distance(_) => 4,
+        }
+    }
+
+    // The name used in the weights file. Patterns are written as
+    // patterns instead.
+    pub fn name(&self) -> Option<String> {
+        match *self {
+            Feature::Pattern(_)  => None,
+            Feature::Capture     => Some(String::from("capture")),
+            Feature::Atari       => Some(String::from("atari")),
+            Feature::SelfAtari   => Some(String::from("self-atari")),
+            Feature::Distance(d) => Some(format!("distance-{}", d)),
+        }
+    }
+
+}
+
+pub fn features(board: &Board, m: &Move, last_move: Option<Coord>) -> Vec<Feature> {
+    let c = m.coord();
+    let code = relative_pattern_code(board.pattern_code(&c), *m.color());
+    let mut features = vec!(Feature::Pattern(canonical_pattern_code(code)));
+    if board.removes_multiple_enemy_neighbouring_stones(*m).0 {
+        features.push(Feature::Capture);
+    }
+    if is_atari(board, m) {
+        features.push(Feature::Atari);
+    }
+    if !board.is_not_self_atari(m) {
+        features.push(Feature::SelfAtari);
+    }
+    if let Some(last) = last_move {
+        if is_near(&c, &last) {
+            features.push(Feature::Distance(distance(&c, &last)));
+        }
+    }
+    features
+}
+
+fn is_atari(board: &Board, m: &Move) -> bool {
+    let enemy = m.color().opposite();
+    board.neighbours(m.coord()).iter()
+        .filter(|c| board.color(c) == enemy)
+        .map(|&c| board.get_chain(c).unwrap())
+        .any(|chain| chain.liberties().len() == 2)
+}
+
+// The distance measure from Coulom's "Computing Elo Ratings of Move
+// Patterns in the Game of Go": dx + dy + max(dx, dy). Adjacent points
+// have a distance of 2, diagonal ones of 3.
+pub fn distance(a: &Coord, b: &Coord) -> u8 {
+    let dx = (a.col as i16 - b.col as i16).abs() as u8;
+    let dy = (a.row as i16 - b.row as i16).abs() as u8;
+    dx.saturating_add(dy).saturating_add(cmp::max(dx, dy))
+}
+
+// Within a manhattan distance of three, the points of
+// Coord::manhattan_distance_three_neighbours.
+pub fn is_near(a: &Coord, b: &Coord) -> bool {
+    let dx = (a.col as i16 - b.col as i16).abs();
+    let dy = (a.row as i16 - b.row as i16).abs();
+    dx + dy <= 3
+}
+
+// Swaps the colors of the stones for white, so that the stones of
+// the player to move are always black (the X of the patterns files)
+// and the opponent's are white.
+pub fn relative_pattern_code(code: u16, color: Color) -> u16 {
+    if color != White {
+        return code;
+    }
+    (0..8).fold(0, |swapped, i| {
+        let value = match (code >> (2 * i)) & 3 {
+            1 => 2,
+            2 => 1,
+            v => v,
+        };
+        swapped | (value << (2 * i))
+    })
+}
+
+pub fn canonical_pattern_code(code: u16) -> u16 {
+    *pattern_code_symmetries(code).iter().min().unwrap()
+}
+
+// All distinct codes that can be reached by rotating and mirroring.
+// The colors are left alone, as the own and the opponent's stones
+// play different roles. The neighbours are stored clockwise starting
+// in the north west, so a rotation by 90 degrees shifts them by two
+// and mirroring reverses their order.
+pub fn pattern_code_symmetries(code: u16) -> Vec<u16> {
+    let mut codes = vec!();
+    for shift in 0..4 {
+        for &mirror in [false, true].iter() {
+            let mut transformed = 0;
+            for i in 0..8 {
+                let from = if mirror { (8 + 2 * shift - i) % 8 } else { (i + 2 * shift) % 8 };
+                transformed |= ((code >> (2 * from)) & 3) << (2 * i);
+            }
+            if !codes.contains(&transformed) {
+                codes.push(transformed);
+            }
+        }
+    }
+    codes
+}
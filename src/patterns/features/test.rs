@@ -0,0 +1,142 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Coord;
+use board::Play;
+use board::White;
+use sgf::Parser;
+use super::Feature;
+use super::MAX_DISTANCE;
+use super::canonical_pattern_code;
+use super::distance;
+use super::features;
+use super::is_near;
+use super::pattern_code_symmetries;
+use super::relative_pattern_code;
+
+use std::path::Path;
+
+// Neighbours are stored clockwise starting in the north west with
+// two bits each (1 = black, 2 = white).
+const BLACK_NORTH: u16 = 1 << 2;
+const BLACK_EAST: u16 = 1 << 6;
+const WHITE_NORTH: u16 = 2 << 2;
+const BLACK_NORTH_WEST: u16 = 1;
+
+#[test]
+fn rotations_have_the_same_canonical_code() {
+    let code = canonical_pattern_code(BLACK_NORTH);
+    assert_eq!(code, canonical_pattern_code(BLACK_EAST));
+    assert!(code != canonical_pattern_code(BLACK_NORTH_WEST));
+}
+
+#[test]
+fn color_swaps_have_different_canonical_codes() {
+    assert!(canonical_pattern_code(BLACK_NORTH) != canonical_pattern_code(WHITE_NORTH));
+}
+
+#[test]
+fn pattern_codes_are_relative_to_the_player_to_move() {
+    assert_eq!(BLACK_NORTH, relative_pattern_code(BLACK_NORTH, Black));
+    assert_eq!(WHITE_NORTH, relative_pattern_code(BLACK_NORTH, White));
+    assert_eq!(3, relative_pattern_code(3, White));
+}
+
+#[test]
+fn the_empty_neighbourhood_has_no_other_symmetries() {
+    assert_eq!(vec!(0), pattern_code_symmetries(0));
+}
+
+#[test]
+fn a_single_stone_has_eight_symmetries() {
+    let symmetries = pattern_code_symmetries(BLACK_NORTH_WEST);
+    assert_eq!(8, symmetries.len());
+    assert!(symmetries.contains(&BLACK_NORTH_WEST));
+}
+
+#[test]
+fn distance_counts_the_longer_side_twice() {
+    let c = Coord::new(5, 5);
+    assert_eq!(2, distance(&c, &Coord::new(5, 6)));
+    assert_eq!(3, distance(&c, &Coord::new(6, 6)));
+    assert_eq!(4, distance(&c, &Coord::new(5, 7)));
+    assert_eq!(5, distance(&c, &Coord::new(6, 7)));
+}
+
+#[test]
+fn features_of_a_capturing_move() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/hypothetical-plays.sgf")).unwrap();
+    let board = parser.game().unwrap().board();
+    let m = Play(Black, 4, 19);
+    let features = features(&board, &m, Some(Coord::new(4, 18)));
+    assert_eq!(Feature::Pattern(canonical_pattern_code(board.pattern_code(&m.coord()))), features[0]);
+    assert!(features.contains(&Feature::Capture));
+    assert!(features.contains(&Feature::Distance(2)));
+}
+
+#[test]
+fn the_pattern_feature_is_relative_to_the_player() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/hypothetical-plays.sgf")).unwrap();
+    let board = parser.game().unwrap().board();
+    let c = Coord::new(4, 19);
+    let code = relative_pattern_code(board.pattern_code(&c), White);
+    assert_eq!(Feature::Pattern(canonical_pattern_code(code)), features(&board, &Play(White, 4, 19), None)[0]);
+}
+
+#[test]
+fn no_distance_feature_for_far_away_moves() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/hypothetical-plays.sgf")).unwrap();
+    let board = parser.game().unwrap().board();
+    let features = features(&board, &Play(Black, 4, 19), Some(Coord::new(19, 1)));
+    assert!(features.iter().all(|f| f.group() != Feature::Distance(2).group()));
+}
+
+#[test]
+fn only_moves_within_a_manhattan_distance_of_three_have_a_distance_feature() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/hypothetical-plays.sgf")).unwrap();
+    let board = parser.game().unwrap().board();
+    let m = Play(Black, 4, 19);
+    assert!(features(&board, &m, Some(Coord::new(4, 16))).contains(&Feature::Distance(6)));
+    let features = features(&board, &m, Some(Coord::new(6, 17)));
+    assert!(features.iter().all(|f| f.group() != Feature::Distance(6).group()));
+}
+
+#[test]
+fn is_near_matches_the_manhattan_distance_three_neighbours() {
+    let c = Coord::new(5, 5);
+    for n in Coord::for_board_size(9, 9) {
+        let near = c.manhattan_distance_three_neighbours(9, 9).contains(&n);
+        assert_eq!(near, n != c && is_near(&c, &n));
+        if near {
+            assert!(distance(&c, &n) <= MAX_DISTANCE);
+        }
+    }
+}
+
+#[test]
+fn only_patterns_have_no_name() {
+    assert_eq!(None, Feature::Pattern(0).name());
+    assert_eq!(Some(String::from("self-atari")), Feature::SelfAtari.name());
+    assert_eq!(Some(String::from("distance-3")), Feature::Distance(3).name());
+}
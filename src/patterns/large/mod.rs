@@ -0,0 +1,208 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Coord;
+use board::Empty;
+use board::Move;
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand::XorShiftRng;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+mod test;
+
+pub const MIN_RADIUS: usize = 2;
+pub const MAX_RADIUS: usize = 4;
+
+const EMPTY: usize = 0;
+const OWN: usize = 1;
+const OPPONENT: usize = 2;
+const OFF_BOARD: usize = 3;
+
+// Patterns that cover the diamond shaped area (all points within the
+// given manhattan distance) around a move. As they don't contain
+// wildcards they can be looked up with a Zobrist hash of the area.
+// All rotations and reflections of a pattern are added to the table
+// when loading it. The colors are relative to the player to move.
+pub struct LargePatterns {
+    // The points around the move, sorted by their distance to it.
+    offsets: Vec<(i8, i8)>,
+    // The number of offsets within each radius.
+    counts: Vec<usize>,
+    keys: Vec<[u64; 4]>,
+    radius_keys: Vec<u64>,
+    weights: HashMap<u64, f32>,
+}
+
+impl LargePatterns {
+
+    pub fn new() -> LargePatterns {
+        let r = MAX_RADIUS as i8;
+        let mut offsets = vec!();
+        for distance in 1..r+1 {
+            for dy in -r..r+1 {
+                for dx in -r..r+1 {
+                    if dx.abs() + dy.abs() == distance {
+                        offsets.push((dx, dy));
+                    }
+                }
+            }
+        }
+        let counts = (0..MAX_RADIUS+1)
+            .map(|radius| offsets.iter().filter(|&&(dx, dy)| (dx.abs() + dy.abs()) as usize <= radius).count())
+            .collect();
+        // The keys have to be the same every time as the patterns are
+        // loaded as text.
+        let mut rng: XorShiftRng = SeedableRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+        let keys = offsets.iter().map(|_| [rng.gen(), rng.gen(), rng.gen(), rng.gen()]).collect();
+        let radius_keys = (0..MAX_RADIUS+1).map(|_| rng.gen()).collect();
+        LargePatterns {
+            offsets: offsets,
+            counts: counts,
+            keys: keys,
+            radius_keys: radius_keys,
+            weights: HashMap::new(),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<LargePatterns, String> {
+        let mut contents = String::new();
+        match File::open(path).and_then(|mut file| file.read_to_string(&mut contents)) {
+            Ok(_) => Self::parse(&contents),
+            Err(e) => Err(format!("Can't read {}: {}", path.display(), e))
+        }
+    }
+
+    // Each pattern starts with a line "large RADIUS WEIGHT" followed
+    // by 2 * RADIUS + 1 rows with the move (*) in the middle. X is
+    // the player to move, O the opponent, . an empty point and # a
+    // point off the board. Characters outside of the diamond are
+    // ignored. Lines starting with # are comments when they're not
+    // part of a pattern.
+    //
+    //     large 2 3.5
+    //       O
+    //      .X.
+    //     ..*..
+    //      ...
+    //       #
+    pub fn parse(contents: &str) -> Result<LargePatterns, String> {
+        let mut patterns = Self::new();
+        let mut lines = contents.lines().enumerate();
+        while let Some((n, line)) = lines.next() {
+            let trimmed = line.trim();
+            if trimmed.len() == 0 || trimmed.starts_with("#") {
+                continue;
+            }
+            let words: Vec<&str> = trimmed.split_whitespace().collect();
+            let (radius, weight) = match (words.get(0), words.get(1).map(|w| w.parse::<usize>()), words.get(2).map(|w| w.parse::<f32>())) {
+                (Some(&"large"), Some(Ok(radius)), Some(Ok(weight))) if radius >= MIN_RADIUS && radius <= MAX_RADIUS => (radius, weight),
+                _ => return Err(format!("Line {}: expected 'large RADIUS WEIGHT' with a radius from {} to {}", n+1, MIN_RADIUS, MAX_RADIUS))
+            };
+            let mut rows = vec!();
+            for _ in 0..2*radius+1 {
+                match lines.next() {
+                    Some((_, row)) => rows.push(row.chars().collect::<Vec<char>>()),
+                    None => return Err(format!("Line {}: the pattern needs {} rows", n+1, 2*radius+1))
+                }
+            }
+            let mut states = vec!();
+            for &(dx, dy) in patterns.offsets[0..patterns.counts[radius]].iter() {
+                let row = (radius as i8 - dy) as usize;
+                let col = (radius as i8 + dx) as usize;
+                let state = match rows[row].get(col) {
+                    Some(&'.') => EMPTY,
+                    Some(&'X') => OWN,
+                    Some(&'O') => OPPONENT,
+                    Some(&'#') => OFF_BOARD,
+                    _ => return Err(format!("Line {}: missing or unknown point at column {}", n+2+row, col+1))
+                };
+                states.push(state);
+            }
+            patterns.add(radius, &states, weight);
+        }
+        Ok(patterns)
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    fn add(&mut self, radius: usize, states: &Vec<usize>, weight: f32) {
+        for rotation in 0..4 {
+            for &mirror in [false, true].iter() {
+                let mut hash = self.radius_keys[radius];
+                for (i, &state) in states.iter().enumerate() {
+                    let (dx, dy) = Self::transform(self.offsets[i], rotation, mirror);
+                    let index = self.offsets.iter().position(|&o| o == (dx, dy)).unwrap();
+                    hash ^= self.keys[index][state];
+                }
+                self.weights.insert(hash, weight);
+            }
+        }
+    }
+
+    fn transform((dx, dy): (i8, i8), rotation: usize, mirror: bool) -> (i8, i8) {
+        let (mut x, mut y) = if mirror { (-dx, dy) } else { (dx, dy) };
+        for _ in 0..rotation {
+            let rotated = (-y, x);
+            x = rotated.0;
+            y = rotated.1;
+        }
+        (x, y)
+    }
+
+    // The weight of the largest pattern that matches around the move.
+    pub fn weight(&self, board: &Board, m: &Move) -> Option<f32> {
+        if self.weights.len() == 0 || m.is_pass() || m.is_resign() {
+            return None;
+        }
+        let c = m.coord();
+        let own = *m.color();
+        let mut hash = 0;
+        let mut found = None;
+        for (i, &(dx, dy)) in self.offsets.iter().enumerate() {
+            let col = c.col as i16 + dx as i16;
+            let row = c.row as i16 + dy as i16;
+            let state = if col < 1 || row < 1 || col > board.width() as i16 || row > board.height() as i16 {
+                OFF_BOARD
+            } else {
+                let color = board.color(&Coord::new(col as u8, row as u8));
+                if color == Empty { EMPTY } else if color == own { OWN } else { OPPONENT }
+            };
+            hash ^= self.keys[i][state];
+            for radius in MIN_RADIUS..MAX_RADIUS+1 {
+                if self.counts[radius] == i + 1 {
+                    if let Some(&weight) = self.weights.get(&(hash ^ self.radius_keys[radius])) {
+                        found = Some(weight);
+                    }
+                }
+            }
+        }
+        found
+    }
+
+}
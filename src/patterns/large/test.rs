@@ -0,0 +1,100 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Pass;
+use board::Play;
+use board::White;
+use ruleset::KgsChinese;
+use super::LargePatterns;
+
+use std::path::Path;
+
+fn patterns() -> LargePatterns {
+    LargePatterns::from_file(Path::new("fixtures/patterns/large.txt")).unwrap()
+}
+
+fn board() -> Board {
+    let mut board = Board::new(9, 6.5, KgsChinese);
+    board.play(Play(Black, 5, 6)).unwrap();
+    board.play(Play(White, 5, 7)).unwrap();
+    board
+}
+
+#[test]
+fn loads_all_rotations_and_reflections() {
+    // Both patterns are symmetric along one axis, so only 4 of their
+    // 8 variations are distinct.
+    assert_eq!(4 + 4, patterns().len());
+}
+
+#[test]
+fn matches_a_pattern() {
+    assert_eq!(Some(3.5), patterns().weight(&board(), &Play(Black, 5, 5)));
+}
+
+#[test]
+fn matches_a_rotated_pattern() {
+    let mut board = Board::new(9, 6.5, KgsChinese);
+    board.play(Play(Black, 6, 5)).unwrap();
+    board.play(Play(White, 7, 5)).unwrap();
+    assert_eq!(Some(3.5), patterns().weight(&board, &Play(Black, 5, 5)));
+}
+
+#[test]
+fn colors_are_relative_to_the_player() {
+    assert_eq!(None, patterns().weight(&board(), &Play(White, 5, 5)));
+}
+
+#[test]
+fn matches_points_off_the_board() {
+    let board = Board::new(9, 6.5, KgsChinese);
+    assert_eq!(Some(2.0), patterns().weight(&board, &Play(Black, 1, 1)));
+    assert_eq!(Some(2.0), patterns().weight(&board, &Play(Black, 9, 9)));
+    assert_eq!(None, patterns().weight(&board, &Play(Black, 2, 1)));
+}
+
+#[test]
+fn prefers_the_largest_pattern() {
+    let contents = "large 2 3.5\n  O\n .X.\n..*..\n ...\n  .\n\
+                    large 3 7\n   .\n  .O.\n ..X..\n...*...\n .....\n  ...\n   .\n";
+    let patterns = LargePatterns::parse(contents).unwrap();
+    assert_eq!(Some(7.0), patterns.weight(&board(), &Play(Black, 5, 5)));
+}
+
+#[test]
+fn passes_dont_match() {
+    assert_eq!(None, patterns().weight(&board(), &Pass(Black)));
+}
+
+#[test]
+fn fails_for_an_unsupported_radius() {
+    assert!(LargePatterns::parse("large 5 1.0\n").is_err());
+}
+
+#[test]
+fn fails_for_unknown_points() {
+    let result = LargePatterns::parse("large 2 1\n  .\n .?.\n..*..\n ...\n  .\n");
+    assert_eq!(Err(String::from("Line 3: missing or unknown point at column 3")), result.map(|p| p.len()));
+}
@@ -0,0 +1,114 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use super::Pattern;
+
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+mod test;
+
+#[derive(Debug, PartialEq)]
+pub struct Weights {
+    pub patterns: Vec<(Pattern, f32)>,
+    pub features: Vec<(String, f32)>,
+}
+
+// Reads patterns in the same notation as Matcher::patterns(). Each
+// pattern is introduced by a line "pattern" (optionally followed by
+// a weight that defaults to 1) and followed by exactly three rows.
+// Rows shorter than three characters are padded with off board
+// points, as editors like to strip trailing whitespace. Empty lines
+// and lines starting with # are ignored between patterns. For the
+// weights, X are the stones of the player to move and O are the
+// opponent's.
+//
+// The weights of the other move features (see patterns::features)
+// are given on a single line each.
+//
+//     # hane pattern - enclosing hane
+//     pattern 1.5
+//     XOX
+//     ...
+//     ???
+//
+//     feature capture 12.3
+pub fn parse(contents: &str) -> Result<Weights, String> {
+    let mut patterns = Vec::new();
+    let mut features = Vec::new();
+    let mut lines = contents.lines().enumerate();
+    while let Some((n, line)) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.len() == 0 || trimmed.starts_with("#") {
+            continue;
+        }
+        let mut words = trimmed.split_whitespace();
+        match words.next() {
+            Some("pattern") => {},
+            Some("feature") => {
+                let feature = match (words.next(), words.next().map(|w| w.parse::<f32>())) {
+                    (Some(name), Some(Ok(weight))) => (String::from(name), weight),
+                    _ => return Err(format!("Line {}: expected 'feature NAME WEIGHT'", n+1))
+                };
+                features.push(feature);
+                continue;
+            },
+            _ => return Err(format!("Line {}: expected 'pattern' or 'feature', found '{}'", n+1, trimmed))
+        }
+        let weight = match words.next() {
+            Some(w) => match w.parse::<f32>() {
+                Ok(weight) => weight,
+                Err(_) => return Err(format!("Line {}: invalid weight '{}'", n+1, w))
+            },
+            None => 1.0
+        };
+        let mut rows = [[' '; 3]; 3];
+        for row in 0..3 {
+            match lines.next() {
+                Some((n, line)) => {
+                    let chars: Vec<char> = line.chars().collect();
+                    if chars.len() > 3 {
+                        return Err(format!("Line {}: a row has at most 3 points", n+1));
+                    }
+                    for (col, &c) in chars.iter().enumerate() {
+                        if !"XOxo.? ".contains(c) {
+                            return Err(format!("Line {}: unknown point '{}'", n+1, c));
+                        }
+                        rows[row][col] = c;
+                    }
+                },
+                None => return Err(format!("Line {}: the pattern needs three rows", n+1))
+            }
+        }
+        patterns.push((Pattern::new(rows), weight));
+    }
+    Ok(Weights { patterns: patterns, features: features })
+}
+
+pub fn load(path: &Path) -> Result<Weights, String> {
+    let mut contents = String::new();
+    match File::open(path).and_then(|mut file| file.read_to_string(&mut contents)) {
+        Ok(_) => parse(&contents),
+        Err(e) => Err(format!("Can't read {}: {}", path.display(), e))
+    }
+}
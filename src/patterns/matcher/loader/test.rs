@@ -0,0 +1,114 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+pub use hamcrest::assert_that;
+pub use hamcrest::equal_to;
+pub use hamcrest::is;
+pub use std::path::Path;
+
+pub use super::load;
+pub use super::parse;
+pub use super::super::Pattern;
+
+describe! parse {
+
+    it "reads a pattern" {
+        let patterns = parse("pattern\nXO.\nxo?\n...\n").unwrap().patterns;
+        let expected = Pattern::new([
+            ['X', 'O', '.'],
+            ['x', 'o', '?'],
+            ['.', '.', '.']]);
+        assert_that(patterns, is(equal_to(vec!((expected, 1.0)))));
+    }
+
+    it "reads the weight" {
+        let patterns = parse("pattern 2.5\n...\nX..\n...\n").unwrap().patterns;
+        assert_that(patterns[0].1, is(equal_to(2.5)));
+    }
+
+    it "pads short rows with off board points" {
+        let patterns = parse("pattern\nX.\nO.\n\n").unwrap().patterns;
+        let expected = Pattern::new([
+            ['X', '.', ' '],
+            ['O', '.', ' '],
+            [' ', ' ', ' ']]);
+        assert_that(patterns[0].0.clone(), is(equal_to(expected)));
+    }
+
+    it "skips comments and empty lines" {
+        let patterns = parse("# comment\n\npattern\n...\nX..\n...\n\n# another\npattern\n...\nO..\n...\n").unwrap().patterns;
+        assert_that(patterns.len(), is(equal_to(2)));
+    }
+
+    it "reads the feature weights" {
+        let weights = parse("feature capture 12.5\npattern\n...\nX..\n...\nfeature distance-2 3\n").unwrap();
+        assert_that(weights.patterns.len(), is(equal_to(1)));
+        assert_that(weights.features, is(equal_to(vec!(
+            (String::from("capture"), 12.5),
+            (String::from("distance-2"), 3.0)))));
+    }
+
+    it "fails for features without a weight" {
+        let result = parse("feature capture\n");
+        assert_that(result, is(equal_to(Err(String::from("Line 1: expected 'feature NAME WEIGHT'")))));
+    }
+
+    it "fails for unknown points" {
+        let result = parse("pattern\n...\nZ..\n...\n");
+        assert_that(result, is(equal_to(Err(String::from("Line 3: unknown point 'Z'")))));
+    }
+
+    it "fails for rows that are too long" {
+        let result = parse("pattern\n....\n...\n...\n");
+        assert_that(result, is(equal_to(Err(String::from("Line 2: a row has at most 3 points")))));
+    }
+
+    it "fails for missing rows" {
+        let result = parse("pattern\n...\n...");
+        assert_that(result, is(equal_to(Err(String::from("Line 1: the pattern needs three rows")))));
+    }
+
+    it "fails for an invalid weight" {
+        let result = parse("pattern heavy\n...\nX..\n...\n");
+        assert_that(result, is(equal_to(Err(String::from("Line 1: invalid weight 'heavy'")))));
+    }
+
+    it "fails for lines outside of a pattern" {
+        let result = parse("XO.\n");
+        assert_that(result, is(equal_to(Err(String::from("Line 1: expected 'pattern' or 'feature', found 'XO.'")))));
+    }
+
+}
+
+describe! load {
+
+    it "reads the michi patterns" {
+        let patterns = load(Path::new("fixtures/patterns/michi.txt")).unwrap().patterns;
+        assert_that(patterns.len(), is(equal_to(13)));
+    }
+
+    it "fails for a missing file" {
+        assert!(load(Path::new("fixtures/patterns/missing.txt")).is_err());
+    }
+
+}
@@ -21,36 +21,117 @@
 
 pub use self::pattern::Pattern;
 use board::Board;
+use board::Color;
 use board::Coord;
+use board::Move;
+use patterns::features;
+use patterns::large::LargePatterns;
 use self::tree::Tree;
 
+use std::collections::HashMap;
+use std::path::Path;
+
+mod loader;
 mod pattern;
 mod point;
 mod test;
 mod tree;
 
+/// The 3x3 patterns (and the move feature weights) the priors and
+/// the playouts use.
 pub struct Matcher {
-    tree: Tree
+    // The number of matching patterns for every possible 3x3
+    // neighbourhood code (see Board::pattern_code) and the sum of the
+    // weights of the patterns matching it when black is to move (see
+    // features::relative_pattern_code).
+    table: Vec<u16>,
+    weights: Vec<f32>,
+    // The weights of the non pattern move features, if they were
+    // loaded from a file.
+    features: HashMap<String, f32>,
+    large: LargePatterns,
 }
 
 impl Matcher {
 
+    /// The built-in patterns.
     pub fn new() -> Matcher {
         Self::with_patterns(Self::expand_patterns(Self::patterns()))
     }
 
+    /// The patterns and weights of a file written by the trainer or
+    /// by hand, in the notation of the built-in patterns.
+    pub fn from_file(path: &Path) -> Result<Matcher, String> {
+        let weights = try!(loader::load(path));
+        let patterns = weights.patterns.iter().map(|&(ref pattern, _)| pattern.clone()).collect();
+        let mut matcher = Self::with_weighted_patterns(
+            Self::expand_patterns(patterns),
+            Self::expand_weighted_patterns(weights.patterns));
+        matcher.features = weights.features.into_iter().collect();
+        Ok(matcher)
+    }
+
     fn with_patterns(patterns: Vec<Pattern>) -> Matcher {
-        Matcher { tree: Tree::from_patterns(patterns) }
+        let weighted = patterns.iter().map(|p| (p.clone(), 1.0)).collect();
+        Self::with_weighted_patterns(patterns, weighted)
+    }
+
+    // The counted patterns give the pattern counts, the weighted ones
+    // (with X as the player to move) the pattern weights. The weight
+    // of a pattern is added to the codes it matches, so the time
+    // this takes doesn't depend on the number of distinct weights.
+    fn with_weighted_patterns(counted: Vec<Pattern>, weighted: Vec<(Pattern, f32)>) -> Matcher {
+        let tree = Tree::from_patterns(counted);
+        let table = (0..65536)
+            .map(|code| tree.count_for(Board::colors_for_pattern_code(code as u16)) as u16)
+            .collect();
+        let mut weights = vec!(0.0; 65536);
+        for (pattern, weight) in weighted {
+            for code in pattern.codes() {
+                weights[code as usize] += weight;
+            }
+        }
+        Matcher {
+            table: table,
+            weights: weights,
+            features: HashMap::new(),
+            large: LargePatterns::new(),
+        }
     }
 
     pub fn pattern_count(&self, board: &Board, coord: &Coord) -> usize {
-        self.tree.pattern_count(board, coord)
+        self.table[board.pattern_code(coord) as usize] as usize
+    }
+
+    pub fn pattern_weight(&self, board: &Board, coord: &Coord, color: Color) -> f32 {
+        self.weights[features::relative_pattern_code(board.pattern_code(coord), color) as usize]
+    }
+
+    pub fn load_large_patterns(&mut self, path: &Path) -> Result<(), String> {
+        self.large = try!(LargePatterns::from_file(path));
+        Ok(())
+    }
+
+    pub fn large_pattern_weight(&self, board: &Board, m: &Move) -> Option<f32> {
+        self.large.weight(board, m)
+    }
+
+    pub fn feature_weight(&self, name: &str) -> Option<f32> {
+        self.features.get(name).cloned()
     }
 
     fn expand_patterns(patterns: Vec<Pattern>) -> Vec<Pattern> {
         patterns.iter().flat_map(|pattern| pattern.expand()).collect()
     }
 
+    // The colors of loaded patterns are relative to the player to
+    // move, so they are only rotated and mirrored.
+    fn expand_weighted_patterns(patterns: Vec<(Pattern, f32)>) -> Vec<(Pattern, f32)> {
+        patterns.iter()
+            .flat_map(|&(ref pattern, weight)| pattern.rotated().into_iter().map(move |p| (p, weight)))
+            .collect()
+    }
+
     // Patterns liften from michi.py
     // * X, O are colors
     // * . is an empty intersection
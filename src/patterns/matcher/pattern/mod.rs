@@ -19,7 +19,11 @@
  *                                                                      *
  ************************************************************************/
 
+use board::Black;
+use board::Board;
 use board::Color;
+use board::Empty;
+use board::White;
 use super::point::Point;
 
 mod test;
@@ -73,7 +77,28 @@ impl Pattern {
         }
     }
 
-    fn rotated(&self) -> Vec<Pattern> {
+    // The codes (see Board::pattern_code) of all the neighbourhoods
+    // that the pattern matches.
+    pub fn codes(&self) -> Vec<u16> {
+        let colors = [Some(Empty), Some(Black), Some(White), None];
+        let mut neighbourhoods: Vec<Vec<Option<Color>>> = vec!(vec!());
+        for point in self.points.iter() {
+            let mut expanded = vec!();
+            for neighbourhood in neighbourhoods.iter() {
+                for &color in colors.iter().filter(|&&color| point.matches(color)) {
+                    let mut neighbourhood = neighbourhood.clone();
+                    neighbourhood.push(color);
+                    expanded.push(neighbourhood);
+                }
+            }
+            neighbourhoods = expanded;
+        }
+        neighbourhoods.iter().map(|neighbourhood| Board::pattern_code_for_colors(neighbourhood)).collect()
+    }
+
+    // The pattern itself with its rotations and reflections, but
+    // without swapping the colors.
+    pub fn rotated(&self) -> Vec<Pattern> {
         vec!(
             self.clone(),
             self.rotated90(),
@@ -25,14 +25,42 @@ pub use hamcrest::assert_that;
 pub use hamcrest::contains;
 pub use hamcrest::equal_to;
 pub use hamcrest::is;
-pub use std::path::Path;
 
 pub use board::Board;
 pub use board::Coord;
-pub use sgf::Parser;
+pub use sgf::test::board_from_sgf;
 
 pub use super::Pattern;
 
+describe! codes {
+
+    it "has one code for a pattern without wildcards" {
+        let pattern = Pattern::new([
+            ['X', 'O', '.'],
+            ['.', '.', '.'],
+            [' ', ' ', ' ']]);
+        assert_that(pattern.codes().len(), is(equal_to(1)));
+    }
+
+    it "has a code for every color a wildcard matches" {
+        let pattern = Pattern::new([
+            ['?', 'x', 'o'],
+            ['.', '.', '.'],
+            ['.', '.', '.']]);
+        assert_that(pattern.codes().len(), is(equal_to(4 * 3 * 3)));
+    }
+
+    it "has the code of the neighbourhoods it matches" {
+        let pattern = Pattern::new([
+            ['.', '.', '.'],
+            ['X', '.', '.'],
+            ['.', '.', '.']]);
+        let board = board_from_sgf("3x3/one-black-w.sgf");
+        assert_that(pattern.codes(), is(equal_to(vec!(board.pattern_code(&Coord::new(5, 5))))));
+    }
+
+}
+
 describe! expand {
 
     before_each {
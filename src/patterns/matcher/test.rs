@@ -26,12 +26,18 @@ pub use hamcrest::equal_to;
 pub use hamcrest::is;
 pub use std::path::Path;
 
+pub use board::Black;
 pub use board::Board;
 pub use board::Coord;
-pub use sgf::Parser;
+pub use board::White;
+pub use sgf::test::board_from_sgf;
 
+pub use patterns::Trainer;
+pub use patterns::features::pattern_code_symmetries;
 pub use super::Matcher;
 pub use super::Pattern;
+pub use super::loader::parse;
+pub use super::tree::Tree;
 
 describe! expand_patterns {
 
@@ -50,12 +56,6 @@ describe! expand_patterns {
 
 }
 
-pub fn board_from_sgf(s: &str) -> Board {
-    let parser = Parser::from_path(Path::new(&format!("fixtures/sgf/{}", s))).unwrap();
-    let game = parser.game().unwrap();
-    game.board()
-}
-
 describe! pattern_count {
 
     before_each {
@@ -136,3 +136,84 @@ describe! pattern_count {
         assert_that(matcher.pattern_count(board, off_center), is(equal_to(0)));
     }
 }
+
+describe! pattern_table {
+
+    before_each {
+        let matcher = Matcher::new();
+        let tree = Tree::from_patterns(Matcher::expand_patterns(Matcher::patterns()));
+    }
+
+    it "returns the same counts as walking the tree" {
+        let board = &board_from_sgf("endgame-black-wins.sgf");
+        for coord in board.vacant().iter() {
+            assert_that(matcher.pattern_count(board, coord), is(equal_to(tree.pattern_count(board, coord))));
+        }
+    }
+
+}
+
+describe! from_file {
+
+    it "gives the same results as the built-in patterns" {
+        let matcher = Matcher::from_file(Path::new("fixtures/patterns/michi.txt")).unwrap();
+        assert_that(matcher.table, is(equal_to(Matcher::new().table)));
+    }
+
+    it "fails for a missing file" {
+        assert!(Matcher::from_file(Path::new("fixtures/patterns/missing.txt")).is_err());
+    }
+
+}
+
+describe! pattern_weight {
+
+    it "sums up the weights of the matching patterns" {
+        let patterns = vec!(
+            (Pattern::new([
+                ['.', '.', '.'],
+                ['X', '.', '.'],
+                ['.', '.', '.']]), 2.5),
+            (Pattern::new([
+                ['?', '?', '?'],
+                ['?', '.', '?'],
+                ['?', '?', '?']]), 1.0));
+        let counted = patterns.iter().map(|&(ref p, _)| p.clone()).collect();
+        let matcher = Matcher::with_weighted_patterns(counted, patterns);
+        let board = &board_from_sgf("3x3/one-black-w.sgf");
+        assert_that(matcher.pattern_count(board, &Coord::new(5, 5)), is(equal_to(2)));
+        assert_that(matcher.pattern_weight(board, &Coord::new(5, 5), Black), is(equal_to(3.5)));
+        assert_that(matcher.pattern_weight(board, &Coord::new(4, 4), Black), is(equal_to(1.0)));
+    }
+
+    it "treats X as the stones of the player to move" {
+        let patterns = vec!(
+            (Pattern::new([
+                ['.', '.', '.'],
+                ['X', '.', '.'],
+                ['.', '.', '.']]), 2.5));
+        let counted = patterns.iter().map(|&(ref p, _)| p.clone()).collect();
+        let matcher = Matcher::with_weighted_patterns(counted, patterns);
+        let board = &board_from_sgf("3x3/one-black-w.sgf");
+        assert_that(matcher.pattern_weight(board, &Coord::new(5, 5), Black), is(equal_to(2.5)));
+        assert_that(matcher.pattern_weight(board, &Coord::new(5, 5), White), is(equal_to(0.0)));
+    }
+
+}
+
+describe! learned_weights {
+
+    it "gives all symmetries of a learned pattern the same weight" {
+        let mut trainer = Trainer::new();
+        trainer.add_directory(Path::new("fixtures/training")).unwrap();
+        let weights = parse(&trainer.weights(&trainer.train())).unwrap();
+        let matcher = Matcher::with_weighted_patterns(vec!(), Matcher::expand_weighted_patterns(weights.patterns));
+        for code in 0..65536 {
+            let weight = matcher.weights[code];
+            for symmetry in pattern_code_symmetries(code as u16) {
+                assert_that(matcher.weights[symmetry as usize], is(equal_to(weight)));
+            }
+        }
+    }
+
+}
@@ -60,8 +60,12 @@ impl Tree {
         }
     }
 
+    #[cfg(test)]
     pub fn pattern_count(&self, board: &Board, coord: &Coord) -> usize {
-        let colors = board.neighbours8_checked(*coord);
+        self.count_for(board.neighbours8_checked(*coord))
+    }
+
+    pub fn count_for(&self, colors: Vec<Option<Color>>) -> usize {
         self.walk(colors, 0, &self)
     }
 
@@ -26,14 +26,13 @@ pub use hamcrest::equal_to;
 pub use hamcrest::is;
 pub use hamcrest::is_not;
 pub use hamcrest::none;
-pub use std::path::Path;
 
 pub use board::Black;
 pub use board::Board;
 pub use board::Coord;
 pub use board::Empty;
 pub use board::White;
-pub use sgf::Parser;
+pub use sgf::test::board_from_sgf;
 pub use super::Pattern;
 pub use super::Tree;
 
@@ -189,12 +188,6 @@ describe! from_patterns {
 
 }
 
-pub fn board_from_sgf(s: &str) -> Board {
-    let parser = Parser::from_path(Path::new(&format!("fixtures/sgf/{}", s))).unwrap();
-    let game = parser.game().unwrap();
-    game.board()
-}
-
 describe! pattern_count {
 
     before_each {
@@ -20,5 +20,9 @@
  ************************************************************************/
 
 pub use self::matcher::Matcher;
+pub use self::trainer::Trainer;
 
+pub mod features;
+mod large;
 mod matcher;
+mod trainer;
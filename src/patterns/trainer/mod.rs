@@ -0,0 +1,204 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Coord;
+use board::Move;
+use board::Play;
+use game::Game;
+use sgf;
+use sgf::ParseError;
+use sgf::Parser;
+use super::features;
+use super::features::Feature;
+use super::features::GROUPS;
+use super::matcher::Pattern;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+mod test;
+
+const ITERATIONS: usize = 20;
+
+// A move decision from a game record. All legal moves are grouped by
+// their features (a "team" in the generalized Bradley-Terry model)
+// and we remember how often each team occurs and which one was
+// played.
+struct Position {
+    teams: Vec<(Vec<usize>, usize)>,
+    played: usize,
+}
+
+// Learns the weights (gammas) of the move features with the
+// Minorization-Maximization algorithm from Coulom's "Computing Elo
+// Ratings of Move Patterns in the Game of Go".
+pub struct Trainer {
+    features: Vec<Feature>,
+    ids: HashMap<Feature, usize>,
+    positions: Vec<Position>,
+}
+
+impl Trainer {
+
+    pub fn new() -> Trainer {
+        Trainer {
+            features: vec!(),
+            ids: HashMap::new(),
+            positions: vec!(),
+        }
+    }
+
+    pub fn position_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    // Adds all *.sgf files in the directory and returns the number
+    // of games that could be read. Broken games are skipped.
+    pub fn add_directory(&mut self, dir: &Path) -> Result<usize, String> {
+        sgf::for_each_game(dir, |parser| self.add_game(parser))
+    }
+
+    pub fn add_game(&mut self, parser: &Parser) -> Result<(), ParseError> {
+        parser.replay(|game, m| self.add_position(game, m)).map(|_| ())
+    }
+
+    fn add_position(&mut self, game: &Game, m: &Move) {
+        // Handicap stones are sometimes given as consecutive moves of
+        // the same color. These aren't real decisions.
+        if m.is_pass() || m.is_resign() || *m.color() != game.next_player() {
+            return;
+        }
+        let board = game.board();
+        let last_move = match game.last_move() {
+            Play(_, col, row) => Some(Coord::new(col, row)),
+            _ => None
+        };
+        let mut teams: HashMap<Vec<usize>, usize> = HashMap::new();
+        let mut played = None;
+        for candidate in board.legal_moves_without_superko_check() {
+            let team = self.team_for(features::features(&board, &candidate, last_move));
+            if candidate == *m {
+                played = Some(team.clone());
+            }
+            *teams.entry(team).or_insert(0) += 1;
+        }
+        if let Some(played) = played {
+            let teams: Vec<(Vec<usize>, usize)> = teams.into_iter().collect();
+            let index = teams.iter().position(|&(ref team, _)| *team == played).unwrap();
+            self.positions.push(Position { teams: teams, played: index });
+        }
+    }
+
+    fn team_for(&mut self, features: Vec<Feature>) -> Vec<usize> {
+        let mut team: Vec<usize> = features.into_iter().map(|feature| self.id_for(feature)).collect();
+        team.sort();
+        team
+    }
+
+    fn id_for(&mut self, feature: Feature) -> usize {
+        if let Some(&id) = self.ids.get(&feature) {
+            return id;
+        }
+        let id = self.features.len();
+        self.features.push(feature);
+        self.ids.insert(feature, id);
+        id
+    }
+
+    pub fn train(&self) -> Vec<f64> {
+        let mut gammas = vec!(1.0; self.features.len());
+        let mut wins = vec!(0.0; self.features.len());
+        for position in self.positions.iter() {
+            for &id in position.teams[position.played].0.iter() {
+                wins[id] += 1.0;
+            }
+        }
+        for _ in 0..ITERATIONS {
+            for group in 0..GROUPS {
+                self.update_group(group, &wins, &mut gammas);
+            }
+        }
+        gammas
+    }
+
+    // As every team contains at most one feature of a group, all
+    // features of a group can be updated at the same time. Every
+    // feature gets a virtual win and loss against an opponent with a
+    // gamma of 1 so that rare features don't end up at 0 or infinity.
+    fn update_group(&self, group: usize, wins: &Vec<f64>, gammas: &mut Vec<f64>) {
+        let mut c = vec!(0.0; gammas.len());
+        for position in self.positions.iter() {
+            let strengths: Vec<f64> = position.teams.iter()
+                .map(|&(ref team, _)| team.iter().fold(1.0, |s, &id| s * gammas[id]))
+                .collect();
+            let total = position.teams.iter().zip(strengths.iter())
+                .fold(0.0, |sum, (&(_, count), strength)| sum + count as f64 * strength);
+            for (&(ref team, count), strength) in position.teams.iter().zip(strengths.iter()) {
+                for &id in team.iter().filter(|&&id| self.features[id].group() == group) {
+                    c[id] += count as f64 * strength / gammas[id] / total;
+                }
+            }
+        }
+        for id in 0..gammas.len() {
+            if self.features[id].group() == group {
+                let gamma = gammas[id];
+                gammas[id] = (wins[id] + 1.0) / (c[id] + 2.0 / (gamma + 1.0));
+            }
+        }
+    }
+
+    // Writes the gammas in the format of the pattern loader, with X
+    // as the player to move. The matcher adds up the weights of all
+    // rotations of a loaded pattern, so every symmetry of a pattern
+    // is written with a fraction of the gamma to make them add up to
+    // the gamma again.
+    pub fn weights(&self, gammas: &Vec<f64>) -> String {
+        let expansions = Pattern::new([['.'; 3]; 3]).rotated().len() as f64;
+        let mut s = format!("# Learned from {} positions\n", self.positions.len());
+        for (feature, gamma) in self.features.iter().zip(gammas.iter()) {
+            if let Some(name) = feature.name() {
+                s.push_str(&format!("feature {} {}\n", name, gamma));
+            }
+        }
+        for (feature, gamma) in self.features.iter().zip(gammas.iter()) {
+            if let &Feature::Pattern(code) = feature {
+                s.push_str(&format!("\n# gamma {}\n", gamma));
+                for symmetry in features::pattern_code_symmetries(code) {
+                    s.push_str(&format!("pattern {}\n{}", gamma / expansions, Self::rows(symmetry)));
+                }
+            }
+        }
+        s
+    }
+
+    fn rows(code: u16) -> String {
+        let c: Vec<char> = (0..8).map(|i| {
+            match (code >> (2 * i)) & 3 {
+                0 => '.',
+                1 => 'X',
+                2 => 'O',
+                _ => ' ',
+            }
+        }).collect();
+        format!("{}{}{}\n{}.{}\n{}{}{}\n", c[0], c[1], c[2], c[7], c[3], c[6], c[5], c[4])
+    }
+
+}
@@ -0,0 +1,84 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use super::super::features::Feature;
+use super::Position;
+use super::Trainer;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+// A trainer with a single capture feature that was played in all of
+// the positions.
+fn capture_trainer(played: usize) -> Trainer {
+    let mut trainer = Trainer {
+        features: vec!(Feature::Capture),
+        ids: HashMap::new(),
+        positions: vec!(),
+    };
+    trainer.ids.insert(Feature::Capture, 0);
+    for _ in 0..10 {
+        trainer.positions.push(Position {
+            teams: vec!((vec!(0), 1), (vec!(), 9)),
+            played: played,
+        });
+    }
+    trainer
+}
+
+#[test]
+fn add_directory_reads_all_sgf_files() {
+    let mut trainer = Trainer::new();
+    assert_eq!(Ok(2), trainer.add_directory(Path::new("fixtures/training")));
+    assert_eq!(24, trainer.position_count());
+}
+
+#[test]
+fn add_directory_fails_for_a_missing_directory() {
+    let mut trainer = Trainer::new();
+    assert!(trainer.add_directory(Path::new("fixtures/missing")).is_err());
+}
+
+#[test]
+fn frequently_played_features_get_a_high_gamma() {
+    let gammas = capture_trainer(0).train();
+    assert!(gammas[0] > 1.0);
+}
+
+#[test]
+fn features_that_are_never_played_get_a_low_gamma() {
+    let gammas = capture_trainer(1).train();
+    assert!(gammas[0] < 1.0);
+}
+
+#[test]
+fn weights_lists_the_features_by_name() {
+    let trainer = capture_trainer(0);
+    let weights = trainer.weights(&vec!(2.5));
+    assert!(weights.contains("feature capture 2.5\n"));
+}
+
+#[test]
+fn rows_start_in_the_north_west() {
+    assert_eq!("X..\nO. \n...\n", Trainer::rows(1 | (3 << 6) | (2 << 14)));
+}
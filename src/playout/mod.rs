@@ -21,32 +21,50 @@
 
 use board::Board;
 use board::Color;
-use board::Coord;
+use board::GoBoard;
 use board::Move;
-use board::Pass;
-use board::Play;
+use board::PseudoLibertyBoard;
 use config::Config;
 use patterns::Matcher;
+pub use self::policy::Policy;
+use self::policy::CascadePolicy;
+use self::policy::Gammas;
+use self::policy::LightPolicy;
+use self::policy::PlayoutPolicy;
+use self::policy::RandomPolicy;
+use self::policy::SoftmaxPolicy;
+use self::policy::light_move;
 
-use rand::Rng;
 use rand::XorShiftRng;
-use std::cmp;
 use std::sync::Arc;
 
+mod policy;
 mod test;
 
 pub struct Playout {
     config: Arc<Config>,
+    gammas: Gammas,
     matcher: Arc<Matcher>
 }
 
 impl Playout {
 
-    pub fn new(config: Arc<Config>, matcher: Arc<Matcher>) -> Playout {
-        Playout { config: config, matcher: matcher }
+    // The pseudo liberty board only supports the light policy.
+    pub fn new(config: Arc<Config>, matcher: Arc<Matcher>) -> Result<Playout, String> {
+        if config.playout.use_pseudo_liberty_board && config.playout.policy != Policy::Light {
+            return Err(format!("The pseudo liberty board can't be used with the {} playout policy", config.playout.policy));
+        }
+        let gammas = Gammas::from_matcher(&matcher);
+        Ok(Playout { config: config, gammas: gammas, matcher: matcher })
     }
 
     pub fn run(&self, board: &mut Board, initial_move: Option<&Move>, rng: &mut XorShiftRng) -> PlayoutResult {
+        // Only possible with the light policy, see new().
+        if self.config.playout.use_pseudo_liberty_board {
+            let mut fast_board = PseudoLibertyBoard::from_board(board);
+            return self.run_light(&mut fast_board, initial_move, rng);
+        }
+        let mut policy = self.policy();
         let mut played_moves = Vec::new();
 
         initial_move.map(|&m| {
@@ -54,165 +72,53 @@ impl Playout {
             played_moves.push(m);
         });
 
-        let max_moves = self.max_moves(board.size());
+        let max_moves = self.max_moves(board.point_count());
         while !board.is_game_over() && played_moves.len() < max_moves {
-            let heuristic_set = self.heuristic_set(&played_moves, board, rng);
-            let m = self.select_move(board, heuristic_set, rng);
+            let m = policy.select_move(board, &played_moves, rng);
             board.play_legal_move(m);
+            policy.played(board, &m);
             played_moves.push(m);
         }
         PlayoutResult::new(played_moves, board.winner())
     }
 
-    //don't self atari strings that will make an eye after dying, which is strings of 7+
-    fn is_playable(&self, board: &Board, m: &Move) -> bool {
-        !board.is_eye(&m.coord(), *m.color()) &&
-            (board.is_not_self_atari(m) ||
-             board.new_chain_length_less_than(*m, self.cutoff())) //suicide for smaller groups is ok
-    }
-
-    fn max_moves(&self, size: u8) -> usize {
-        size as usize * size as usize * 3
-    }
-
-    fn heuristic_set(&self, played_moves: &Vec<Move>, board: &Board, rng: &mut XorShiftRng) -> Vec<Coord> {
-        let moves_to_consider = self.config.playout.last_moves_for_heuristics as isize;
-        let idx = cmp::max(played_moves.len() as isize - moves_to_consider,0) as usize;
-        let moves = &played_moves[idx..played_moves.len()];
-        let mut coords = vec!();
-        // The neighbours of the latest move should come first as we
-        // select a matching move from the start of the vector and
-        // these should take precedence.
-        for i in (0..moves.len()).rev() {
-            if !moves[i].is_pass() {
-                let mut candidates : Vec<Coord> = board.neighbours(moves[i].coord()).iter().chain(board.diagonals(moves[i].coord())).cloned().collect();
-                rng.shuffle(&mut candidates);
-                for c in candidates {
-                    if !coords.contains(&c) {
-                        coords.push(c);
-                    }
-                }
-            }
-        }
-        coords
-    }
-
-    fn select_move(&self, board: &Board, heuristic_set: Vec<Coord>, rng: &mut XorShiftRng) -> Move {
-        let color = board.next_player();
-
-        if self.check_for_atari() {
-            let possible_move = self.atari_move(color, board, rng);
-            if possible_move.is_some() {
-                return possible_move.unwrap();
-            }
-        }
-        if self.use_patterns(rng) {
-            let possible_move = self.pattern_move(color, heuristic_set, board);
-            if possible_move.is_some() {
-                return possible_move.unwrap();
-            }
-        }
-        self.random_move(color, board, rng)
-    }
-
-    // If own group of more than one stone has one liberty, check if it can be captured
-    fn atari_move(&self, color: Color, board: &Board, rng: &mut XorShiftRng) -> Option<Move> {
-        let mut in_danger = board.chains().iter()
-            .filter(|chain| {
-                chain.color() == color && chain.coords().len() > 1 && chain.liberties().len() == 1
-            });
-        match in_danger.next() {
-            Some(chain) => {
-                let solutions = if self.check_for_ladders() {
-                    board.save_group(chain)
-                } else {
-                    board.fix_atari_no_ladder_check(chain)
-                };
-                if solutions.len() > 0 { //if we can actually save it
-                    let random = rng.gen::<usize>() % solutions.len();
-                    Some(solutions[random])
-                } else {
-                    None
-                }
-            },
-            None => None
-        }
-    }
-
-    fn pattern_move(&self, color: Color, coords: Vec<Coord>, board: &Board) -> Option<Move> {
-        // This works as coords is randomly ordered, so taking the
-        // first we find is OK.
-        coords.iter()
-            .map(|c| Play(color, c.col, c.row))
-            .find(|&m| {
-                board.is_legal(m).is_ok() && self.matches(board, &m)
-            })
-    }
-
-    fn matches(&self, board: &Board, m: &Move) -> bool {
-        self.matcher.pattern_count(board, &m.coord()) > 0
-    }
-
-    fn random_move(&self, color: Color, board: &Board, rng: &mut XorShiftRng) -> Move {
-        let vacant = board.vacant();
-        let playable_move = vacant
-            .iter()
-            .map(|c| Play(color, c.col, c.row))
-            .position(|m| board.is_legal(m).is_ok() && self.is_playable(board, &m));
-        if let Some(first) = playable_move {
-            let mut include_pass = 0;
-            loop {
-                let r = first + rng.gen::<usize>() % (vacant.len() - first + include_pass);
-
-                if r == vacant.len() {
-                    return Pass(color);
-                }
-                let c = vacant[r];
-                let m = Play(color, c.col, c.row);
-                if board.is_legal(m).is_ok() && self.is_playable(board, &m) {
-                    if include_pass == 0 && !board.is_not_self_atari(&m) {
-                        include_pass = 1; //try to pass in a seki sometimes
-                    } else {
-                        return if self.play_in_middle_of_eye() {
-                            board.play_in_middle_of_eye(m).unwrap_or(m)
-                        } else {
-                            m
-                        };
-                    }
-                }
-            }
-        } else {
-            Pass(color)
+    fn policy<'a>(&'a self) -> Box<PlayoutPolicy + 'a> {
+        match self.config.playout.policy {
+            Policy::Cascade => Box::new(CascadePolicy::new(&self.config, &self.matcher)),
+            Policy::Light   => Box::new(LightPolicy),
+            Policy::Random  => Box::new(RandomPolicy),
+            Policy::Softmax => Box::new(SoftmaxPolicy::new(&self.matcher, &self.gammas)),
         }
     }
 
-    fn check_for_ladders(&self) -> bool {
-        self.config.playout.ladder_check
-    }
+    // The light policy only needs the operations of GoBoard, so it
+    // can run on the faster board representations.
+    fn run_light<B: GoBoard>(&self, board: &mut B, initial_move: Option<&Move>, rng: &mut XorShiftRng) -> PlayoutResult {
+        let mut played_moves = Vec::new();
 
-    fn check_for_atari(&self) -> bool {
-        self.config.playout.atari_check
-    }
+        initial_move.map(|&m| {
+            board.play_legal_move(m);
+            played_moves.push(m);
+        });
 
-    fn use_patterns(&self, rng: &mut XorShiftRng) -> bool {
-        if self.config.playout.use_patterns {
-            rng.gen_range(0f32, 1f32) < self.config.playout.pattern_probability
-        } else {
-            false
+        let max_moves = self.max_moves(board.point_count());
+        while !board.is_game_over() && played_moves.len() < max_moves {
+            let m = light_move(board, rng);
+            board.play_legal_move(m);
+            played_moves.push(m);
         }
+        PlayoutResult::new(played_moves, board.winner())
     }
 
-    fn play_in_middle_of_eye(&self) -> bool {
-        self.config.playout.play_in_middle_of_eye
-    }
-
-    fn cutoff(&self) -> usize {
-        self.config.playout.no_self_atari_cutoff
+    fn max_moves(&self, point_count: usize) -> usize {
+        point_count * 3
     }
 
 }
 
 pub struct PlayoutResult {
+    // Only read by the tests.
+    #[allow(dead_code)]
     moves: Vec<Move>,
     winner: Color,
 }
@@ -223,6 +129,7 @@ impl PlayoutResult {
         PlayoutResult { moves: moves, winner: winner }
     }
 
+    #[cfg(test)]
     pub fn moves(&self) -> &Vec<Move> {
         &self.moves
     }
@@ -0,0 +1,247 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Move;
+use board::Pass;
+use board::Play;
+use config::Config;
+use patterns::Matcher;
+use super::PlayoutPolicy;
+
+use rand::Rng;
+use rand::XorShiftRng;
+use std::cmp;
+
+// Tries to save own groups in atari first (and optionally to win
+// capturing races), then plays a move that matches a pattern around
+// the last moves and falls back to a random move that isn't an
+// obviously bad self atari.
+pub struct CascadePolicy<'a> {
+    config: &'a Config,
+    matcher: &'a Matcher,
+}
+
+impl<'a> CascadePolicy<'a> {
+
+    pub fn new(config: &'a Config, matcher: &'a Matcher) -> CascadePolicy<'a> {
+        CascadePolicy { config: config, matcher: matcher }
+    }
+
+    //don't self atari strings that will make an eye after dying, which is strings of 7+
+    //and don't fill the shared liberties of a seki
+    fn is_playable(&self, board: &Board, m: &Move) -> bool {
+        !board.is_eye(&m.coord(), *m.color()) &&
+            (board.is_not_self_atari(m) ||
+             (board.new_chain_length_less_than(*m, self.cutoff()) && //suicide for smaller groups is ok
+              !(self.check_for_seki() && board.is_seki_liberty(&m.coord()))))
+    }
+
+    fn heuristic_set(&self, played_moves: &Vec<Move>, board: &Board, rng: &mut XorShiftRng) -> Vec<Coord> {
+        let moves_to_consider = self.config.playout.last_moves_for_heuristics as isize;
+        let idx = cmp::max(played_moves.len() as isize - moves_to_consider,0) as usize;
+        let moves = &played_moves[idx..played_moves.len()];
+        let mut coords = vec!();
+        // The neighbours of the latest move should come first as we
+        // select a matching move from the start of the vector and
+        // these should take precedence.
+        for i in (0..moves.len()).rev() {
+            if !moves[i].is_pass() {
+                let mut candidates : Vec<Coord> = board.neighbours(moves[i].coord()).iter().chain(board.diagonals(moves[i].coord())).cloned().collect();
+                rng.shuffle(&mut candidates);
+                for c in candidates {
+                    if !coords.contains(&c) {
+                        coords.push(c);
+                    }
+                }
+            }
+        }
+        coords
+    }
+
+    fn cascade(&self, board: &Board, heuristic_set: Vec<Coord>, rng: &mut XorShiftRng) -> Move {
+        let color = board.next_player();
+
+        if self.check_for_atari() {
+            let possible_move = self.atari_move(color, board, rng);
+            if possible_move.is_some() {
+                return possible_move.unwrap();
+            }
+        }
+        if self.check_for_semeai() {
+            let possible_move = self.semeai_move(color, board, rng);
+            if possible_move.is_some() {
+                return possible_move.unwrap();
+            }
+        }
+        if self.use_patterns(rng) {
+            let possible_move = self.pattern_move(color, heuristic_set, board);
+            if possible_move.is_some() {
+                return possible_move.unwrap();
+            }
+        }
+        self.random_move(color, board, rng)
+    }
+
+    // If own group of more than one stone has one liberty, check if it can be captured
+    fn atari_move(&self, color: Color, board: &Board, rng: &mut XorShiftRng) -> Option<Move> {
+        let mut in_danger = board.chains().iter()
+            .filter(|chain| {
+                chain.color() == color && chain.coords().len() > 1 && chain.liberties().len() == 1
+            });
+        match in_danger.next() {
+            Some(chain) => {
+                let solutions = if self.check_for_ladders() {
+                    board.save_group(chain)
+                } else {
+                    board.fix_atari_no_ladder_check(chain)
+                };
+                if solutions.len() > 0 { //if we can actually save it
+                    let random = rng.gen::<usize>() % solutions.len();
+                    Some(solutions[random])
+                } else {
+                    None
+                }
+            },
+            None => None
+        }
+    }
+
+    // Fill a liberty of an opponent chain in a capturing race that
+    // is only won by moving now.
+    fn semeai_move(&self, color: Color, board: &Board, rng: &mut XorShiftRng) -> Option<Move> {
+        let mut moves = vec!();
+        for semeai in board.semeais(color).iter().filter(|s| s.winner == color && s.critical) {
+            for c in semeai.moves.iter() {
+                let m = Play(color, c.col, c.row);
+                if self.is_playable(board, &m) {
+                    moves.push(m);
+                }
+            }
+        }
+        if moves.len() > 0 {
+            let random = rng.gen::<usize>() % moves.len();
+            Some(moves[random])
+        } else {
+            None
+        }
+    }
+
+    fn pattern_move(&self, color: Color, coords: Vec<Coord>, board: &Board) -> Option<Move> {
+        // This works as coords is randomly ordered, so taking the
+        // first we find is OK.
+        coords.iter()
+            .map(|c| Play(color, c.col, c.row))
+            .find(|&m| {
+                board.is_legal(m).is_ok() && self.matches(board, &m)
+            })
+    }
+
+    fn matches(&self, board: &Board, m: &Move) -> bool {
+        self.matcher.pattern_count(board, &m.coord()) > 0
+    }
+
+    fn random_move(&self, color: Color, board: &Board, rng: &mut XorShiftRng) -> Move {
+        let vacant = board.vacant();
+        let playable_move = vacant
+            .iter()
+            .map(|c| Play(color, c.col, c.row))
+            .position(|m| board.is_legal(m).is_ok() && self.is_playable(board, &m));
+        if let Some(first) = playable_move {
+            let mut include_pass = 0;
+            loop {
+                let r = first + rng.gen::<usize>() % (vacant.len() - first + include_pass);
+
+                if r == vacant.len() {
+                    return Pass(color);
+                }
+                let c = vacant[r];
+                let m = Play(color, c.col, c.row);
+                if board.is_legal(m).is_ok() && self.is_playable(board, &m) {
+                    if include_pass == 0 && !board.is_not_self_atari(&m) {
+                        include_pass = 1; //try to pass in a seki sometimes
+                    } else {
+                        if self.use_nakade() {
+                            if let Some(vital) = board.nakade_move(m) {
+                                return vital;
+                            }
+                        }
+                        return if self.play_in_middle_of_eye() {
+                            board.play_in_middle_of_eye(m).unwrap_or(m)
+                        } else {
+                            m
+                        };
+                    }
+                }
+            }
+        } else {
+            Pass(color)
+        }
+    }
+
+    fn check_for_ladders(&self) -> bool {
+        self.config.playout.ladder_check
+    }
+
+    fn check_for_atari(&self) -> bool {
+        self.config.playout.atari_check
+    }
+
+    fn check_for_seki(&self) -> bool {
+        self.config.playout.seki_check
+    }
+
+    fn check_for_semeai(&self) -> bool {
+        self.config.playout.semeai_check
+    }
+
+    fn use_patterns(&self, rng: &mut XorShiftRng) -> bool {
+        if self.config.playout.use_patterns {
+            rng.gen_range(0f32, 1f32) < self.config.playout.pattern_probability
+        } else {
+            false
+        }
+    }
+
+    fn use_nakade(&self) -> bool {
+        self.config.playout.nakade
+    }
+
+    fn play_in_middle_of_eye(&self) -> bool {
+        self.config.playout.play_in_middle_of_eye
+    }
+
+    fn cutoff(&self) -> usize {
+        self.config.playout.no_self_atari_cutoff
+    }
+
+}
+
+impl<'a> PlayoutPolicy for CascadePolicy<'a> {
+
+    fn select_move(&mut self, board: &Board, played_moves: &Vec<Move>, rng: &mut XorShiftRng) -> Move {
+        let heuristic_set = self.heuristic_set(played_moves, board, rng);
+        self.cascade(board, heuristic_set, rng)
+    }
+
+}
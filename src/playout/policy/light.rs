@@ -0,0 +1,59 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::GoBoard;
+use board::Move;
+use board::Pass;
+use board::Play;
+use super::PlayoutPolicy;
+
+use rand::Rng;
+use rand::XorShiftRng;
+
+// Plays uniformly random moves that don't fill one's own eyes.
+pub struct LightPolicy;
+
+impl PlayoutPolicy for LightPolicy {
+
+    fn select_move(&mut self, board: &Board, _: &Vec<Move>, rng: &mut XorShiftRng) -> Move {
+        light_move(board, rng)
+    }
+
+}
+
+// It only needs the operations of GoBoard so that it can also run on
+// the faster board representations.
+pub fn light_move<B: GoBoard>(board: &B, rng: &mut XorShiftRng) -> Move {
+    let color = board.next_player();
+    let vacant = board.vacant();
+    if vacant.len() > 0 {
+        let start = rng.gen::<usize>() % vacant.len();
+        for i in 0..vacant.len() {
+            let c = vacant[(start + i) % vacant.len()];
+            let m = Play(color, c.col, c.row);
+            if !board.is_eye(&c, color) && board.is_legal(m).is_ok() {
+                return m;
+            }
+        }
+    }
+    Pass(color)
+}
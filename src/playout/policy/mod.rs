@@ -0,0 +1,100 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Move;
+use config::Hint;
+pub use self::Policy::Cascade;
+pub use self::Policy::Light;
+pub use self::Policy::Random;
+pub use self::Policy::Softmax;
+pub use self::cascade::CascadePolicy;
+pub use self::light::LightPolicy;
+pub use self::light::light_move;
+pub use self::random::RandomPolicy;
+pub use self::softmax::Gammas;
+pub use self::softmax::SoftmaxPolicy;
+
+use rand::XorShiftRng;
+use std::fmt;
+use std::str::FromStr;
+
+mod cascade;
+mod light;
+mod random;
+mod softmax;
+mod test;
+
+// Selects the moves of a playout. A new policy is created for every
+// playout, so implementations can keep state between the moves.
+pub trait PlayoutPolicy {
+
+    fn select_move(&mut self, board: &Board, played_moves: &Vec<Move>, rng: &mut XorShiftRng) -> Move;
+
+    // Called after the move returned by select_move was played.
+    fn played(&mut self, _: &Board, _: &Move) {}
+
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum Policy {
+    Cascade,
+    Light,
+    Random,
+    Softmax,
+}
+
+impl FromStr for Policy {
+
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Policy, Self::Err> {
+        match s {
+            "cascade" => Ok(Cascade),
+            "light"   => Ok(Light),
+            "random"  => Ok(Random),
+            "softmax" => Ok(Softmax),
+            _         => Err(format!("Unknown playout policy '{}'", s)),
+        }
+    }
+
+}
+
+impl fmt::Display for Policy {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            Cascade => "cascade",
+            Light   => "light",
+            Random  => "random",
+            Softmax => "softmax",
+        };
+        s.fmt(f)
+    }
+}
+
+impl Hint for Policy {
+
+    fn hint_str(&self) -> &'static str {
+        "cascade|light|random|softmax"
+    }
+
+}
@@ -0,0 +1,54 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Move;
+use board::Pass;
+use board::Play;
+use super::PlayoutPolicy;
+
+use rand::Rng;
+use rand::XorShiftRng;
+
+// Plays uniformly random legal moves, even if they fill one's own
+// eyes. It only passes if there's no legal move left, so these
+// playouts usually end because of the move limit.
+pub struct RandomPolicy;
+
+impl PlayoutPolicy for RandomPolicy {
+
+    fn select_move(&mut self, board: &Board, _: &Vec<Move>, rng: &mut XorShiftRng) -> Move {
+        let color = board.next_player();
+        let vacant = board.vacant();
+        if vacant.len() > 0 {
+            let start = rng.gen::<usize>() % vacant.len();
+            for i in 0..vacant.len() {
+                let c = vacant[(start + i) % vacant.len()];
+                let m = Play(color, c.col, c.row);
+                if board.is_legal(m).is_ok() {
+                    return m;
+                }
+            }
+        }
+        Pass(color)
+    }
+
+}
@@ -0,0 +1,293 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::Move;
+use board::Pass;
+use board::Play;
+use board::White;
+use patterns::Matcher;
+use patterns::features;
+use patterns::features::Feature;
+use patterns::features::MAX_DISTANCE;
+use super::PlayoutPolicy;
+
+use rand::Rng;
+use rand::XorShiftRng;
+
+// The gammas of the non pattern features. Features without a weight
+// in the patterns file are neutral.
+pub struct Gammas {
+    capture: f32,
+    atari: f32,
+    self_atari: f32,
+    distance: Vec<f32>,
+}
+
+impl Gammas {
+
+    pub fn from_matcher(matcher: &Matcher) -> Gammas {
+        let gamma = |feature: Feature| {
+            feature.name().and_then(|name| matcher.feature_weight(&name)).unwrap_or(1.0)
+        };
+        Gammas {
+            capture: gamma(Feature::Capture),
+            atari: gamma(Feature::Atari),
+            self_atari: gamma(Feature::SelfAtari),
+            distance: (0..MAX_DISTANCE+1).map(|d| gamma(Feature::Distance(d))).collect(),
+        }
+    }
+
+    fn gamma(&self, feature: &Feature) -> f32 {
+        match *feature {
+            Feature::Pattern(_)  => 1.0,
+            Feature::Capture     => self.capture,
+            Feature::Atari       => self.atari,
+            Feature::SelfAtari   => self.self_atari,
+            Feature::Distance(d) => self.distance[d as usize],
+        }
+    }
+
+}
+
+// Selects moves with a probability proportional to the product of
+// the gammas of their features. The weights of all points are kept in
+// a table per color (with the sums of each row to speed up sampling)
+// and only the points around the last move are recomputed after
+// each move. The distance to the previous move is applied when
+// sampling (to the points within a manhattan distance of three, the
+// only ones with a distance feature), as it changes with every move
+// anyway.
+pub struct Softmax {
+    width: u8,
+    weights: Vec<Vec<f32>>,
+    row_totals: Vec<Vec<f32>>,
+    ko: Option<Coord>,
+}
+
+impl Softmax {
+
+    pub fn new(board: &Board, matcher: &Matcher, gammas: &Gammas) -> Softmax {
+        let mut softmax = Softmax {
+            width: board.width(),
+            weights: vec!(vec!(0.0; board.point_count()); 2),
+            row_totals: vec!(vec!(0.0; board.height() as usize); 2),
+            ko: board.ko(),
+        };
+        let all = Coord::for_board_size(board.width(), board.height());
+        softmax.recompute(board, matcher, gammas, all);
+        softmax
+    }
+
+    // Has to be called after m was played on the board.
+    pub fn update(&mut self, board: &Board, matcher: &Matcher, gammas: &Gammas, m: &Move) {
+        let mut dirty = vec!();
+        if let Some(ko) = self.ko {
+            dirty.push(ko);
+        }
+        if let Some(ko) = board.ko() {
+            dirty.push(ko);
+        }
+        if !m.is_pass() && !m.is_resign() {
+            let changed: Vec<Coord> = Some(m.coord()).into_iter()
+                .chain(board.adv_stones_removed().iter().cloned())
+                .chain(board.friend_stones_removed().iter().cloned())
+                .collect();
+            for &c in changed.iter() {
+                dirty.push(c);
+                dirty.extend(board.neighbours8_unchecked(c).iter()
+                             .filter(|n| n.is_inside(board.width(), board.height()))
+                             .cloned());
+                // Chains next to a changed point have gained or lost
+                // liberties, which changes the capture, atari and
+                // self atari features of these liberties.
+                for n in board.neighbours(c).iter() {
+                    if let Some(chain) = board.get_chain(*n) {
+                        dirty.extend(chain.liberties().iter().cloned());
+                    }
+                }
+            }
+        }
+        dirty.sort_by(|a, b| a.to_index(self.width).cmp(&b.to_index(self.width)));
+        dirty.dedup();
+        self.ko = board.ko();
+        self.recompute(board, matcher, gammas, dirty);
+    }
+
+    pub fn select(&self, board: &Board, gammas: &Gammas, last: Option<Coord>, rng: &mut XorShiftRng) -> Move {
+        let color = board.next_player();
+        let table = Self::table(color);
+        let near: Vec<(Coord, f32)> = match last {
+            Some(last) => last.manhattan_distance_three_neighbours(board.width(), board.height())
+                .into_iter()
+                .map(|c| {
+                    let d = features::distance(&c, &last);
+                    (c, self.weights[table][c.to_index(self.width)] * gammas.distance[d as usize])
+                })
+                .collect(),
+            None => vec!()
+        };
+        let near_total = near.iter().fold(0.0, |sum, &(_, w)| sum + w);
+        let near_base = near.iter().fold(0.0, |sum, &(c, _)| sum + self.weights[table][c.to_index(self.width)]);
+        let far_total = self.row_totals[table].iter().fold(0.0, |sum, w| sum + w) - near_base;
+        if near_total + far_total <= 0.0 {
+            return Pass(color);
+        }
+        let r = rng.gen_range(0.0, near_total + far_total);
+        if r < near_total || far_total <= 0.0 {
+            let mut r = r;
+            for &(c, w) in near.iter() {
+                if r < w {
+                    return Play(color, c.col, c.row);
+                }
+                r -= w;
+            }
+            // Rounding errors
+            if let Some(&(c, _)) = near.iter().rev().find(|&&(_, w)| w > 0.0) {
+                return Play(color, c.col, c.row);
+            }
+        }
+        // Rejection sampling to leave out the points that were
+        // already considered above. The number of tries is limited
+        // as far_total may only be positive due to rounding errors.
+        let mut sampled = None;
+        for _ in 0..100 {
+            sampled = self.sample(table, board, rng);
+            match sampled {
+                Some(c) if near.iter().any(|&(n, _)| n == c) => {},
+                _ => break
+            }
+        }
+        match sampled {
+            Some(c) => Play(color, c.col, c.row),
+            None => Pass(color)
+        }
+    }
+
+    #[cfg(test)]
+    pub fn weight_at(&self, c: &Coord, color: Color) -> f32 {
+        self.weights[Self::table(color)][c.to_index(self.width)]
+    }
+
+    fn sample(&self, table: usize, board: &Board, rng: &mut XorShiftRng) -> Option<Coord> {
+        let total = self.row_totals[table].iter().fold(0.0, |sum, w| sum + w);
+        if total <= 0.0 {
+            return None;
+        }
+        let mut r = rng.gen_range(0.0, total);
+        for row in 0..board.height() {
+            let row_total = self.row_totals[table][row as usize];
+            if r < row_total {
+                for col in 0..board.width() {
+                    let w = self.weights[table][row as usize * self.width as usize + col as usize];
+                    if r < w {
+                        return Some(Coord::new(col + 1, row + 1));
+                    }
+                    r -= w;
+                }
+            }
+            r -= row_total;
+        }
+        None
+    }
+
+    fn recompute(&mut self, board: &Board, matcher: &Matcher, gammas: &Gammas, coords: Vec<Coord>) {
+        let mut rows = vec!();
+        for c in coords {
+            let index = c.to_index(self.width);
+            for &color in [Black, White].iter() {
+                self.weights[Self::table(color)][index] = Self::weight(board, matcher, gammas, c, color);
+            }
+            if !rows.contains(&c.row) {
+                rows.push(c.row);
+            }
+        }
+        for row in rows {
+            let start = (row as usize - 1) * self.width as usize;
+            for table in 0..2 {
+                self.row_totals[table][row as usize - 1] = self.weights[table][start..start + self.width as usize]
+                    .iter()
+                    .fold(0.0, |sum, w| sum + w);
+            }
+        }
+    }
+
+    fn weight(board: &Board, matcher: &Matcher, gammas: &Gammas, c: Coord, color: Color) -> f32 {
+        let m = Play(color, c.col, c.row);
+        // The weights of both colors are kept up to date, so the
+        // color that just moved mustn't be rejected for playing twice.
+        if board.color(&c) != Empty || board.is_eye(&c, color) || board.is_legal_regardless_of_turn(m).is_err() {
+            return 0.0;
+        }
+        // Neighbourhoods that weren't seen during training are
+        // neutral.
+        let pattern = matcher.pattern_weight(board, &c, color);
+        let mut weight = if pattern > 0.0 { pattern } else { 1.0 };
+        for feature in features::features(board, &m, None) {
+            weight *= gammas.gamma(&feature);
+        }
+        weight
+    }
+
+    fn table(color: Color) -> usize {
+        if color == Black { 0 } else { 1 }
+    }
+
+}
+
+pub struct SoftmaxPolicy<'a> {
+    matcher: &'a Matcher,
+    gammas: &'a Gammas,
+    // Created on the first move, as the playout may start with a
+    // move that was already played.
+    softmax: Option<Softmax>,
+}
+
+impl<'a> SoftmaxPolicy<'a> {
+
+    pub fn new(matcher: &'a Matcher, gammas: &'a Gammas) -> SoftmaxPolicy<'a> {
+        SoftmaxPolicy { matcher: matcher, gammas: gammas, softmax: None }
+    }
+
+}
+
+impl<'a> PlayoutPolicy for SoftmaxPolicy<'a> {
+
+    fn select_move(&mut self, board: &Board, played_moves: &Vec<Move>, rng: &mut XorShiftRng) -> Move {
+        if self.softmax.is_none() {
+            self.softmax = Some(Softmax::new(board, self.matcher, self.gammas));
+        }
+        let last = played_moves.last()
+            .and_then(|m| if m.is_pass() { None } else { Some(m.coord()) });
+        self.softmax.as_ref().unwrap().select(board, self.gammas, last, rng)
+    }
+
+    fn played(&mut self, board: &Board, m: &Move) {
+        if let Some(ref mut softmax) = self.softmax {
+            softmax.update(board, self.matcher, self.gammas, m);
+        }
+    }
+
+}
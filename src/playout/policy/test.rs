@@ -0,0 +1,99 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Empty;
+use board::Pass;
+use board::Play;
+use board::White;
+use patterns::Matcher;
+use ruleset::KgsChinese;
+use super::Cascade;
+use super::Gammas;
+use super::LightPolicy;
+use super::PlayoutPolicy;
+use super::Policy;
+use super::RandomPolicy;
+use super::Softmax;
+use super::softmax::Softmax as SoftmaxWeights;
+
+use rand::weak_rng;
+
+#[test]
+fn policies_can_be_parsed() {
+    assert_eq!(Ok(Cascade), "cascade".parse::<Policy>());
+    assert_eq!(Ok(Softmax), "softmax".parse::<Policy>());
+    assert!("heavy".parse::<Policy>().is_err());
+}
+
+#[test]
+fn policies_are_displayed_like_they_are_parsed() {
+    for s in ["cascade", "light", "random", "softmax"].iter() {
+        assert_eq!(*s, format!("{}", s.parse::<Policy>().unwrap()));
+    }
+}
+
+#[test]
+fn light_policy_passes_when_only_eyes_are_left() {
+    let mut board = Board::new(2, 6.5, KgsChinese);
+    board.play_legal_move(Play(Black, 1, 1));
+    board.play_legal_move(Pass(White));
+    board.play_legal_move(Play(Black, 2, 2));
+    board.play_legal_move(Pass(White));
+    let mut rng = weak_rng();
+    assert_eq!(Pass(Black), LightPolicy.select_move(&board, &vec!(), &mut rng));
+}
+
+#[test]
+fn random_policy_also_fills_eyes() {
+    let mut board = Board::new(2, 6.5, KgsChinese);
+    board.play_legal_move(Play(Black, 1, 1));
+    board.play_legal_move(Pass(White));
+    board.play_legal_move(Play(Black, 2, 2));
+    board.play_legal_move(Pass(White));
+    let mut rng = weak_rng();
+    assert!(!RandomPolicy.select_move(&board, &vec!(), &mut rng).is_pass());
+}
+
+#[test]
+fn softmax_keeps_the_weights_of_both_colors_up_to_date() {
+    let matcher = Matcher::new();
+    let gammas = Gammas::from_matcher(&matcher);
+    let mut board = Board::new(9, 6.5, KgsChinese);
+    let mut softmax = SoftmaxWeights::new(&board, &matcher, &gammas);
+    for &m in [Play(Black, 5, 5), Play(White, 5, 6), Play(Black, 4, 6)].iter() {
+        board.play(m).unwrap();
+        softmax.update(&board, &matcher, &gammas, &m);
+        let fresh = SoftmaxWeights::new(&board, &matcher, &gammas);
+        for c in Coord::for_board_size(9, 9) {
+            for &color in [Black, White].iter() {
+                assert_eq!(fresh.weight_at(&c, color), softmax.weight_at(&c, color));
+                if board.color(&c) == Empty {
+                    assert!(softmax.weight_at(&c, color) > 0.0);
+                }
+            }
+        }
+    }
+}
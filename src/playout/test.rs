@@ -28,6 +28,7 @@ use config::Config;
 use patterns::Matcher;
 use ruleset::KgsChinese;
 use super::Playout;
+use super::Policy;
 
 use rand::weak_rng;
 use std::sync::Arc;
@@ -38,7 +39,14 @@ fn config() -> Arc<Config> {
 }
 
 fn playout(matcher: Arc<Matcher>) -> Playout {
-    Playout::new(config(), matcher)
+    Playout::new(config(), matcher).unwrap()
+}
+
+fn pseudo_liberty_playout(matcher: Arc<Matcher>) -> Playout {
+    let mut config = Config::default();
+    config.playout.policy = Policy::Light;
+    config.playout.use_pseudo_liberty_board = true;
+    Playout::new(Arc::new(config), matcher).unwrap()
 }
 
 #[test]
@@ -50,9 +58,70 @@ fn should_add_the_passed_moves_as_the_first_move() {
     assert_eq!(Play(Black, 1, 1), result.moves()[0]);
 }
 
+#[test]
+fn pseudo_liberty_playouts_add_the_passed_move_as_the_first_move() {
+    let mut board = Board::new(9, 6.5, KgsChinese);
+    let playout = pseudo_liberty_playout(Arc::new(Matcher::new()));
+    let mut rng = weak_rng();
+    let result = playout.run(&mut board, Some(&Play(Black, 1, 1)), &mut rng);
+    assert_eq!(Play(Black, 1, 1), result.moves()[0]);
+}
+
+fn policy_playout(policy: Policy, matcher: Arc<Matcher>) -> Playout {
+    let mut config = Config::default();
+    config.playout.policy = policy;
+    Playout::new(Arc::new(config), matcher).unwrap()
+}
+
+fn softmax_playout(matcher: Arc<Matcher>) -> Playout {
+    policy_playout(Policy::Softmax, matcher)
+}
+
+#[test]
+fn softmax_playouts_add_the_passed_move_as_the_first_move() {
+    let mut board = Board::new(9, 6.5, KgsChinese);
+    let playout = softmax_playout(Arc::new(Matcher::new()));
+    let mut rng = weak_rng();
+    let result = playout.run(&mut board, Some(&Play(Black, 1, 1)), &mut rng);
+    assert_eq!(Play(Black, 1, 1), result.moves()[0]);
+}
+
+#[test]
+fn softmax_playouts_only_play_legal_moves() {
+    let mut board = Board::new(9, 6.5, KgsChinese);
+    let playout = softmax_playout(Arc::new(Matcher::new()));
+    let mut rng = weak_rng();
+    let result = playout.run(&mut board.clone(), None, &mut rng);
+    for &m in result.moves().iter() {
+        assert!(board.is_legal(m).is_ok());
+        board.play_legal_move(m);
+    }
+}
+
+#[test]
+fn all_policies_only_play_legal_moves() {
+    for &policy in [Policy::Cascade, Policy::Light, Policy::Random, Policy::Softmax].iter() {
+        let mut board = Board::new(9, 6.5, KgsChinese);
+        let playout = policy_playout(policy, Arc::new(Matcher::new()));
+        let mut rng = weak_rng();
+        let result = playout.run(&mut board.clone(), None, &mut rng);
+        for &m in result.moves().iter() {
+            assert!(board.is_legal(m).is_ok());
+            board.play_legal_move(m);
+        }
+    }
+}
+
+#[test]
+fn the_pseudo_liberty_board_requires_the_light_policy() {
+    let mut config = Config::default();
+    config.playout.use_pseudo_liberty_board = true;
+    assert!(Playout::new(Arc::new(config), Arc::new(Matcher::new())).is_err());
+}
+
 #[test]
 fn max_moves() {
-    assert_eq!(1083, playout(Arc::new(Matcher::new())).max_moves(19));
+    assert_eq!(1083, playout(Arc::new(Matcher::new())).max_moves(361));
 }
 
 #[bench]
@@ -90,3 +159,61 @@ fn playout_19x19(b: &mut Bencher) {
         playout.run(&mut b, Some(&Play(Black, 1, 1)), &mut rng)
     });
 }
+
+#[bench]
+fn softmax_playout_09x09(b: &mut Bencher) {
+    let board = Board::new(9, 6.5, KgsChinese);
+    let playout = softmax_playout(Arc::new(Matcher::new()));
+    let mut rng = weak_rng();
+    b.iter(|| {
+        let mut b = board.clone();
+        playout.run(&mut b, Some(&Play(Black, 1, 1)), &mut rng)
+    });
+}
+
+// Divide 10^9 by the ns/iter reported for these benchmarks to get
+// the playouts per second. Compare the pseudo liberty playouts with
+// the light playouts on the normal board.
+#[bench]
+fn light_playout_09x09(b: &mut Bencher) {
+    let board = Board::new(9, 6.5, KgsChinese);
+    let playout = policy_playout(Policy::Light, Arc::new(Matcher::new()));
+    let mut rng = weak_rng();
+    b.iter(|| {
+        let mut b = board.clone();
+        playout.run(&mut b, Some(&Play(Black, 1, 1)), &mut rng)
+    });
+}
+
+#[bench]
+fn light_playout_19x19(b: &mut Bencher) {
+    let board = Board::new(19, 6.5, KgsChinese);
+    let playout = policy_playout(Policy::Light, Arc::new(Matcher::new()));
+    let mut rng = weak_rng();
+    b.iter(|| {
+        let mut b = board.clone();
+        playout.run(&mut b, Some(&Play(Black, 1, 1)), &mut rng)
+    });
+}
+
+#[bench]
+fn pseudo_liberty_playout_09x09(b: &mut Bencher) {
+    let board = Board::new(9, 6.5, KgsChinese);
+    let playout = pseudo_liberty_playout(Arc::new(Matcher::new()));
+    let mut rng = weak_rng();
+    b.iter(|| {
+        let mut b = board.clone();
+        playout.run(&mut b, Some(&Play(Black, 1, 1)), &mut rng)
+    });
+}
+
+#[bench]
+fn pseudo_liberty_playout_19x19(b: &mut Bencher) {
+    let board = Board::new(19, 6.5, KgsChinese);
+    let playout = pseudo_liberty_playout(Arc::new(Matcher::new()));
+    let mut rng = weak_rng();
+    b.iter(|| {
+        let mut b = board.clone();
+        playout.run(&mut b, Some(&Play(Black, 1, 1)), &mut rng)
+    });
+}
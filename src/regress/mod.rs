@@ -0,0 +1,253 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+// Runs regression suites in the .tst format used by GNU Go and
+// Fuego. A suite is a list of GTP commands. A numbered command that
+// is followed by a line like
+//
+//     #? [C3|D4]
+//
+// is a test. The part in brackets is a regular expression that has
+// to match the whole answer. A leading ! negates it and a trailing *
+// marks a test that is known to fail. The files of loadsgf commands
+// are relative to the directory of the suite.
+
+use config::Config;
+use engine;
+use gtp::Command;
+use gtp::GTPInterpreter;
+use gtp::driver::answer;
+use patterns::Matcher;
+
+use regex::Regex;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+use time::precise_time_ns;
+
+mod test;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Passed,
+    Failed,
+    ExpectedFailure,
+    UnexpectedPass,
+}
+
+impl Outcome {
+
+    pub fn is_failure(&self) -> bool {
+        match *self {
+            Outcome::Failed | Outcome::UnexpectedPass => true,
+            _ => false
+        }
+    }
+
+}
+
+#[derive(Debug)]
+pub struct Expectation {
+    expected_to_fail: bool,
+    negated: bool,
+    pattern: Regex,
+    text: String,
+}
+
+impl Expectation {
+
+    // Parses the part of the line after the #?
+    pub fn parse(s: &str) -> Result<Expectation, String> {
+        let s = s.trim();
+        let (start, end) = match (s.find('['), s.rfind(']')) {
+            (Some(start), Some(end)) if start < end => (start, end),
+            _ => return Err(format!("Expected an answer in brackets: {}", s))
+        };
+        let inside = &s[start+1..end];
+        let negated = inside.starts_with("!");
+        let pattern = if negated { &inside[1..] } else { inside };
+        let regex = match Regex::new(&format!("^(?:{})$", pattern)) {
+            Ok(regex) => regex,
+            Err(e) => return Err(format!("Invalid expected answer {}: {}", s, e))
+        };
+        Ok(Expectation {
+            expected_to_fail: s[end+1..].trim().starts_with("*"),
+            negated: negated,
+            pattern: regex,
+            text: String::from(&s[start..end+1]),
+        })
+    }
+
+    // Commands that fail never match.
+    pub fn matches(&self, answer: &Result<String, String>) -> bool {
+        match *answer {
+            Ok(ref s) => self.pattern.is_match(s.trim()) != self.negated,
+            Err(_) => false
+        }
+    }
+
+    pub fn outcome(&self, answer: &Result<String, String>) -> Outcome {
+        match (self.matches(answer), self.expected_to_fail) {
+            (true, false) => Outcome::Passed,
+            (false, false) => Outcome::Failed,
+            (false, true) => Outcome::ExpectedFailure,
+            (true, true) => Outcome::UnexpectedPass,
+        }
+    }
+
+}
+
+pub struct TestResult {
+    pub answer: Result<String, String>,
+    pub command: String,
+    pub expected: String,
+    pub file: String,
+    pub id: u32,
+    pub outcome: Outcome,
+    // In milliseconds
+    pub time: u64,
+}
+
+impl fmt::Display for TestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let outcome = match self.outcome {
+            Outcome::Passed => "passed",
+            Outcome::Failed => "FAILED",
+            Outcome::ExpectedFailure => "failed (expected)",
+            Outcome::UnexpectedPass => "PASSED (unexpected)",
+        };
+        let answer = match self.answer {
+            Ok(ref s) => s.clone(),
+            Err(ref e) => format!("? {}", e),
+        };
+        write!(f, "{}:{} {} ({}ms): {} answered {}, expected {}",
+               self.file, self.id, outcome, self.time, self.command, answer, self.expected)
+    }
+}
+
+pub struct Regression {
+    config: Arc<Config>,
+    matcher: Arc<Matcher>,
+}
+
+impl Regression {
+
+    pub fn new(config: Arc<Config>, matcher: Arc<Matcher>) -> Regression {
+        Regression {
+            config: config,
+            matcher: matcher,
+        }
+    }
+
+    pub fn run_file(&self, path: &Path) -> Result<Vec<TestResult>, String> {
+        let mut contents = String::new();
+        match File::open(path).and_then(|mut file| file.read_to_string(&mut contents)) {
+            Ok(_) => {},
+            Err(e) => return Err(format!("Can't read {}: {}", path.display(), e))
+        }
+        let dir = path.parent().unwrap_or(Path::new(""));
+        self.run(&format!("{}", path.display()), dir, &contents)
+    }
+
+    // Every suite starts with a new engine.
+    pub fn run(&self, file: &str, dir: &Path, suite: &str) -> Result<Vec<TestResult>, String> {
+        let engine = try!(engine::factory(self.config.clone(), self.matcher.clone()));
+        let mut interpreter = GTPInterpreter::new(self.config.clone(), engine, None);
+        let mut results = vec!();
+        // The last numbered command that may be followed by an
+        // expected answer.
+        let mut last = None;
+        for (line_number, line) in suite.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("#?") {
+                if let Some((id, command, answer, time)) = last.take() {
+                    let (expected, outcome) = match Expectation::parse(&trimmed[2..]) {
+                        Ok(expectation) => (expectation.text.clone(), expectation.outcome(&answer)),
+                        Err(e) => (format!("line {}: {}", line_number + 1, e), Outcome::Failed)
+                    };
+                    results.push(TestResult {
+                        answer: answer,
+                        command: command,
+                        expected: expected,
+                        file: String::from(file),
+                        id: id,
+                        outcome: outcome,
+                        time: time,
+                    });
+                }
+                continue;
+            }
+            let started_at = precise_time_ns();
+            let (id, command) = interpreter.read_with_id(&resolve_paths(line, dir));
+            let time = (precise_time_ns() - started_at) / 1000000;
+            let quit = match command {
+                Command::Quit => true,
+                _             => false
+            };
+            if let Some(answer) = answer(command) {
+                last = id.map(|id| (id, String::from(trimmed), answer, time));
+            }
+            if quit {
+                break;
+            }
+        }
+        interpreter.quit();
+        Ok(results)
+    }
+
+}
+
+// Makes the file of a loadsgf command relative to dir. Absolute paths
+// are kept as they are.
+pub fn resolve_paths(line: &str, dir: &Path) -> String {
+    let mut words: Vec<String> = line.split_whitespace().map(String::from).collect();
+    let command = match words.first().map(|word| word.parse::<u32>()) {
+        Some(Ok(_)) => 1,
+        _           => 0
+    };
+    if words.len() > command + 1 && words[command] == "loadsgf" {
+        words[command + 1] = format!("{}", dir.join(&words[command + 1]).display());
+        words.join(" ")
+    } else {
+        String::from(line)
+    }
+}
+
+// One line per test that didn't go as expected followed by a summary.
+pub fn report(results: &[TestResult]) -> String {
+    let mut lines: Vec<String> = results.iter()
+        .filter(|result| result.outcome != Outcome::Passed)
+        .map(|result| format!("{}", result))
+        .collect();
+    let count = |outcome| results.iter().filter(|result| result.outcome == outcome).count();
+    let time = results.iter().fold(0, |sum, result| sum + result.time);
+    lines.push(format!("{} tests: {} passed, {} failed, {} unexpected passes, {} expected failures ({}ms)",
+                       results.len(),
+                       count(Outcome::Passed),
+                       count(Outcome::Failed),
+                       count(Outcome::UnexpectedPass),
+                       count(Outcome::ExpectedFailure),
+                       time));
+    lines.join("\n")
+}
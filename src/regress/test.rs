@@ -0,0 +1,102 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use config::Config;
+use patterns::Matcher;
+use super::Expectation;
+use super::Outcome;
+use super::Regression;
+use super::report;
+use super::resolve_paths;
+
+use std::path::Path;
+use std::sync::Arc;
+
+fn regression() -> Regression {
+    Regression::new(Arc::new(Config::default()), Arc::new(Matcher::new()))
+}
+
+#[test]
+fn an_expectation_matches_the_whole_answer() {
+    let expectation = Expectation::parse(" [C3|D4]").unwrap();
+    assert!(expectation.matches(&Ok(String::from("C3"))));
+    assert!(expectation.matches(&Ok(String::from("D4"))));
+    assert!(!expectation.matches(&Ok(String::from("C33"))));
+    assert!(!expectation.matches(&Err(String::from("C3"))));
+}
+
+#[test]
+fn an_expectation_can_be_negated() {
+    let expectation = Expectation::parse("[!pass]").unwrap();
+    assert!(expectation.matches(&Ok(String::from("C3"))));
+    assert!(!expectation.matches(&Ok(String::from("pass"))));
+}
+
+#[test]
+fn a_star_marks_an_expected_failure() {
+    let expectation = Expectation::parse("[C3]*").unwrap();
+    assert_eq!(Outcome::ExpectedFailure, expectation.outcome(&Ok(String::from("D4"))));
+    assert_eq!(Outcome::UnexpectedPass, expectation.outcome(&Ok(String::from("C3"))));
+}
+
+#[test]
+fn an_expectation_needs_brackets() {
+    assert!(Expectation::parse("C3").is_err());
+}
+
+#[test]
+fn run_checks_the_numbered_commands() {
+    let results = regression().run("inline", Path::new(""), "boardsize 9\n1 play b c3\n#? []\n2 play b c3\n#? []\n").unwrap();
+    assert_eq!(2, results.len());
+    assert_eq!(Outcome::Passed, results[0].outcome);
+    assert_eq!(Outcome::Failed, results[1].outcome);
+    assert_eq!(Err(String::from("illegal move")), results[1].answer);
+}
+
+#[test]
+fn run_file_reports_every_test() {
+    let results = regression().run_file(Path::new("fixtures/regress/life-and-death.tst")).unwrap();
+    let outcomes: Vec<(u32, Outcome)> = results.iter().map(|r| (r.id, r.outcome)).collect();
+    assert_eq!(vec!(
+        (1, Outcome::Passed),
+        (2, Outcome::Passed),
+        (3, Outcome::ExpectedFailure),
+        (4, Outcome::Passed),
+        (5, Outcome::Failed)), outcomes);
+    assert!(report(&results).contains("5 tests: 3 passed, 1 failed, 0 unexpected passes, 1 expected failures"));
+}
+
+#[test]
+fn run_file_fails_for_missing_files() {
+    assert!(regression().run_file(Path::new("fixtures/regress/missing.tst")).is_err());
+}
+
+#[test]
+fn loadsgf_paths_are_relative_to_the_suite() {
+    let dir = Path::new("fixtures/regress");
+    assert_eq!("loadsgf fixtures/regress/game.sgf 3", resolve_paths("loadsgf game.sgf 3", dir));
+    assert_eq!("1 loadsgf fixtures/regress/game.sgf", resolve_paths("1 loadsgf game.sgf", dir));
+    assert_eq!("loadsgf /tmp/game.sgf", resolve_paths("loadsgf /tmp/game.sgf", dir));
+    assert_eq!("1 group_status c9", resolve_paths("1 group_status c9", dir));
+}
@@ -21,8 +21,13 @@
  ************************************************************************/
 
 use config::Hint;
+pub use self::KoRule::PositionalSuperKo;
+pub use self::KoRule::SimpleKo;
+pub use self::KoRule::SituationalSuperKo;
+pub use self::Ruleset::Aga;
 pub use self::Ruleset::AnySizeTrompTaylor;
 pub use self::Ruleset::CGOS;
+pub use self::Ruleset::ChineseSimpleKo;
 pub use self::Ruleset::KgsChinese;
 pub use self::Ruleset::Minimal;
 
@@ -33,14 +38,36 @@ mod test;
 
 #[derive(Clone, Debug, Eq, PartialEq, Copy)]
 pub enum Ruleset {
+    Aga,
     AnySizeTrompTaylor,
     CGOS,
+    ChineseSimpleKo,
     KgsChinese,
     Minimal,
 }
 
+// Which repetitions are forbidden on top of the basic ko rule.
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum KoRule {
+    // A move may not recreate any earlier board position.
+    PositionalSuperKo,
+    // A move may not recreate an earlier board position with the
+    // same player to move.
+    SituationalSuperKo,
+    // Only the immediate recapture of a single stone is forbidden.
+    SimpleKo,
+}
+
 impl Ruleset {
 
+    pub fn ko_rule(&self) -> KoRule {
+        match *self {
+            Aga             => SituationalSuperKo,
+            ChineseSimpleKo => SimpleKo,
+            _ => PositionalSuperKo
+        }
+    }
+
     pub fn game_over_play(&self) -> bool {
         match *self {
             Minimal => true,
@@ -70,11 +97,13 @@ impl FromStr for Ruleset {
 
     fn from_str(s: &str) -> Result<Ruleset, Self::Err> {
         match s {
-            "tromp-taylor" => Ok(AnySizeTrompTaylor),
-            "cgos"         => Ok(CGOS),
-            "chinese"      => Ok(KgsChinese),
-            "minimal"      => Ok(Minimal),
-            _              => Err(format!("Unknown ruleset '{}'", s)),
+            "aga"               => Ok(Aga),
+            "tromp-taylor"      => Ok(AnySizeTrompTaylor),
+            "cgos"              => Ok(CGOS),
+            "chinese"           => Ok(KgsChinese),
+            "chinese-simple-ko" => Ok(ChineseSimpleKo),
+            "minimal"           => Ok(Minimal),
+            _                   => Err(format!("Unknown ruleset '{}'", s)),
         }
     }
 
@@ -84,8 +113,10 @@ impl fmt::Display for Ruleset {
 
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let s = match *self {
+            Aga => "aga",
             AnySizeTrompTaylor => "tromp-taylor",
             CGOS => "cgos",
+            ChineseSimpleKo => "chinese-simple-ko",
             KgsChinese => "chinese",
             Minimal => "minimal"
         };
@@ -96,7 +127,7 @@ impl fmt::Display for Ruleset {
 impl Hint for Ruleset {
 
     fn hint_str(&self) -> &'static str {
-        "cgos|chinese|tromp-taylor|minimal"
+        "aga|cgos|chinese|chinese-simple-ko|tromp-taylor|minimal"
     }
 
 }
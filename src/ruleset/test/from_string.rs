@@ -21,8 +21,10 @@
 
 #![cfg(test)]
 
+use ruleset::Aga;
 use ruleset::AnySizeTrompTaylor;
 use ruleset::CGOS;
+use ruleset::ChineseSimpleKo;
 use ruleset::KgsChinese;
 use ruleset::Minimal;
 use ruleset::Ruleset;
@@ -44,6 +46,16 @@ fn parses_chinese() {
     assert_eq!(Ok(KgsChinese), Ruleset::from_str("chinese"));
 }
 
+#[test]
+fn parses_aga() {
+    assert_eq!(Ok(Aga), Ruleset::from_str("aga"));
+}
+
+#[test]
+fn parses_chinese_simple_ko() {
+    assert_eq!(Ok(ChineseSimpleKo), Ruleset::from_str("chinese-simple-ko"));
+}
+
 #[test]
 fn parses_minimal() {
     assert_eq!(Ok(Minimal), Ruleset::from_str("minimal"));
@@ -22,10 +22,15 @@
 
 #![cfg(test)]
 
+use super::Aga;
 use super::AnySizeTrompTaylor;
 use super::CGOS;
+use super::ChineseSimpleKo;
 use super::KgsChinese;
 use super::Minimal;
+use super::PositionalSuperKo;
+use super::SimpleKo;
+use super::SituationalSuperKo;
 
 mod from_string;
 
@@ -88,3 +93,33 @@ fn minimal_allows_a_player_playing_twice() {
 fn minimal_allows_game_over_play() {
     assert_eq!(true, Minimal.game_over_play());
 }
+
+#[test]
+fn tromp_taylor_uses_positional_super_ko() {
+    assert_eq!(PositionalSuperKo, AnySizeTrompTaylor.ko_rule());
+}
+
+#[test]
+fn cgos_uses_positional_super_ko() {
+    assert_eq!(PositionalSuperKo, CGOS.ko_rule());
+}
+
+#[test]
+fn kgs_chinese_uses_positional_super_ko() {
+    assert_eq!(PositionalSuperKo, KgsChinese.ko_rule());
+}
+
+#[test]
+fn aga_uses_situational_super_ko() {
+    assert_eq!(SituationalSuperKo, Aga.ko_rule());
+}
+
+#[test]
+fn aga_forbids_suicide() {
+    assert_eq!(false, Aga.suicide_allowed());
+}
+
+#[test]
+fn chinese_simple_ko_only_uses_the_simple_ko_rule() {
+    assert_eq!(SimpleKo, ChineseSimpleKo.ko_rule());
+}
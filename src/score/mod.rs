@@ -34,14 +34,20 @@ use std::fmt;
 mod territory;
 mod test;
 
+/// The area score of a position: the stones and the empty points
+/// surrounded by only one color, with komi added for white.
 pub struct Score {
     black_stones: usize,
     komi:         f32,
+    seki:         Vec<Coord>,
     white_stones: usize,
 }
 
 impl Score {
 
+    /// Counts the stones and the surrounded empty points of the
+    /// board.
+    //
     // Figure out which methods board needs to provide for this to
     // work so that we can create a trait with just those methods for
     // our test purposes.
@@ -53,10 +59,31 @@ impl Score {
         Score {
             black_stones: bs,
             komi:         board.komi(),
+            seki:         Vec::new(),
             white_stones: ws
         }
     }
 
+    /// Also finds the stones in seki, which is too slow for the
+    /// playouts. The score itself is the same: all rulesets count the
+    /// area, so the stones in seki are alive and their shared
+    /// liberties are neutral as they touch both colors.
+    pub fn with_seki(board: &Board) -> Score {
+        let mut score = Score::new(board);
+        score.seki = board.seki_stones();
+        score
+    }
+
+    pub fn from_area(black: usize, white: usize, komi: f32) -> Score {
+        Score {
+            black_stones: black,
+            komi:         komi,
+            seki:         Vec::new(),
+            white_stones: white
+        }
+    }
+
+    /// The winner, `Empty` for a draw.
     pub fn color(&self) -> Color {
         let white_adjusted = self.white_stones as f32 + self.komi;
         if self.black_stones as f32 == white_adjusted {
@@ -68,8 +95,35 @@ impl Score {
         }
     }
 
+    pub fn seki_stones(&self) -> &Vec<Coord> {
+        &self.seki
+    }
+
+    /// Positive if black is ahead and negative if white is ahead.
+    pub fn margin(&self) -> f32 {
+        self.black_stones as f32 - (self.white_stones as f32 + self.komi)
+    }
+
+    /// The color each point is counted for, in the same order as
+    /// `Board::points()`. Neutral points are Empty.
+    pub fn ownership(board: &Board) -> Vec<Color> {
+        let mut owners: Vec<Color> = board.points().iter().map(|point| point.color).collect();
+        let mut empty_intersections = board.vacant().clone();
+        while empty_intersections.len() > 0 {
+            let territory = Score::build_territory_chain(empty_intersections[0], board);
+            for coord in empty_intersections.iter().filter(|coord| territory.contains(coord)) {
+                owners[coord.to_index(board.width())] = territory.color();
+            }
+            empty_intersections = empty_intersections
+                .into_iter()
+                .filter(|coord| !territory.contains(coord))
+                .collect();
+        }
+        owners
+    }
+
     fn score(&self) -> f32 {
-        (self.black_stones as f32 - (self.white_stones as f32 + self.komi)).abs()
+        self.margin().abs()
     }
 
     fn score_tt(board: &Board) -> (usize, usize) {
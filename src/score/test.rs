@@ -25,10 +25,16 @@
 
 use board::Black;
 use board::Board;
+use board::Coord;
+use board::Empty;
 use board::Pass;
 use board::Play;
 use board::White;
 use ruleset::Minimal;
+use sgf::Parser;
+use super::Score;
+
+use std::path::Path;
 
 #[test]
 fn counting_simple_case() {
@@ -103,3 +109,40 @@ fn counting_with_neutral_points() {
     assert_eq!(White, score.color());
     assert_eq!("W+22.5", format!("{}", score));
 }
+
+#[test]
+fn shared_liberties_of_a_seki_are_neutral() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/semeai/seki.sgf")).unwrap();
+    let board = parser.game().unwrap().board();
+
+    let score = Score::with_seki(&board);
+    assert_eq!(75, score.black_stones);
+    assert_eq!(4, score.white_stones);
+    assert_eq!(6, score.seki_stones().len());
+    assert_eq!(0, board.score().seki_stones().len());
+}
+
+#[test]
+fn the_margin_is_negative_if_white_wins() {
+    let score = Score::from_area(40, 41, 6.5);
+    assert_eq!(-7.5, score.margin());
+}
+
+#[test]
+fn ownership_counts_stones_and_territory() {
+    let mut b = Board::new(5, 6.5, Minimal);
+
+    b.play(Play(Black, 2, 1));
+    b.play(Play(White, 3, 1));
+    b.play(Play(Black, 2, 2));
+    b.play(Play(White, 3, 2));
+    b.play(Play(Black, 1, 2));
+    b.play(Play(White, 2, 3));
+
+    let ownership = Score::ownership(&b);
+    assert_eq!(25, ownership.len());
+    assert_eq!(Black, ownership[Coord::new(1, 1).to_index(5)]);
+    assert_eq!(Black, ownership[Coord::new(2, 1).to_index(5)]);
+    assert_eq!(White, ownership[Coord::new(3, 1).to_index(5)]);
+    assert_eq!(Empty, ownership[Coord::new(5, 5).to_index(5)]);
+}
@@ -0,0 +1,354 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+// Plays games between two configurations of the engine without
+// going through GTP, or against an external engine that is spoken to
+// over GTP. Our own Game is the referee in both cases.
+
+use board::Black;
+use board::Color;
+use board::Empty;
+use board::Move;
+use board::White;
+use config::Config;
+use engine;
+use engine::Budget;
+use engine::EngineController;
+use game::Game;
+use gtp::client::Client;
+use patterns::Matcher;
+use ruleset::KgsChinese;
+use ruleset::Ruleset;
+use sgf::Writer;
+
+use std::cmp::max;
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc::channel;
+
+mod test;
+
+pub enum Program {
+    Engine(Arc<Config>),
+    // The command line that starts a GTP engine.
+    Gtp(String),
+}
+
+pub struct Player {
+    pub name: String,
+    pub program: Program,
+}
+
+impl Player {
+
+    pub fn new(name: &str, config: Arc<Config>) -> Player {
+        Player {
+            name: String::from(name),
+            program: Program::Engine(config),
+        }
+    }
+
+    pub fn gtp(name: &str, command: &str) -> Player {
+        Player {
+            name: String::from(name),
+            program: Program::Gtp(String::from(command)),
+        }
+    }
+
+}
+
+// A player during a single game.
+enum Contestant {
+    Engine(EngineController<'static>),
+    Gtp(Client),
+}
+
+pub struct GameResult {
+    // The index of the player that played black.
+    pub black: usize,
+    pub result: String,
+    pub sgf: String,
+    pub winner: Color,
+}
+
+impl GameResult {
+
+    // The index of the winner, or None for a draw.
+    pub fn winning_player(&self) -> Option<usize> {
+        match self.winner {
+            Black => Some(self.black),
+            White => Some(1 - self.black),
+            Empty => None,
+        }
+    }
+
+}
+
+// The results from the point of view of the first player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchResult {
+    pub draws: usize,
+    pub losses: usize,
+    pub wins: usize,
+}
+
+impl MatchResult {
+
+    pub fn new() -> MatchResult {
+        MatchResult {
+            draws: 0,
+            losses: 0,
+            wins: 0,
+        }
+    }
+
+    pub fn add(&mut self, result: &GameResult) {
+        match result.winning_player() {
+            Some(0) => self.wins += 1,
+            Some(_) => self.losses += 1,
+            None => self.draws += 1,
+        }
+    }
+
+    pub fn games(&self) -> usize {
+        self.wins + self.losses + self.draws
+    }
+
+    // Draws count as half a win.
+    pub fn win_rate(&self) -> f32 {
+        if self.games() == 0 {
+            0.5
+        } else {
+            (self.wins as f32 + self.draws as f32 / 2.0) / self.games() as f32
+        }
+    }
+
+    // The 95% confidence interval of the win rate, using the normal
+    // approximation.
+    pub fn confidence_interval(&self) -> (f32, f32) {
+        let p = self.win_rate();
+        let error = 1.96 * (p * (1.0 - p) / max(self.games(), 1) as f32).sqrt();
+        ((p - error).max(0.0), (p + error).min(1.0))
+    }
+
+    pub fn elo(&self) -> f32 {
+        elo(self.win_rate())
+    }
+
+}
+
+impl fmt::Display for MatchResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (low, high) = self.confidence_interval();
+        write!(f, "{} wins, {} losses and {} draws in {} games: {:.1}% ({:.1}% - {:.1}%), {:+.0} Elo ({:+.0} - {:+.0})",
+               self.wins, self.losses, self.draws, self.games(),
+               self.win_rate() * 100.0, low * 100.0, high * 100.0,
+               self.elo(), elo(low), elo(high))
+    }
+}
+
+// The Elo difference that corresponds to the win rate. It's infinite
+// for a win rate of 0% or 100%.
+pub fn elo(win_rate: f32) -> f32 {
+    -400.0 * (1.0 / win_rate - 1.0).log10()
+}
+
+pub struct Match {
+    budget: Budget,
+    komi: f32,
+    matcher: Arc<Matcher>,
+    sgf_dir: Option<PathBuf>,
+    size: u8,
+    timeout: u32,
+}
+
+impl Match {
+
+    pub fn new(size: u8, komi: f32, budget: Budget, matcher: Arc<Matcher>) -> Match {
+        Match {
+            budget: budget,
+            komi: komi,
+            matcher: matcher,
+            sgf_dir: None,
+            size: size,
+            timeout: 60000,
+        }
+    }
+
+    pub fn save_sgfs(&mut self, dir: &Path) {
+        self.sgf_dir = Some(dir.to_path_buf());
+    }
+
+    // How long an external engine may take to answer a command
+    // before it loses the game. Defaults to one minute.
+    pub fn set_timeout(&mut self, ms: u32) {
+        self.timeout = ms;
+    }
+
+    // The players alternate colors, the first one starts with black.
+    // f is called after every game.
+    pub fn play<F: FnMut(usize, &GameResult)>(&self, players: &[Player; 2], games: usize, mut f: F) -> Result<MatchResult, String> {
+        let mut result = MatchResult::new();
+        for i in 0..games {
+            let black = i % 2;
+            let game_result = try!(self.play_game(players, black));
+            if let Some(ref dir) = self.sgf_dir {
+                let path = dir.join(format!("game-{:04}.sgf", i + 1));
+                let written = File::create(&path).and_then(|mut file| file.write_all(game_result.sgf.as_bytes()));
+                if let Err(e) = written {
+                    return Err(format!("Can't write {}: {}", path.display(), e));
+                }
+            }
+            result.add(&game_result);
+            f(i + 1, &game_result);
+        }
+        Ok(result)
+    }
+
+    // Fails only if an external engine can't be set up. All errors
+    // during the game make the player lose by forfeit.
+    pub fn play_game(&self, players: &[Player; 2], black: usize) -> Result<GameResult, String> {
+        let white = 1 - black;
+        let mut contestants = [
+            try!(self.contestant(&players[black])),
+            try!(self.contestant(&players[white]))
+        ];
+        let mut game = Game::new(self.size, self.komi, Self::ruleset(players));
+        let mut writer = Writer::new(self.size, self.size, self.komi);
+        writer.set_players(&players[black].name, &players[white].name);
+        let names = [&players[black].name, &players[white].name];
+        // Superko makes endless games impossible, but they could
+        // still take very long.
+        let max_moves = game.point_count() * 3;
+        let mut forfeited_by = Empty;
+        for _ in 0..max_moves {
+            if game.is_over() {
+                break;
+            }
+            let color = game.next_player();
+            let index = if color == Black { 0 } else { 1 };
+            let played = self.gen_move(&mut contestants[index], color, &game)
+                .and_then(|m| game.play(m).map(|g| (m, g)).map_err(|e| format!("{} played {}: {}", names[index], m.to_gtp(), e)));
+            match played {
+                Ok((m, g)) => {
+                    game = g;
+                    writer.add(m);
+                    if !m.is_resign() {
+                        if let Contestant::Gtp(ref mut client) = contestants[1 - index] {
+                            if let Err(e) = client.play(m) {
+                                log!("{}", e);
+                                forfeited_by = color.opposite();
+                                break;
+                            }
+                        }
+                    }
+                },
+                Err(e) => {
+                    log!("{}", e);
+                    forfeited_by = color;
+                    break;
+                }
+            }
+        }
+        for contestant in contestants.iter_mut() {
+            if let Contestant::Gtp(ref mut client) = *contestant {
+                client.quit();
+            }
+        }
+        let (winner, result) = if forfeited_by != Empty {
+            (forfeited_by.opposite(), format!("{}+F", Self::sgf_color(forfeited_by.opposite())))
+        } else if game.last_move().is_resign() {
+            (game.winner(), format!("{}+R", Self::sgf_color(game.winner())))
+        } else {
+            (game.winner(), format!("{}", game.score()))
+        };
+        writer.set_result(&result);
+        Ok(GameResult {
+            black: black,
+            result: result,
+            sgf: writer.to_string(),
+            winner: winner,
+        })
+    }
+
+    fn contestant(&self, player: &Player) -> Result<Contestant, String> {
+        match player.program {
+            Program::Engine(ref config) => Ok(Contestant::Engine(try!(self.controller(config)))),
+            Program::Gtp(ref command) => {
+                let mut client = try!(Client::spawn(command, self.timeout));
+                try!(client.new_game(self.size, self.komi));
+                if let Budget::Time(ms) = self.budget {
+                    try!(client.time_per_move(ms));
+                }
+                Ok(Contestant::Gtp(client))
+            }
+        }
+    }
+
+    fn gen_move(&self, contestant: &mut Contestant, color: Color, game: &Game) -> Result<Move, String> {
+        match *contestant {
+            Contestant::Engine(ref mut controller) => {
+                let (send_move, receive_move) = channel();
+                match self.budget {
+                    Budget::Time(ms) => controller.run_with_time_limit(color, game, ms, send_move),
+                    Budget::Playouts(_) => controller.run_without_time_limit(color, game, send_move),
+                };
+                Ok(receive_move.recv().unwrap())
+            },
+            Contestant::Gtp(ref mut client) => client.genmove(color)
+        }
+    }
+
+    fn controller(&self, config: &Arc<Config>) -> Result<EngineController<'static>, String> {
+        try!(self.budget.check());
+        let config = match self.budget {
+            Budget::Playouts(playouts) => {
+                let mut config = (**config).clone();
+                config.uct.max_playouts = playouts;
+                Arc::new(config)
+            },
+            Budget::Time(_) => config.clone()
+        };
+        let engine = try!(engine::factory(config.clone(), self.matcher.clone()));
+        Ok(EngineController::new(config, engine, None))
+    }
+
+    // External engines don't tell us their rules, so the rules of
+    // the first engine of ours are used.
+    fn ruleset(players: &[Player; 2]) -> Ruleset {
+        for player in players.iter() {
+            if let Program::Engine(ref config) = player.program {
+                return config.ruleset;
+            }
+        }
+        KgsChinese
+    }
+
+    fn sgf_color(color: Color) -> &'static str {
+        if color == Black { "B" } else { "W" }
+    }
+
+}
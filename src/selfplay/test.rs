@@ -0,0 +1,121 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use board::Black;
+use board::Color;
+use board::Empty;
+use board::White;
+use config::Config;
+use engine::Budget;
+use patterns::Matcher;
+use sgf::Parser;
+use super::GameResult;
+use super::Match;
+use super::MatchResult;
+use super::Player;
+use super::elo;
+
+use std::sync::Arc;
+
+fn game_result(black: usize, winner: Color) -> GameResult {
+    GameResult {
+        black: black,
+        result: String::new(),
+        sgf: String::new(),
+        winner: winner,
+    }
+}
+
+#[test]
+fn the_result_is_counted_for_the_first_player() {
+    let mut result = MatchResult::new();
+    result.add(&game_result(0, Black));
+    result.add(&game_result(1, Black));
+    result.add(&game_result(1, White));
+    result.add(&game_result(0, Empty));
+    assert_eq!(MatchResult { draws: 1, losses: 1, wins: 2 }, result);
+    assert_eq!(0.625, result.win_rate());
+}
+
+#[test]
+fn the_confidence_interval_shrinks_with_more_games() {
+    let few = MatchResult { draws: 0, losses: 5, wins: 5 };
+    let many = MatchResult { draws: 0, losses: 500, wins: 500 };
+    let (few_low, few_high) = few.confidence_interval();
+    let (many_low, many_high) = many.confidence_interval();
+    assert!(few_low < many_low);
+    assert!(few_high > many_high);
+    assert!(many_low < 0.5 && 0.5 < many_high);
+}
+
+#[test]
+fn elo_is_zero_for_even_players() {
+    assert_eq!(0.0, elo(0.5));
+    assert!((elo(0.75) - 190.8).abs() < 0.1);
+    assert!(elo(0.25) < 0.0);
+}
+
+#[test]
+fn play_alternates_the_colors() {
+    let config = Arc::new(Config::default());
+    let players = [Player::new("A", config.clone()), Player::new("B", config)];
+    let selfplay = Match::new(5, 0.5, Budget::Playouts(20), Arc::new(Matcher::new()));
+    let mut blacks = vec!();
+    let result = selfplay.play(&players, 2, |_, game| {
+        blacks.push(game.black);
+        assert!(Parser::new(game.sgf.clone()).game().is_ok());
+    }).unwrap();
+    assert_eq!(vec!(0, 1), blacks);
+    assert_eq!(2, result.games());
+}
+
+#[test]
+fn an_external_engine_that_rejects_a_legal_move_forfeits() {
+    let players = [
+        Player::new("A", Arc::new(Config::default())),
+        Player::gtp("B", "sh fixtures/gtp/pass.sh")
+    ];
+    let selfplay = Match::new(5, 0.5, Budget::Playouts(20), Arc::new(Matcher::new()));
+    let result = selfplay.play_game(&players, 0).unwrap();
+    assert_eq!("B+F", result.result);
+    assert_eq!(Some(0), result.winning_player());
+}
+
+#[test]
+fn play_fails_if_an_external_engine_can_not_be_started() {
+    let players = [
+        Player::new("A", Arc::new(Config::default())),
+        Player::gtp("B", "does-not-exist-gtp")
+    ];
+    let selfplay = Match::new(5, 0.5, Budget::Playouts(20), Arc::new(Matcher::new()));
+    assert!(selfplay.play(&players, 1, |_, _| {}).is_err());
+}
+
+#[test]
+fn play_fails_for_a_budget_of_zero_playouts() {
+    let config = Arc::new(Config::default());
+    let players = [Player::new("A", config.clone()), Player::new("B", config)];
+    let selfplay = Match::new(5, 0.5, Budget::Playouts(0), Arc::new(Matcher::new()));
+    assert!(selfplay.play_game(&players, 0).is_err());
+}
@@ -19,7 +19,42 @@
  *                                                                      *
  ************************************************************************/
 
+pub use self::parser::ParseError;
 pub use self::parser::Parser;
+pub use self::writer::Writer;
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
 
 pub mod parser;
-mod test;
+pub mod writer;
+pub mod test;
+
+// Calls f for every *.sgf file in the directory (in the order of
+// their names) and returns the number of games f accepted. Files that
+// can't be read or that f rejects are skipped.
+pub fn for_each_game<F>(dir: &Path, mut f: F) -> Result<usize, String>
+    where F: FnMut(&Parser) -> Result<(), ParseError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => return Err(format!("Can't read {}: {}", dir.display(), e))
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map(|ext| ext == "sgf").unwrap_or(false))
+        .collect();
+    paths.sort();
+    let mut games = 0;
+    for path in paths {
+        match Parser::from_path(&path) {
+            Ok(parser) => match f(&parser) {
+                Ok(_) => games += 1,
+                Err(e) => log!("Skipping {}: {:?}", path.display(), e)
+            },
+            Err(e) => log!("Skipping {}: {}", path.display(), e)
+        }
+    }
+    Ok(games)
+}
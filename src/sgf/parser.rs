@@ -20,22 +20,50 @@
  *                                                                      *
  ************************************************************************/
 use std::io::prelude::*;
+use std::fmt;
 use std::fs::File;
 use std::path::Path;
+use std::usize;
 use board::Black;
+use board::Board;
 use board::Color;
 use board::Empty;
 use board::IllegalMove;
+use board::Move;
 use board::Pass;
 use board::Play;
 use board::White;
 use game::Game;
 use ruleset::Minimal;
 
+/// Reads the main line of an SGF game record: the board size, komi,
+/// setup stones and moves.
 pub struct Parser {
     sgf: String
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseError {
+    IllegalMove(IllegalMove),
+    InvalidSize(String),
+}
+
+impl From<IllegalMove> for ParseError {
+    fn from(e: IllegalMove) -> ParseError {
+        ParseError::IllegalMove(e)
+    }
+}
+
+impl fmt::Display for ParseError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::IllegalMove(ref e) => write!(f, "illegal move: {}", e),
+            ParseError::InvalidSize(ref s) => write!(f, "invalid board size: {}", s),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Property<'a> {
     name: &'a str,
@@ -50,16 +78,18 @@ impl<'a> Property<'a> {
 
     // SGF counts from top to bottom, while we count from bottom to
     // top (and we start at 1).
-    fn row(&self, size: u8) -> u8 {
-        size - self.char_to_int(self.val.as_bytes()[1]) + 1
+    fn row(&self, height: u8) -> u8 {
+        height - self.char_to_int(self.val.as_bytes()[1]) + 1
     }
 
     fn char_to_int(&self, c: u8) -> u8 {
         c - ('a' as u8) + 1
     }
 
-    fn is_pass(&self) -> bool {
-        self.val == ""
+    // FF[3] used "tt" for a pass, which is only unambiguous on
+    // boards up to 19x19.
+    fn is_pass(&self, width: u8, height: u8) -> bool {
+        self.val == "" || (self.val == "tt" && width <= 19 && height <= 19)
     }
 
     fn is_move(&self) -> bool {
@@ -69,6 +99,10 @@ impl<'a> Property<'a> {
         }
     }
 
+    fn is_setup(&self) -> bool {
+        self.name == "AB" || self.name == "AW"
+    }
+
     fn color(&self) -> Color {
         match self.name {
             "AB" | "B" => Black,
@@ -77,24 +111,22 @@ impl<'a> Property<'a> {
         }
     }
 
-    fn play(&self, game: Game) -> Result<Game, IllegalMove> {
-        if self.is_move() {
-            if self.is_pass() {
-                game.play(Pass(self.color()))
-            } else {
-                game.play(Play(self.color(), self.col(), self.row(game.size())))
-            }
+    fn to_move(&self, width: u8, height: u8) -> Move {
+        if self.is_pass(width, height) {
+            Pass(self.color())
         } else {
-            Ok(game)
+            Play(self.color(), self.col(), self.row(height))
         }
     }
 }
 
 impl Parser {
+    /// A parser for the contents of an SGF file.
     pub fn new(sgf: String) -> Parser {
         Parser {sgf: sgf}
     }
 
+    /// A parser for the SGF file at `path`.
     pub fn from_path(path: &Path) -> Result<Parser, ::std::io::Error> {
     	match File::open(&path) {
     		Ok(mut file) => {
@@ -108,24 +140,67 @@ impl Parser {
 		}
 	}
 
-    pub fn game(&self) -> Result<Game, IllegalMove> {
-        let mut game = Game::new(self.size(), self.komi(), Minimal);
+    /// The game at the end of the record. It uses the `Minimal`
+    /// ruleset, as game records may contain any move.
+    pub fn game(&self) -> Result<Game, ParseError> {
+        self.replay(|_, _| {})
+    }
+
+    /// Plays through the game and calls f with the position before
+    /// each move (but not for the setup stones).
+    pub fn replay<F: FnMut(&Game, &Move)>(&self, f: F) -> Result<Game, ParseError> {
+        self.replay_before(usize::MAX, f)
+    }
+
+    /// The position before the given move is played, counting from 1
+    /// and not counting the setup stones. This is what the optional
+    /// move number of the GTP loadsgf command means.
+    pub fn game_before(&self, move_number: usize) -> Result<Game, ParseError> {
+        self.replay_before(move_number, |_, _| {})
+    }
+
+    fn replay_before<F: FnMut(&Game, &Move)>(&self, move_number: usize, mut f: F) -> Result<Game, ParseError> {
+        let (width, height) = try!(self.size());
+        let mut game = Game::rectangular(width, height, self.komi(), Minimal);
         let props = self.tokenize();
-        for prop in props.iter() {
-            match prop.play(game) {
-                Ok(g) => {
-                    game = g;
-                },
-                Err(e) => return Err(e)
+        let mut played = 0;
+        for prop in props.iter().filter(|p| p.is_move()) {
+            let m = prop.to_move(width, height);
+            if !prop.is_setup() {
+                played += 1;
+                if played >= move_number {
+                    break;
+                }
+                f(&game, &m);
             }
+            game = try!(game.play(m));
         }
         Ok(game)
     }
 
-    fn size(&self) -> u8 {
+    // The size is either given as SZ[19] or as SZ[19:13] for
+    // rectangular boards (columns first).
+    fn size(&self) -> Result<(u8, u8), ParseError> {
         let props = self.tokenize();
-        let prop = props.iter().find(|p| p.name == "SZ");
-        prop.and_then(|p| p.val.parse().ok()).unwrap_or(19)
+        match props.iter().find(|p| p.name == "SZ") {
+            Some(prop) => match Self::parse_size(prop.val) {
+                Some((width, height)) if Board::is_valid_size(width, height) => Ok((width, height)),
+                _ => Err(ParseError::InvalidSize(String::from(prop.val)))
+            },
+            None => Ok((19, 19))
+        }
+    }
+
+    fn parse_size(val: &str) -> Option<(u8, u8)> {
+        let parts: Vec<&str> = val.split(':').map(|s| s.trim()).collect();
+        match parts.len() {
+            1 => parts[0].parse().ok().map(|size| (size, size)),
+            2 => match (parts[0].parse(), parts[1].parse()) {
+                (Ok(width), Ok(height)) => Some((width, height)),
+                _ => None
+            },
+            _ => None
+        }
     }
 
     fn komi(&self) -> f32 {
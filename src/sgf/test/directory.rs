@@ -0,0 +1,39 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use sgf::for_each_game;
+
+use std::path::Path;
+
+#[test]
+fn visits_every_game_in_the_directory() {
+    let mut visited = 0;
+    let games = for_each_game(Path::new("fixtures/training"), |_| { visited += 1; Ok(()) });
+    assert_eq!(Ok(2), games);
+    assert_eq!(2, visited);
+}
+
+#[test]
+fn fails_for_a_missing_directory() {
+    assert!(for_each_game(Path::new("fixtures/missing"), |_| Ok(())).is_err());
+}
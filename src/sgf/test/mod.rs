@@ -19,4 +19,20 @@
  *                                                                      *
  ************************************************************************/
 
+#![cfg(test)]
+
+use board::Board;
+use super::Parser;
+
+use std::path::Path;
+
+mod directory;
 mod parser;
+mod writer;
+
+// The board at the end of fixtures/sgf/<name>, for the tests of all
+// modules.
+pub fn board_from_sgf(name: &str) -> Board {
+    let path = format!("fixtures/sgf/{}", name);
+    Parser::from_path(Path::new(&path)).unwrap().game().unwrap().board()
+}
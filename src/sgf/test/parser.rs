@@ -22,7 +22,11 @@
 #![cfg(test)]
 use board::Black;
 use board::Coord;
+use board::Empty;
+use board::Pass;
+use board::Play;
 use board::White;
+use sgf::parser::ParseError;
 use sgf::parser::Parser;
 
 use std::path::Path;
@@ -31,7 +35,33 @@ use std::path::Path;
 fn sets_the_board_size_from_sgf() {
     let parser = Parser::from_path(Path::new("fixtures/sgf/empty.sgf")).unwrap();
     let game  = parser.game().unwrap();
-    assert_eq!(game.size(), 19);
+    assert_eq!(game.width(), 19);
+    assert_eq!(game.height(), 19);
+}
+
+#[test]
+fn sets_a_rectangular_board_size_from_sgf() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/rectangular.sgf")).unwrap();
+    let game  = parser.game().unwrap();
+    assert_eq!(game.width(), 13);
+    assert_eq!(game.height(), 9);
+    assert_eq!(game.board().color(&Coord::new(13, 9)), Black);
+    assert_eq!(game.board().color(&Coord::new(1, 1)), White);
+}
+
+#[test]
+fn play_moves_on_a_25x25_board() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/25x25.sgf")).unwrap();
+    let game  = parser.game().unwrap();
+    assert_eq!(game.board().color(&Coord::new(25, 1)), Black);
+    assert_eq!(game.board().color(&Coord::new(25, 25)), White);
+}
+
+#[test]
+fn tt_is_a_pass_on_19x19() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/tt-pass.sgf")).unwrap();
+    let game  = parser.game().unwrap();
+    assert_eq!(Pass(White), game.last_move());
 }
 
 #[test]
@@ -64,3 +94,45 @@ fn finished_game() {
     let game   = parser.game().unwrap();
     assert!(game.is_over());
 }
+
+#[test]
+fn replay_yields_the_moves_in_order() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/twomoves.sgf")).unwrap();
+    let mut moves = vec!();
+    parser.replay(|game, &m| moves.push((game.board().color(&Coord::new(4, 15)), m))).unwrap();
+    assert_eq!(moves, vec!(
+        (Empty, Play(Black, 4, 15)),
+        (Black, Play(White, 16, 7))));
+}
+
+#[test]
+fn replay_skips_the_handicap_stones() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/handicap.sgf")).unwrap();
+    let mut count = 0;
+    parser.replay(|_, _| count += 1).unwrap();
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn game_before_stops_before_the_given_move() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/twomoves.sgf")).unwrap();
+    let game = parser.game_before(2).unwrap();
+    assert_eq!(game.board().color(&Coord::new(4, 15)), Black);
+    assert_eq!(game.board().color(&Coord::new(16, 7)), Empty);
+    assert_eq!(game.next_player(), White);
+}
+
+#[test]
+fn game_before_keeps_the_handicap_stones() {
+    let parser = Parser::from_path(Path::new("fixtures/sgf/handicap.sgf")).unwrap();
+    let game = parser.game_before(1).unwrap();
+    assert_eq!(game.board().color(&Coord::new(4,4)), Black);
+}
+
+#[test]
+fn rejects_unsupported_board_sizes() {
+    for size in ["0", "40", "9:30", "nine"].iter() {
+        let parser = Parser::new(format!("(;GM[1]SZ[{}];B[aa])", size));
+        assert_eq!(Err(ParseError::InvalidSize(String::from(*size))), parser.game().map(|_| ()));
+    }
+}
@@ -0,0 +1,53 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+use board::Black;
+use board::Coord;
+use board::Pass;
+use board::Play;
+use board::White;
+use sgf::parser::Parser;
+use sgf::writer::Writer;
+
+#[test]
+fn the_parser_reads_back_what_the_writer_writes() {
+    let mut writer = Writer::new(13, 9, 7.5);
+    writer.add(Play(Black, 1, 1));
+    writer.add(Play(White, 13, 9));
+    writer.add(Pass(Black));
+    let game = Parser::new(writer.to_string()).game().unwrap();
+    assert_eq!(13, game.width());
+    assert_eq!(9, game.height());
+    assert_eq!(7.5, game.komi());
+    assert_eq!(Black, game.board().color(&Coord::new(1, 1)));
+    assert_eq!(White, game.board().color(&Coord::new(13, 9)));
+    assert_eq!(Pass(Black), game.last_move());
+}
+
+#[test]
+fn players_and_result_are_escaped() {
+    let mut writer = Writer::new(9, 9, 6.5);
+    writer.set_players("a]b", "c");
+    writer.set_result("B+R");
+    assert_eq!("(;GM[1]FF[4]CA[UTF-8]AP[Iomrascalai]SZ[9]KM[6.5]PB[a\\]b]PW[c]RE[B+R]\n)\n", writer.to_string());
+}
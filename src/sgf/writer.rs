@@ -0,0 +1,107 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use board::Black;
+use board::Move;
+use board::Pass;
+use board::Play;
+
+// Writes a game record that Parser can read back. Resignations are
+// not moves in SGF, they only show up in the result.
+pub struct Writer {
+    black: String,
+    height: u8,
+    komi: f32,
+    moves: Vec<Move>,
+    result: String,
+    white: String,
+    width: u8,
+}
+
+impl Writer {
+
+    pub fn new(width: u8, height: u8, komi: f32) -> Writer {
+        Writer {
+            black: String::new(),
+            height: height,
+            komi: komi,
+            moves: vec!(),
+            result: String::new(),
+            white: String::new(),
+            width: width,
+        }
+    }
+
+    pub fn set_players(&mut self, black: &str, white: &str) {
+        self.black = String::from(black);
+        self.white = String::from(white);
+    }
+
+    pub fn set_result(&mut self, result: &str) {
+        self.result = String::from(result);
+    }
+
+    pub fn add(&mut self, m: Move) {
+        self.moves.push(m);
+    }
+
+    pub fn to_string(&self) -> String {
+        let size = if self.width == self.height {
+            format!("{}", self.width)
+        } else {
+            format!("{}:{}", self.width, self.height)
+        };
+        let mut s = format!("(;GM[1]FF[4]CA[UTF-8]AP[Iomrascalai]SZ[{}]KM[{}]", size, self.komi);
+        for &(name, ref value) in [("PB", &self.black), ("PW", &self.white), ("RE", &self.result)].iter() {
+            if !value.is_empty() {
+                s.push_str(&format!("{}[{}]", name, Self::escape(value)));
+            }
+        }
+        s.push_str("\n");
+        for m in self.moves.iter() {
+            match *m {
+                Play(color, col, row) => {
+                    let name = if color == Black { "B" } else { "W" };
+                    s.push_str(&format!(";{}[{}{}]", name, Self::letter(col), Self::letter(self.height - row + 1)));
+                },
+                Pass(color) => {
+                    let name = if color == Black { "B" } else { "W" };
+                    s.push_str(&format!(";{}[]", name));
+                },
+                _ => {}
+            }
+        }
+        s.push_str(")\n");
+        s
+    }
+
+    // SGF counts from 'a' at the top, while we count from 1 at the
+    // bottom.
+    fn letter(n: u8) -> char {
+        ('a' as u8 + n - 1) as char
+    }
+
+    fn escape(value: &str) -> String {
+        value.replace("\\", "\\\\").replace("]", "\\]")
+    }
+
+}
@@ -0,0 +1,273 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::MAX_SIZE;
+use board::Move;
+use board::Pass;
+use board::Play;
+use board::Symmetry;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+
+mod test;
+
+// Regions with more empty points than this are too large to read
+// out.
+pub const MAX_REGION_SIZE: usize = 20;
+
+// Long ko fights are cut off at this depth.
+pub const MAX_DEPTH: usize = 40;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    Alive,
+    Dead,
+    Unsettled,
+    Unknown,
+}
+
+impl Status {
+
+    pub fn combine(attack: &Solution, defence: &Solution) -> Status {
+        match (attack.status, defence.status) {
+            (Status::Alive, _)            => Status::Alive,
+            (_, Status::Dead)             => Status::Dead,
+            (Status::Dead, Status::Alive) => Status::Unsettled,
+            _                             => Status::Unknown,
+        }
+    }
+
+}
+
+impl fmt::Display for Status {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            Status::Alive     => "alive",
+            Status::Dead      => "dead",
+            Status::Unsettled => "unsettled",
+            Status::Unknown   => "unknown",
+        };
+        s.fmt(f)
+    }
+
+}
+
+// The result of reading out a region with a given player moving
+// first. The status is either alive, dead or unknown (if the search
+// ran out of nodes) and the move is the one that achieves it for the
+// player moving first.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Solution {
+    pub status: Status,
+    pub best_move: Option<Move>,
+}
+
+// From the point of view of the player to move.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Outcome {
+    Win,
+    Loss,
+    Unknown,
+}
+
+// Reads out the life and death of a group in a small enclosed
+// region with an alpha-beta search (with only win and loss as
+// values) and a transposition table.
+//
+// The attacker wins when the group is captured. The defender wins
+// when the attacker has no legal moves left in the region. Only the
+// defender can pass (i.e. tenuki). Positions repeated through ko
+// are only bounded by MAX_DEPTH.
+//
+// The node limit covers all searches of a solver, so a single solver
+// bounds the work of analysing several groups.
+pub struct Solver {
+    defender: Color,
+    node_limit: usize,
+    nodes: usize,
+    region: Vec<Coord>,
+    table: HashMap<u64, (Outcome, Option<Move>)>,
+    target: Coord,
+}
+
+impl Solver {
+
+    pub fn new(node_limit: usize) -> Solver {
+        Solver {
+            defender: Empty,
+            node_limit: node_limit,
+            nodes: 0,
+            region: Vec::new(),
+            table: HashMap::new(),
+            target: Coord::new(1, 1),
+        }
+    }
+
+    pub fn status(&mut self, board: &Board, target: Coord) -> Result<Status, String> {
+        let (attack, defence) = try!(self.analyse(board, target));
+        Ok(Status::combine(&attack, &defence))
+    }
+
+    // Reads out the group at the target with the attacker and with
+    // the defender moving first.
+    pub fn analyse(&mut self, board: &Board, target: Coord) -> Result<(Solution, Solution), String> {
+        let defender = try!(Self::defender(board, target));
+        let attack = try!(self.solve(board, target, defender.opposite()));
+        let defence = try!(self.solve(board, target, defender));
+        Ok((attack, defence))
+    }
+
+    pub fn solve(&mut self, board: &Board, target: Coord, first: Color) -> Result<Solution, String> {
+        let defender = try!(Self::defender(board, target));
+        if first == Empty {
+            return Err(String::from("The first player can't be empty"));
+        }
+        self.region = try!(Self::region(board, target));
+        self.defender = defender;
+        self.table.clear();
+        self.target = target;
+        let mut start = board.clone();
+        // The defender mustn't pass right after this pass as that
+        // would end the game.
+        let can_pass = start.next_player() == first;
+        if !can_pass {
+            start.play_legal_move(Pass(start.next_player()));
+        }
+        let (outcome, best_move) = self.search(&start, 0, can_pass);
+        let status = match (outcome, first == defender) {
+            (Outcome::Win, true) | (Outcome::Loss, false) => Status::Alive,
+            (Outcome::Loss, true) | (Outcome::Win, false) => Status::Dead,
+            _                                             => Status::Unknown,
+        };
+        Ok(Solution { status: status, best_move: best_move })
+    }
+
+    fn defender(board: &Board, target: Coord) -> Result<Color, String> {
+        if !target.is_inside(board.width(), board.height()) {
+            return Err(format!("{} is not on the board", target.to_gtp()));
+        }
+        match board.color(&target) {
+            Empty => Err(format!("There is no stone at {}", target.to_gtp())),
+            color => Ok(color)
+        }
+    }
+
+    // The empty points and the stones of the defender that can be
+    // reached from the target without crossing a stone of the
+    // attacker. Attacker stones that only have liberties inside the
+    // region are part of it, too.
+    pub fn region(board: &Board, target: Coord) -> Result<Vec<Coord>, String> {
+        let attacker = board.color(&target).opposite();
+        let mut region = HashSet::new();
+        let mut todo = vec!(target);
+        let mut empty = 0;
+        while let Some(c) = todo.pop() {
+            if region.contains(&c) || board.color(&c) == attacker {
+                continue;
+            }
+            if board.color(&c) == Empty {
+                empty += 1;
+                if empty > MAX_REGION_SIZE {
+                    return Err(format!("The region around {} is too large", target.to_gtp()));
+                }
+            }
+            region.insert(c);
+            todo.extend(board.neighbours(c).iter().cloned());
+        }
+        let inside: Vec<Coord> = board.chains().iter()
+            .filter(|chain| chain.color() == attacker)
+            .filter(|chain| chain.liberties().iter().all(|l| region.contains(l)))
+            .filter(|chain| chain.coords().iter().any(|&c| board.neighbours(c).iter().any(|n| region.contains(n))))
+            .flat_map(|chain| chain.coords().iter().cloned())
+            .collect();
+        region.extend(inside);
+        let mut region: Vec<Coord> = region.into_iter().collect();
+        region.sort();
+        Ok(region)
+    }
+
+    fn search(&mut self, board: &Board, depth: usize, can_pass: bool) -> (Outcome, Option<Move>) {
+        let defending = board.next_player() == self.defender;
+        if board.color(&self.target) != self.defender {
+            return (if defending { Outcome::Loss } else { Outcome::Win }, None);
+        }
+        let hash = self.hash(board);
+        if let Some(&result) = self.table.get(&hash) {
+            return result;
+        }
+        if self.nodes >= self.node_limit || depth >= MAX_DEPTH {
+            return (Outcome::Unknown, None);
+        }
+        self.nodes += 1;
+        let mut result = (Outcome::Loss, None);
+        for m in self.moves(board, can_pass) {
+            let mut next = board.clone();
+            next.play_legal_move(m);
+            // Suicide isn't forbidden by all rulesets but never helps.
+            if !m.is_pass() && next.color(&m.coord()) == Empty {
+                continue;
+            }
+            match self.search(&next, depth + 1, true).0 {
+                Outcome::Loss    => {
+                    result = (Outcome::Win, Some(m));
+                    break;
+                },
+                Outcome::Unknown => result = (Outcome::Unknown, None),
+                Outcome::Win     => {},
+            }
+        }
+        if result.0 != Outcome::Unknown && can_pass {
+            self.table.insert(hash, result);
+        }
+        result
+    }
+
+    // The empty points with the most empty neighbours (i.e. the
+    // vital points of the eye space) are tried first.
+    fn moves(&self, board: &Board, can_pass: bool) -> Vec<Move> {
+        let player = board.next_player();
+        let mut candidates: Vec<(usize, Coord)> = self.region.iter()
+            .filter(|c| board.color(c) == Empty)
+            .filter(|c| board.is_legal(Play(player, c.col, c.row)).is_ok())
+            .map(|&c| (board.neighbours(c).iter().filter(|n| board.color(n) == Empty).count(), c))
+            .collect();
+        candidates.sort_by(|a, b| (b.0, a.1).cmp(&(a.0, b.1)));
+        let mut moves: Vec<Move> = candidates.iter().map(|&(_, c)| Play(player, c.col, c.row)).collect();
+        if can_pass && player == self.defender {
+            moves.push(Pass(player));
+        }
+        moves
+    }
+
+    fn hash(&self, board: &Board) -> u64 {
+        let ko = board.ko().map(|c| c.to_index(MAX_SIZE) as u64 + 1).unwrap_or(0);
+        board.symmetric_hash(Symmetry::Identity) ^ ko.wrapping_mul(0x9e3779b97f4a7c15)
+    }
+
+}
@@ -0,0 +1,94 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use board::Black;
+use board::Coord;
+use board::Play;
+use board::White;
+use sgf::test::board_from_sgf;
+use super::Solver;
+use super::Status;
+
+#[test]
+fn a_group_with_two_eyes_is_alive() {
+    let board = board_from_sgf("life-and-death/alive.sgf");
+    let mut solver = Solver::new(10000);
+    assert_eq!(Ok(Status::Alive), solver.status(&board, Coord::from_gtp("B9")));
+}
+
+#[test]
+fn a_group_with_a_straight_two_is_dead() {
+    let board = board_from_sgf("life-and-death/dead.sgf");
+    let mut solver = Solver::new(10000);
+    assert_eq!(Ok(Status::Dead), solver.status(&board, Coord::from_gtp("C9")));
+}
+
+#[test]
+fn a_group_with_a_straight_three_is_unsettled() {
+    let board = board_from_sgf("life-and-death/unsettled.sgf");
+    let mut solver = Solver::new(10000);
+    assert_eq!(Ok(Status::Unsettled), solver.status(&board, Coord::from_gtp("D9")));
+}
+
+#[test]
+fn the_vital_point_of_a_straight_three_kills_and_lives() {
+    let board = board_from_sgf("life-and-death/unsettled.sgf");
+    let mut solver = Solver::new(10000);
+    let attack = solver.solve(&board, Coord::from_gtp("D9"), Black).unwrap();
+    assert_eq!(Status::Dead, attack.status);
+    assert_eq!(Some(Play(Black, 2, 9)), attack.best_move);
+    let defence = solver.solve(&board, Coord::from_gtp("D9"), White).unwrap();
+    assert_eq!(Status::Alive, defence.status);
+    assert_eq!(Some(Play(White, 2, 9)), defence.best_move);
+}
+
+#[test]
+fn the_region_contains_the_eye_space_and_the_group() {
+    let board = board_from_sgf("life-and-death/unsettled.sgf");
+    let region = Solver::region(&board, Coord::from_gtp("D9")).unwrap();
+    assert_eq!(8, region.len());
+    assert!(region.contains(&Coord::from_gtp("A9")));
+    assert!(!region.contains(&Coord::from_gtp("E9")));
+}
+
+#[test]
+fn open_groups_are_rejected() {
+    let board = board_from_sgf("life-and-death/open.sgf");
+    let mut solver = Solver::new(10000);
+    assert!(solver.status(&board, Coord::from_gtp("E5")).is_err());
+}
+
+#[test]
+fn empty_points_are_rejected() {
+    let board = board_from_sgf("life-and-death/alive.sgf");
+    let mut solver = Solver::new(10000);
+    assert!(solver.status(&board, Coord::from_gtp("A9")).is_err());
+}
+
+#[test]
+fn the_status_is_unknown_when_the_node_limit_is_reached() {
+    let board = board_from_sgf("life-and-death/unsettled.sgf");
+    let mut solver = Solver::new(1);
+    assert_eq!(Ok(Status::Unknown), solver.status(&board, Coord::from_gtp("D9")));
+}
@@ -94,6 +94,10 @@ impl Timer {
 
     }
 
+    pub fn set_config(&mut self, config: Arc<Config>) {
+        self.config = config;
+    }
+
     pub fn reset(&mut self) {
         self.main_time_left  = self.main_time;
         self.byo_time_left   = self.byo_time;
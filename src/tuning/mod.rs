@@ -0,0 +1,242 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+// Tunes the numeric parameters of the engine with SPSA
+// (simultaneous perturbation stochastic approximation). In every
+// iteration all parameters are moved up or down at random by the
+// same number of steps, the two resulting configurations play a few
+// games against each other and the parameters move in the direction
+// of the winner.
+
+use config::Config;
+use engine::Budget;
+use patterns::Matcher;
+use selfplay::Match;
+use selfplay::MatchResult;
+use selfplay::Player;
+
+use rand::random;
+use std::sync::Arc;
+
+mod test;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    // The key in the configuration file (see Config::get).
+    pub name: &'static str,
+    pub min: f32,
+    pub max: f32,
+    // The size of the perturbation in the first iteration.
+    pub step: f32,
+    pub integer: bool,
+}
+
+impl Parameter {
+
+    fn new(name: &'static str, min: f32, max: f32, step: f32, integer: bool) -> Parameter {
+        Parameter {
+            name: name,
+            min: min,
+            max: max,
+            step: step,
+            integer: integer,
+        }
+    }
+
+    pub fn get(&self, config: &Config) -> Result<f32, String> {
+        let value = try!(config.get(self.name));
+        value.parse().map_err(|_| format!("{} isn't numeric: {}", self.name, value))
+    }
+
+    // The value is clamped to the range of the parameter and rounded
+    // for integer parameters.
+    pub fn set(&self, config: &mut Config, value: f32) -> Result<(), String> {
+        let value = if self.integer {
+            format!("{}", self.clamp(value).round() as usize)
+        } else {
+            format!("{}", self.clamp(value))
+        };
+        config.set(self.name, &value)
+    }
+
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.max(self.min).min(self.max)
+    }
+
+    // Formats the value the way it's given in the configuration file.
+    pub fn format(&self, value: f32) -> String {
+        if self.integer {
+            format!("{}", self.clamp(value).round() as usize)
+        } else {
+            format!("{:.3}", self.clamp(value))
+        }
+    }
+
+}
+
+pub fn parameters() -> Vec<Parameter> {
+    vec!(
+        Parameter::new("playout.no_self_atari_cutoff", 0.0, 20.0, 2.0, true),
+        Parameter::new("playout.pattern_probability", 0.0, 1.0, 0.05, false),
+        Parameter::new("timer.c", 0.05, 2.0, 0.1, false),
+        Parameter::new("uct.end_of_game_cutoff", 0.0, 0.3, 0.02, false),
+        Parameter::new("uct.priors.capture_many", 0.0, 100.0, 5.0, true),
+        Parameter::new("uct.priors.capture_one", 0.0, 100.0, 5.0, true),
+        Parameter::new("uct.priors.empty", 0.0, 100.0, 5.0, true),
+        Parameter::new("uct.priors.large_patterns", 0.0, 100.0, 5.0, true),
+        Parameter::new("uct.priors.neutral_plays", 0.0, 50.0, 3.0, true),
+        Parameter::new("uct.priors.neutral_wins", 0.0, 50.0, 3.0, true),
+        Parameter::new("uct.priors.patterns", 0.0, 100.0, 5.0, true),
+        Parameter::new("uct.priors.self_atari", 0.0, 100.0, 5.0, true),
+        Parameter::new("uct.priors.semeai", 0.0, 100.0, 5.0, true),
+    )
+}
+
+pub fn parameter(name: &str) -> Option<Parameter> {
+    parameters().into_iter().find(|p| p.name == name)
+}
+
+// The parameters that are tuned when none are given. The timer isn't
+// used when playing with a fixed budget per move and parameters whose
+// feature is switched off in the configuration have no effect.
+pub fn default_parameters(config: &Config) -> Vec<Parameter> {
+    parameters().into_iter()
+        .filter(|p| is_used(p.name, config))
+        .collect()
+}
+
+fn is_used(name: &str, config: &Config) -> bool {
+    match name {
+        "timer.c" => false,
+        "playout.pattern_probability" => config.playout.use_patterns,
+        "uct.priors.empty" => config.uct.priors.use_empty,
+        "uct.priors.large_patterns" => config.uct.priors.use_large_patterns,
+        "uct.priors.patterns" => config.uct.priors.use_patterns,
+        "uct.priors.semeai" => config.uct.priors.use_semeai,
+        _ => true
+    }
+}
+
+pub struct Iteration {
+    pub iteration: usize,
+    // From the point of view of the configuration that was perturbed
+    // in the positive direction.
+    pub result: MatchResult,
+    // The values after the update.
+    pub values: Vec<f32>,
+}
+
+pub struct Spsa {
+    budget: Budget,
+    config: Arc<Config>,
+    games: usize,
+    komi: f32,
+    learning_rate: f32,
+    matcher: Arc<Matcher>,
+    parameters: Vec<Parameter>,
+    size: u8,
+}
+
+impl Spsa {
+
+    pub fn new(config: Arc<Config>, matcher: Arc<Matcher>, parameters: Vec<Parameter>, size: u8, komi: f32, budget: Budget) -> Spsa {
+        Spsa {
+            budget: budget,
+            config: config,
+            games: 2,
+            komi: komi,
+            learning_rate: 1.0,
+            matcher: matcher,
+            parameters: parameters,
+            size: size,
+        }
+    }
+
+    // The number of games per iteration. It should be even so that
+    // both configurations play black equally often.
+    pub fn set_games(&mut self, games: usize) {
+        self.games = games;
+    }
+
+    // With the default of 1.0 a parameter moves at most half a step
+    // in the first iteration.
+    pub fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.learning_rate = learning_rate;
+    }
+
+    pub fn parameters(&self) -> &Vec<Parameter> {
+        &self.parameters
+    }
+
+    // Returns the tuned values, in the order of the parameters. f is
+    // called after every iteration.
+    pub fn run<F: FnMut(&Iteration)>(&self, iterations: usize, mut f: F) -> Result<Vec<f32>, String> {
+        let values: Result<Vec<f32>, String> = self.parameters.iter().map(|p| p.get(&self.config)).collect();
+        let mut values = try!(values);
+        // The usual choices from Spall's "Implementation of the
+        // Simultaneous Perturbation Algorithm for Stochastic
+        // Optimization".
+        let alpha = 0.602;
+        let gamma = 0.101;
+        let stability = iterations as f32 / 10.0;
+        for k in 1..(iterations + 1) {
+            let a = self.learning_rate * ((stability + 1.0) / (k as f32 + stability)).powf(alpha);
+            let c = 1.0 / (k as f32).powf(gamma);
+            let signs: Vec<f32> = self.parameters.iter().map(|_| if random::<bool>() { 1.0 } else { -1.0 }).collect();
+            let players = [
+                Player::new("+", try!(self.perturbed(&values, &signs, c))),
+                Player::new("-", try!(self.perturbed(&values, &signs, -c))),
+            ];
+            let selfplay = Match::new(self.size, self.komi, self.budget, self.matcher.clone());
+            let result = try!(selfplay.play(&players, self.games, |_, _| {}));
+            let r = (result.wins as f32 - result.losses as f32) / self.games as f32;
+            for (i, parameter) in self.parameters.iter().enumerate() {
+                let value = values[i] + parameter.step * a * r * signs[i] / (2.0 * c);
+                values[i] = parameter.clamp(value);
+            }
+            f(&Iteration {
+                iteration: k,
+                result: result,
+                values: values.clone(),
+            });
+        }
+        Ok(values)
+    }
+
+    // The values in the format of the configuration file, one per
+    // line.
+    pub fn config_file(&self, values: &Vec<f32>) -> String {
+        let lines: Vec<String> = self.parameters.iter().zip(values.iter())
+            .map(|(p, &v)| format!("{} = {}\n", p.name, p.format(v)))
+            .collect();
+        lines.concat()
+    }
+
+    fn perturbed(&self, values: &Vec<f32>, signs: &Vec<f32>, c: f32) -> Result<Arc<Config>, String> {
+        let mut config = (*self.config).clone();
+        for (i, parameter) in self.parameters.iter().enumerate() {
+            try!(parameter.set(&mut config, values[i] + c * signs[i] * parameter.step));
+        }
+        Ok(Arc::new(config))
+    }
+
+}
@@ -0,0 +1,115 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+
+#![cfg(test)]
+
+use config::Config;
+use engine::Budget;
+use patterns::Matcher;
+use super::Spsa;
+use super::default_parameters;
+use super::parameter;
+use super::parameters;
+
+use std::sync::Arc;
+
+#[test]
+fn every_parameter_can_be_read_and_written() {
+    for p in parameters().iter() {
+        let mut config = Config::default();
+        let value = p.get(&config).unwrap();
+        p.set(&mut config, value + p.step).unwrap();
+        assert!(p.get(&config).unwrap() != value, "{} didn't change", p.name);
+    }
+}
+
+#[test]
+fn set_clamps_and_rounds() {
+    let mut config = Config::default();
+    let prior = parameter("uct.priors.semeai").unwrap();
+    prior.set(&mut config, 12.6).unwrap();
+    assert_eq!(13, config.uct.priors.semeai);
+    prior.set(&mut config, -5.0).unwrap();
+    assert_eq!(0, config.uct.priors.semeai);
+    let probability = parameter("playout.pattern_probability").unwrap();
+    probability.set(&mut config, 1.5).unwrap();
+    assert_eq!(1.0, config.playout.pattern_probability);
+}
+
+#[test]
+fn parameter_returns_none_for_unknown_names() {
+    assert!(parameter("threads").is_none());
+}
+
+#[test]
+fn every_parameter_is_a_numeric_configuration_key() {
+    let config = Config::default();
+    for p in parameters().iter() {
+        assert!(Config::keys().contains(&p.name), "{} isn't a key", p.name);
+        assert!(p.get(&config).is_ok());
+    }
+}
+
+#[test]
+fn default_parameters_leave_out_the_priors_that_are_switched_off() {
+    let mut config = Config::default();
+    let names = |config: &Config| -> Vec<&'static str> {
+        default_parameters(config).iter().map(|p| p.name).collect()
+    };
+    assert!(!names(&config).contains(&"timer.c"));
+    assert!(!names(&config).contains(&"uct.priors.large_patterns"));
+    assert!(!names(&config).contains(&"uct.priors.patterns"));
+    assert!(!names(&config).contains(&"uct.priors.semeai"));
+    assert!(names(&config).contains(&"uct.priors.empty"));
+    config.uct.priors.use_semeai = true;
+    config.uct.priors.use_empty = false;
+    assert!(names(&config).contains(&"uct.priors.semeai"));
+    assert!(!names(&config).contains(&"uct.priors.empty"));
+}
+
+#[test]
+fn the_tuned_values_can_be_loaded_as_a_configuration_file() {
+    let config = Arc::new(Config::default());
+    let parameters = vec!(parameter("uct.priors.semeai").unwrap(), parameter("playout.pattern_probability").unwrap());
+    let spsa = Spsa::new(config, Arc::new(Matcher::new()), parameters, 5, 0.5, Budget::Playouts(10));
+    let file = spsa.config_file(&vec!(20.2, 0.9));
+    assert_eq!("uct.priors.semeai = 20\nplayout.pattern_probability = 0.900\n", file);
+    let mut loaded = Config::default();
+    loaded.load_str(&file).unwrap();
+    assert_eq!(20, loaded.uct.priors.semeai);
+    assert_eq!(0.9, loaded.playout.pattern_probability);
+}
+
+#[test]
+fn run_calls_back_after_every_iteration() {
+    let config = Arc::new(Config::default());
+    let spsa = Spsa::new(config.clone(), Arc::new(Matcher::new()), default_parameters(&config), 5, 0.5, Budget::Playouts(10));
+    let mut iterations = vec!();
+    let values = spsa.run(2, |iteration| {
+        assert_eq!(2, iteration.result.games());
+        iterations.push(iteration.iteration);
+    }).unwrap();
+    assert_eq!(vec!(1, 2), iterations);
+    for (p, &v) in spsa.parameters().iter().zip(values.iter()) {
+        assert!(p.min <= v && v <= p.max);
+    }
+}
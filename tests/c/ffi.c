@@ -0,0 +1,63 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#include <stdio.h>
+
+#include "iomrascalai.h"
+
+#define CHECK(condition) \
+    if (!(condition)) { \
+        fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+        return 1; \
+    }
+
+int main(void) {
+    IomVertex vertices[81];
+    IomVertex vertex = { 5, 5 };
+    IomVertex move;
+    int owners[81];
+    size_t i;
+
+    CHECK(iom_game_new(0, 0, 6.5) == NULL);
+
+    IomGame *game = iom_game_new(9, 9, 6.5);
+    CHECK(game != NULL);
+    CHECK(iom_next_player(game) == IOM_BLACK);
+    CHECK(iom_legal_moves(game, vertices, 81) == 81);
+
+    CHECK(iom_play(game, IOM_BLACK, vertex) == IOM_OK);
+    CHECK(iom_play(game, IOM_WHITE, vertex) == IOM_ILLEGAL_MOVE);
+    CHECK(iom_legal_moves(game, vertices, 81) == 80);
+    for (i = 0; i < 80; i++) {
+        CHECK(vertices[i].col != 5 || vertices[i].row != 5);
+    }
+
+    CHECK(iom_genmove(game, IOM_WHITE, 100, &move) == IOM_OK);
+    CHECK(iom_next_player(game) == IOM_BLACK);
+    CHECK(iom_legal_moves(game, NULL, 0) == 79);
+
+    CHECK(iom_ownership(game, owners, 81) == 81);
+    CHECK(owners[(5 - 1) * 9 + (5 - 1)] == IOM_BLACK);
+    CHECK(iom_score(game) == -6.5);
+
+    iom_game_free(game);
+    return 0;
+}